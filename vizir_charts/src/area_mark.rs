@@ -7,11 +7,12 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
-use kurbo::BezPath;
+use kurbo::{BezPath, Point};
 use peniko::{Brush, Color};
 use vizir_core::{ColId, InputRef, Mark, MarkId, TableId};
 
 use crate::axis::StrokeStyle;
+use crate::decimate::Decimation;
 use crate::scale::ScaleContinuous;
 
 /// An area mark derived from a table.
@@ -41,6 +42,8 @@ pub struct AreaMarkSpec {
     pub stroke: Option<StrokeStyle>,
     /// Rendering order hint (`vizir_core::Mark::z_index`) for the filled area.
     pub z_index: i32,
+    /// Vertex reduction applied to dense series (both the fill and the outline).
+    pub decimation: Decimation,
 }
 
 impl AreaMarkSpec {
@@ -64,6 +67,7 @@ impl AreaMarkSpec {
             fill: Brush::default(),
             stroke: None,
             z_index: crate::z_order::SERIES_FILL,
+            decimation: Decimation::None,
         }
     }
 
@@ -99,6 +103,12 @@ impl AreaMarkSpec {
         self
     }
 
+    /// Sets the vertex reduction for dense series (see [`Decimation`]).
+    pub fn with_decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
     /// Generates marks for this mark.
     pub fn marks(&self) -> Vec<Mark> {
         let table_id = self.table;
@@ -107,6 +117,7 @@ impl AreaMarkSpec {
         let x_scale = self.x_scale;
         let y_scale = self.y_scale;
         let baseline = self.baseline;
+        let decimation = self.decimation;
 
         let fill = self.fill.clone();
         let area_id = MarkId::from_raw(self.id_base);
@@ -116,26 +127,28 @@ impl AreaMarkSpec {
             .z_index(z_index)
             .path_compute([InputRef::Table { table: table_id }], move |ctx, _| {
                 let n = ctx.table_row_count(table_id).unwrap_or(0);
-                let mut p = BezPath::new();
                 if n == 0 {
-                    return p;
+                    return BezPath::new();
                 }
+                let xs = ctx.table_f64_column(table_id, x_col);
+                let ys = ctx.table_f64_column(table_id, y_col);
+                let mut p = BezPath::with_capacity(decimation.max_points(&x_scale, n) + 3);
 
                 let y0 = y_scale.map(baseline);
                 let mut last_x = x_scale.map(0.0);
 
-                for row in 0..n {
-                    let x = ctx.table_f64(table_id, row, x_col).unwrap_or(0.0);
-                    let y = ctx.table_f64(table_id, row, y_col).unwrap_or(baseline);
-                    let pt = (x_scale.map(x), y_scale.map(y));
-                    last_x = pt.0;
-                    if row == 0 {
-                        p.move_to((pt.0, y0));
-                        p.line_to(pt);
-                    } else {
-                        p.line_to(pt);
+                let point = |row| {
+                    let x = xs.and_then(|c| c.get(row)).unwrap_or(0.0);
+                    let y = ys.and_then(|c| c.get(row)).unwrap_or(baseline);
+                    Point::new(x_scale.map(x), y_scale.map(y))
+                };
+                decimation.for_each_point(&x_scale, n, point, |pt| {
+                    if p.elements().is_empty() {
+                        p.move_to((pt.x, y0));
                     }
-                }
+                    p.line_to(pt);
+                    last_x = pt.x;
+                });
 
                 p.line_to((last_x, y0));
                 p.close_path();
@@ -151,27 +164,33 @@ impl AreaMarkSpec {
             let line_id = MarkId::from_raw(self.id_base + 1);
             let stroke_brush = stroke.brush.clone();
             let stroke_width = stroke.stroke_width;
+            let stroke_style = stroke.to_stroke();
             let line = Mark::builder(line_id)
                 .path()
                 .z_index(z_index.saturating_add(crate::z_order::SERIES_STROKE))
                 .path_compute([InputRef::Table { table: table_id }], move |ctx, _| {
                     let n = ctx.table_row_count(table_id).unwrap_or(0);
-                    let mut p = BezPath::new();
-                    for row in 0..n {
-                        let x = ctx.table_f64(table_id, row, x_col).unwrap_or(0.0);
-                        let y = ctx.table_f64(table_id, row, y_col).unwrap_or(baseline);
-                        let pt = (x_scale.map(x), y_scale.map(y));
-                        if row == 0 {
+                    let xs = ctx.table_f64_column(table_id, x_col);
+                    let ys = ctx.table_f64_column(table_id, y_col);
+                    let mut p = BezPath::with_capacity(decimation.max_points(&x_scale, n));
+                    let point = |row| {
+                        let x = xs.and_then(|c| c.get(row)).unwrap_or(0.0);
+                        let y = ys.and_then(|c| c.get(row)).unwrap_or(baseline);
+                        Point::new(x_scale.map(x), y_scale.map(y))
+                    };
+                    decimation.for_each_point(&x_scale, n, point, |pt| {
+                        if p.elements().is_empty() {
                             p.move_to(pt);
                         } else {
                             p.line_to(pt);
                         }
-                    }
+                    });
                     p
                 })
                 .fill_const(Color::TRANSPARENT)
                 .stroke_brush_const(stroke_brush)
                 .stroke_width_const(stroke_width)
+                .stroke_style_const(stroke_style)
                 .build();
             out.push(line);
         }
@@ -179,3 +198,80 @@ impl AreaMarkSpec {
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::boxed::Box;
+
+    use kurbo::PathEl;
+    use vizir_core::{MarkDiff, MarkPayload, Scene, Table, TableData};
+
+    use super::*;
+    use crate::scale::ScaleLinear;
+
+    #[derive(Debug)]
+    struct Series;
+
+    impl TableData for Series {
+        fn row_count(&self) -> usize {
+            10_000
+        }
+
+        fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+            match col.0 {
+                0 => Some(row as f64 * 0.01),
+                1 => Some((row % 5) as f64 + 1.0),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn decimated_area_closes_on_the_baseline() {
+        let table_id = TableId(1);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys((0..10_000).collect());
+        table.data = Some(Box::new(Series));
+        scene.insert_table(table);
+
+        let spec = AreaMarkSpec::new(
+            1,
+            table_id,
+            ColId(0),
+            ColId(1),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 100.0), (0.0, 100.0))),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 5.0), (100.0, 0.0))),
+        )
+        .with_stroke(StrokeStyle::default())
+        .with_decimation(Decimation::M4);
+        let diffs = scene.tick(spec.marks());
+        let paths: Vec<_> = diffs
+            .iter()
+            .map(|d| match d {
+                MarkDiff::Enter { new, .. } => match &**new {
+                    MarkPayload::Path(p) => p.path.clone(),
+                    _ => panic!("expected path payload"),
+                },
+                _ => panic!("expected enter diffs"),
+            })
+            .collect();
+        let [fill, line] = &paths[..] else {
+            panic!("expected fill and outline paths");
+        };
+
+        // Fill: baseline move, the decimated series, a drop back to the baseline, and a close.
+        let els = fill.elements();
+        assert!(els.len() <= 404 + 3, "emitted {} elements", els.len());
+        assert_eq!(els[0], PathEl::MoveTo(Point::new(0.0, 100.0)));
+        let PathEl::LineTo(end) = els[els.len() - 2] else {
+            panic!("expected a drop to the baseline");
+        };
+        assert!((end.x - 99.99).abs() < 1e-9 && end.y == 100.0);
+        assert_eq!(els[els.len() - 1], PathEl::ClosePath);
+        // The outline follows the same decimated vertices.
+        assert_eq!(line.elements().len(), els.len() - 3);
+    }
+}
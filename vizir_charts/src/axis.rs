@@ -16,10 +16,10 @@ use alloc::vec::Vec;
 #[cfg(not(feature = "std"))]
 use crate::float::FloatExt;
 
-use kurbo::{BezPath, Rect};
+use kurbo::{BezPath, Cap, Dashes, Join, Rect, Stroke};
 use peniko::Brush;
 use peniko::color::palette::css;
-use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline};
+use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline, TextFont};
 
 use crate::format::format_tick_with_step;
 use crate::measure::TextMeasurer;
@@ -29,21 +29,76 @@ use crate::scale::{
 };
 use crate::z_order;
 
-/// A paint + width pair for stroked paths (domain lines, ticks, gridlines).
+/// Paint, width, and dash/cap/join styling for stroked paths (domain lines, ticks, gridlines,
+/// series lines).
 #[derive(Clone, Debug, PartialEq)]
 pub struct StrokeStyle {
     /// Stroke paint.
     pub brush: Brush,
     /// Stroke width in scene coordinates.
     pub stroke_width: f64,
+    /// Dash lengths in alternating on/off order; empty for a solid stroke.
+    pub dash_pattern: Dashes,
+    /// Offset of the first dash.
+    pub dash_offset: f64,
+    /// Cap style for both ends of open subpaths.
+    pub cap: Cap,
+    /// Join style between segments.
+    pub join: Join,
+    /// Limit for miter joins.
+    pub miter_limit: f64,
 }
 
 impl StrokeStyle {
-    /// Convenience for a solid stroke.
+    /// Convenience for a solid stroke (butt caps, miter joins).
     pub fn solid(brush: impl Into<Brush>, stroke_width: f64) -> Self {
+        let defaults = vizir_core::default_stroke_style();
         Self {
             brush: brush.into(),
             stroke_width,
+            dash_pattern: defaults.dash_pattern,
+            dash_offset: defaults.dash_offset,
+            cap: defaults.start_cap,
+            join: defaults.join,
+            miter_limit: defaults.miter_limit,
+        }
+    }
+
+    /// Sets the dash pattern (alternating on/off lengths) and offset.
+    pub fn with_dashes(mut self, offset: f64, pattern: impl IntoIterator<Item = f64>) -> Self {
+        self.dash_offset = offset;
+        self.dash_pattern = pattern.into_iter().collect();
+        self
+    }
+
+    /// Sets the cap style.
+    pub fn with_cap(mut self, cap: Cap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets the join style.
+    pub fn with_join(mut self, join: Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Sets the miter limit.
+    pub fn with_miter_limit(mut self, miter_limit: f64) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Returns the equivalent `kurbo` stroke (for `vizir_core::MarkBuilder::stroke_style_const`).
+    pub fn to_stroke(&self) -> Stroke {
+        Stroke {
+            width: self.stroke_width,
+            join: self.join,
+            miter_limit: self.miter_limit,
+            start_cap: self.cap,
+            end_cap: self.cap,
+            dash_pattern: self.dash_pattern.clone(),
+            dash_offset: self.dash_offset,
         }
     }
 }
@@ -63,10 +118,14 @@ pub struct AxisStyle {
     pub label_fill: Brush,
     /// Font size for tick labels.
     pub label_font_size: f64,
+    /// Font family, weight, style, and line height for tick labels.
+    pub label_font: TextFont,
     /// Fill paint for the axis title.
     pub title_fill: Brush,
     /// Font size for the axis title.
     pub title_font_size: f64,
+    /// Font family, weight, style, and line height for the axis title.
+    pub title_font: TextFont,
 }
 
 impl Default for AxisStyle {
@@ -76,8 +135,10 @@ impl Default for AxisStyle {
             rule: rule.clone(),
             label_fill: rule.brush.clone(),
             label_font_size: 10.0,
+            label_font: TextFont::default(),
             title_fill: rule.brush,
             title_font_size: 11.0,
+            title_font: TextFont::default(),
         }
     }
 }
@@ -92,14 +153,25 @@ pub struct GridStyle {
 impl Default for GridStyle {
     fn default() -> Self {
         Self {
-            stroke: StrokeStyle {
-                brush: Brush::Solid(css::BLACK.with_alpha(40.0 / 255.0)),
-                stroke_width: 1.0,
-            },
+            stroke: StrokeStyle::solid(css::BLACK.with_alpha(40.0 / 255.0), 1.0),
         }
     }
 }
 
+impl GridStyle {
+    /// Sets the gridline stroke style.
+    pub fn with_stroke(mut self, stroke: StrokeStyle) -> Self {
+        self.stroke = stroke;
+        self
+    }
+
+    /// Makes gridlines dashed (alternating on/off lengths, starting at `offset`).
+    pub fn with_dashes(mut self, offset: f64, pattern: impl IntoIterator<Item = f64>) -> Self {
+        self.stroke = self.stroke.with_dashes(offset, pattern);
+        self
+    }
+}
+
 /// Axis orientation, matching Vega’s axis `orient` values.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AxisOrient {
@@ -431,7 +503,9 @@ impl AxisSpec {
 
     /// Measure the thickness this axis needs along its normal direction.
     ///
-    /// This is intended for a measure/arrange layout pass.
+    /// This is intended for a measure/arrange layout pass. The title strip reserves the title's
+    /// measured height (from `measurer`), so a measurer with taller line boxes widens vertical
+    /// axes.
     pub fn measure(&self, measurer: &impl TextMeasurer) -> f64 {
         let tick_extent = if self.ticks {
             self.tick_size.abs()
@@ -450,7 +524,11 @@ impl AxisSpec {
                     let cos = theta.cos().abs();
                     for v in ticks {
                         let label = self.format_tick(v, step);
-                        let (w, h) = measurer.measure(&label, self.style.label_font_size);
+                        let (w, h) = measurer.measure(
+                            &label,
+                            self.style.label_font_size,
+                            &self.style.label_font,
+                        );
                         let rotated_h = sin * w + cos * h;
                         max_label_extent = max_label_extent.max(rotated_h);
                     }
@@ -463,7 +541,8 @@ impl AxisSpec {
                 };
                 let mut out = tick_extent + label_thickness;
                 if let Some(title) = &self.title {
-                    let (_tw, th) = measurer.measure(title, self.style.title_font_size);
+                    let (_tw, th) =
+                        measurer.measure(title, self.style.title_font_size, &self.style.title_font);
                     out += self.title_offset.max(0.0) + th;
                 }
                 out
@@ -478,7 +557,11 @@ impl AxisSpec {
                     let cos = theta.cos().abs();
                     for v in ticks {
                         let label = self.format_tick(v, step);
-                        let (w, h) = measurer.measure(&label, self.style.label_font_size);
+                        let (w, h) = measurer.measure(
+                            &label,
+                            self.style.label_font_size,
+                            &self.style.label_font,
+                        );
                         let rotated_w = cos * w + sin * h;
                         max_label_extent = max_label_extent.max(rotated_w);
                     }
@@ -490,9 +573,11 @@ impl AxisSpec {
                     0.0
                 };
                 let mut out = tick_extent + label_thickness;
-                if self.title.is_some() {
+                if let Some(title) = &self.title {
                     // With a rotated title, height maps to width.
-                    out += self.title_offset.max(0.0) + self.style.title_font_size;
+                    let (_tw, th) =
+                        measurer.measure(title, self.style.title_font_size, &self.style.title_font);
+                    out += self.title_offset.max(0.0) + th;
                 }
                 out
             }
@@ -571,8 +656,7 @@ impl AxisSpec {
                 &ticks_in_plot,
                 tick_x,
                 plot,
-                &grid.stroke.brush,
-                grid.stroke.stroke_width,
+                &grid.stroke,
                 z_order::GRID_LINES,
             ));
         }
@@ -585,8 +669,7 @@ impl AxisSpec {
             out.push(domain_mark(
                 self.id_base,
                 domain,
-                &self.style.rule.brush,
-                self.style.rule.stroke_width,
+                &self.style.rule,
                 z_order::AXIS_RULES,
             ));
         }
@@ -607,8 +690,7 @@ impl AxisSpec {
                     self.id_base,
                     i,
                     tick,
-                    &self.style.rule.brush,
-                    self.style.rule.stroke_width,
+                    &self.style.rule,
                     z_order::AXIS_RULES,
                 ));
             }
@@ -661,6 +743,7 @@ impl AxisSpec {
                         .text_baseline(TextBaseline::Hanging)
                         .angle_const(self.label_angle)
                         .font_size_const(self.style.label_font_size)
+                        .font_const(&self.style.label_font)
                         .fill_brush_const(self.style.label_fill.clone())
                         .build(),
                 );
@@ -680,6 +763,7 @@ impl AxisSpec {
                     .y_const(y)
                     .text_const(title.clone())
                     .font_size_const(self.style.title_font_size)
+                    .font_const(&self.style.title_font)
                     .fill_brush_const(self.style.title_fill.clone())
                     .text_anchor_middle()
                     .text_baseline(TextBaseline::Hanging)
@@ -738,8 +822,7 @@ impl AxisSpec {
                 &ticks_in_plot,
                 tick_x,
                 plot,
-                &grid.stroke.brush,
-                grid.stroke.stroke_width,
+                &grid.stroke,
                 z_order::GRID_LINES,
             ));
         }
@@ -752,8 +835,7 @@ impl AxisSpec {
             out.push(domain_mark(
                 self.id_base,
                 domain,
-                &self.style.rule.brush,
-                self.style.rule.stroke_width,
+                &self.style.rule,
                 z_order::AXIS_RULES,
             ));
         }
@@ -774,8 +856,7 @@ impl AxisSpec {
                     self.id_base,
                     i,
                     tick,
-                    &self.style.rule.brush,
-                    self.style.rule.stroke_width,
+                    &self.style.rule,
                     z_order::AXIS_RULES,
                 ));
             }
@@ -818,6 +899,7 @@ impl AxisSpec {
                         .text_baseline(TextBaseline::Ideographic)
                         .angle_const(self.label_angle)
                         .font_size_const(self.style.label_font_size)
+                        .font_const(&self.style.label_font)
                         .fill_brush_const(self.style.label_fill.clone())
                         .build(),
                 );
@@ -837,6 +919,7 @@ impl AxisSpec {
                     .y_const(y)
                     .text_const(title.clone())
                     .font_size_const(self.style.title_font_size)
+                    .font_const(&self.style.title_font)
                     .fill_brush_const(self.style.title_fill.clone())
                     .text_anchor_middle()
                     .text_baseline(TextBaseline::Ideographic)
@@ -897,8 +980,7 @@ impl AxisSpec {
                 &ticks_in_plot,
                 tick_y,
                 plot,
-                &grid.stroke.brush,
-                grid.stroke.stroke_width,
+                &grid.stroke,
                 z_order::GRID_LINES,
             ));
         }
@@ -911,8 +993,7 @@ impl AxisSpec {
             out.push(domain_mark(
                 self.id_base,
                 domain,
-                &self.style.rule.brush,
-                self.style.rule.stroke_width,
+                &self.style.rule,
                 z_order::AXIS_RULES,
             ));
         }
@@ -932,8 +1013,7 @@ impl AxisSpec {
                     self.id_base,
                     i,
                     tick,
-                    &self.style.rule.brush,
-                    self.style.rule.stroke_width,
+                    &self.style.rule,
                     z_order::AXIS_RULES,
                 ));
             }
@@ -950,6 +1030,7 @@ impl AxisSpec {
                         .text_baseline(TextBaseline::Middle)
                         .angle_const(self.label_angle)
                         .font_size_const(self.style.label_font_size)
+                        .font_const(&self.style.label_font)
                         .fill_brush_const(self.style.label_fill.clone())
                         .build(),
                 );
@@ -973,6 +1054,7 @@ impl AxisSpec {
                     .y_const(y)
                     .text_const(title.clone())
                     .font_size_const(self.style.title_font_size)
+                    .font_const(&self.style.title_font)
                     .fill_brush_const(self.style.title_fill.clone())
                     .text_anchor_middle()
                     .angle_const(-90.0)
@@ -1031,8 +1113,7 @@ impl AxisSpec {
                 &ticks_in_plot,
                 tick_y,
                 plot,
-                &grid.stroke.brush,
-                grid.stroke.stroke_width,
+                &grid.stroke,
                 z_order::GRID_LINES,
             ));
         }
@@ -1045,8 +1126,7 @@ impl AxisSpec {
             out.push(domain_mark(
                 self.id_base,
                 domain,
-                &self.style.rule.brush,
-                self.style.rule.stroke_width,
+                &self.style.rule,
                 z_order::AXIS_RULES,
             ));
         }
@@ -1066,8 +1146,7 @@ impl AxisSpec {
                     self.id_base,
                     i,
                     tick,
-                    &self.style.rule.brush,
-                    self.style.rule.stroke_width,
+                    &self.style.rule,
                     z_order::AXIS_RULES,
                 ));
             }
@@ -1084,6 +1163,7 @@ impl AxisSpec {
                         .text_baseline(TextBaseline::Middle)
                         .angle_const(self.label_angle)
                         .font_size_const(self.style.label_font_size)
+                        .font_const(&self.style.label_font)
                         .fill_brush_const(self.style.label_fill.clone())
                         .build(),
                 );
@@ -1102,6 +1182,7 @@ impl AxisSpec {
                     .y_const(y)
                     .text_const(title.clone())
                     .font_size_const(self.style.title_font_size)
+                    .font_const(&self.style.title_font)
                     .fill_brush_const(self.style.title_fill.clone())
                     .text_anchor_middle()
                     .angle_const(90.0)
@@ -1113,13 +1194,7 @@ impl AxisSpec {
     }
 }
 
-fn domain_mark(
-    id_base: u64,
-    path: BezPath,
-    stroke: &Brush,
-    stroke_width: f64,
-    z_index: i32,
-) -> Mark {
+fn domain_mark(id_base: u64, path: BezPath, stroke: &StrokeStyle, z_index: i32) -> Mark {
     let mut it = path.into_iter();
     let (x0, y0) = match it.next() {
         Some(kurbo::PathEl::MoveTo(p)) => (p.x, p.y),
@@ -1130,7 +1205,7 @@ fn domain_mark(
         _ => (x0, y0),
     };
     RuleMarkSpec::new(MarkId::from_raw(id_base), x0, y0, x1, y1)
-        .with_stroke(stroke.clone(), stroke_width)
+        .with_stroke_style(stroke)
         .with_z_index(z_index)
         .mark()
 }
@@ -1140,8 +1215,7 @@ fn grid_vertical(
     ticks: &[f64],
     map: impl Fn(f64) -> f64,
     plot: Rect,
-    stroke: &Brush,
-    stroke_width: f64,
+    stroke: &StrokeStyle,
     z_index: i32,
 ) -> Vec<Mark> {
     let base = id_base.wrapping_sub(5_000);
@@ -1150,7 +1224,7 @@ fn grid_vertical(
         let x = map(v);
         out.push(
             RuleMarkSpec::vertical(MarkId::from_raw(base + i as u64), x, plot.y0, plot.y1)
-                .with_stroke(stroke.clone(), stroke_width)
+                .with_stroke_style(stroke)
                 .with_z_index(z_index)
                 .mark(),
         );
@@ -1163,8 +1237,7 @@ fn grid_horizontal(
     ticks: &[f64],
     map: impl Fn(f64) -> f64,
     plot: Rect,
-    stroke: &Brush,
-    stroke_width: f64,
+    stroke: &StrokeStyle,
     z_index: i32,
 ) -> Vec<Mark> {
     let base = id_base.wrapping_sub(5_000);
@@ -1173,7 +1246,7 @@ fn grid_horizontal(
         let y = map(v);
         out.push(
             RuleMarkSpec::horizontal(MarkId::from_raw(base + i as u64), y, plot.x0, plot.x1)
-                .with_stroke(stroke.clone(), stroke_width)
+                .with_stroke_style(stroke)
                 .with_z_index(z_index)
                 .mark(),
         );
@@ -1185,8 +1258,7 @@ fn tick_mark(
     id_base: u64,
     index: usize,
     path: BezPath,
-    stroke: &Brush,
-    stroke_width: f64,
+    stroke: &StrokeStyle,
     z_index: i32,
 ) -> Mark {
     let mut it = path.into_iter();
@@ -1199,7 +1271,7 @@ fn tick_mark(
         _ => (x0, y0),
     };
     RuleMarkSpec::new(MarkId::from_raw(id_base + 1 + index as u64), x0, y0, x1, y1)
-        .with_stroke(stroke.clone(), stroke_width)
+        .with_stroke_style(stroke)
         .with_z_index(z_index)
         .mark()
 }
@@ -1345,6 +1417,66 @@ mod tests {
         assert!((title_x - expected).abs() < 1e-9);
     }
 
+    #[test]
+    fn vertical_axis_title_strip_uses_measured_title_height() {
+        /// Like the heuristic, but with a line box 1.5x the font size.
+        struct TallMeasurer;
+
+        impl TextMeasurer for TallMeasurer {
+            fn measure(&self, text: &str, font_size: f64, font: &TextFont) -> (f64, f64) {
+                let (w, _) = HeuristicTextMeasurer.measure(text, font_size, font);
+                (w, 1.5 * font_size)
+            }
+        }
+
+        let plot = Rect::new(100.0, 0.0, 200.0, 100.0);
+        for axis in [
+            AxisSpec::left(1, ScaleLinearSpec::new((0.0, 10.0))),
+            AxisSpec::right(1, ScaleLinearSpec::new((0.0, 10.0))),
+        ] {
+            let axis = axis
+                .with_tick_count(3)
+                .with_tick_size(6.0)
+                .with_labels(false)
+                .with_title("Y")
+                .with_title_offset(10.0);
+            let title_font_size = axis.style.title_font_size;
+
+            // Ticks (6) + `title_offset` (10) + the measured title height, not the font size.
+            let w = axis.measure(&TallMeasurer);
+            assert_eq!(w, 6.0 + 10.0 + 1.5 * title_font_size);
+            assert_eq!(
+                w - axis.measure(&HeuristicTextMeasurer),
+                0.5 * title_font_size
+            );
+
+            // The title stays anchored to the outer edge, so the extra height widens the gap to
+            // the ticks instead of pushing the title into them.
+            let (axis_rect, expected_x) = match axis.orient {
+                AxisOrient::Left => {
+                    let r = Rect::new(plot.x0 - w, plot.y0, plot.x0, plot.y1);
+                    (r, r.x0 + 0.5 * title_font_size)
+                }
+                _ => {
+                    let r = Rect::new(plot.x1, plot.y0, plot.x1 + w, plot.y1);
+                    (r, r.x1 - 0.5 * title_font_size)
+                }
+            };
+            let title = axis
+                .marks(plot, axis_rect)
+                .into_iter()
+                .find(|m| m.id == MarkId::from_raw(1 + 9000))
+                .expect("missing title");
+            let MarkEncodings::Text(enc) = title.encodings else {
+                panic!("expected a text mark");
+            };
+            let Encoding::Const(x) = enc.x else {
+                panic!("expected a constant x");
+            };
+            assert_eq!(x, expected_x);
+        }
+    }
+
     #[test]
     fn axis_right_title_uses_axis_rect_edge_to_avoid_label_overlap() {
         let measurer = HeuristicTextMeasurer;
@@ -1598,4 +1730,60 @@ mod tests {
             "missing '1000' in {labels:?}"
         );
     }
+
+    #[test]
+    fn dashed_grid_style_reaches_gridline_marks() {
+        let plot = Rect::new(50.0, 30.0, 250.0, 130.0);
+        let axis_rect = Rect::new(0.0, 30.0, 50.0, 130.0);
+        let axis = AxisSpec::left(1, ScaleLinearSpec::new((0.0, 10.0)))
+            .with_grid(GridStyle::default().with_dashes(1.0, [4.0, 2.0]));
+
+        let mut grid_lines = 0;
+        for m in axis.marks(plot, axis_rect) {
+            let MarkEncodings::Path(e) = &m.encodings else {
+                continue;
+            };
+            let Encoding::Const(style) = &e.stroke_style else {
+                panic!("expected a constant stroke style");
+            };
+            if m.z_index == z_order::GRID_LINES {
+                grid_lines += 1;
+                assert_eq!(&style.dash_pattern[..], &[4.0, 2.0]);
+                assert_eq!(style.dash_offset, 1.0);
+            } else {
+                assert!(style.dash_pattern.is_empty(), "axis rule should stay solid");
+            }
+        }
+        assert!(grid_lines > 0);
+    }
+
+    #[test]
+    fn label_font_reaches_marks_and_measure() {
+        use vizir_core::{FontStyle, FontWeight};
+
+        let measurer = HeuristicTextMeasurer;
+        let axis = AxisSpec::left(1, ScaleLinearSpec::new((0.0, 1000.0))).with_tick_count(3);
+        let bold = axis.clone().with_style(AxisStyle {
+            label_font: TextFont::default()
+                .with_family("monospace")
+                .with_weight(FontWeight::BOLD)
+                .with_style(FontStyle::Italic),
+            ..AxisStyle::default()
+        });
+        assert!(bold.measure(&measurer) > axis.measure(&measurer));
+
+        let plot = Rect::new(100.0, 0.0, 200.0, 100.0);
+        let axis_rect = Rect::new(50.0, 0.0, 100.0, 100.0);
+        let mut saw_label = false;
+        for m in bold.marks(plot, axis_rect) {
+            let MarkEncodings::Text(e) = &m.encodings else {
+                continue;
+            };
+            assert!(matches!(&e.font_family, Encoding::Const(f) if f == "monospace"));
+            assert!(matches!(e.font_weight, Encoding::Const(FontWeight::BOLD)));
+            assert!(matches!(e.font_style, Encoding::Const(FontStyle::Italic)));
+            saw_label = true;
+        }
+        assert!(saw_label);
+    }
 }
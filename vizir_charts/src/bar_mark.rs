@@ -7,21 +7,27 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
+use kurbo::RoundedRectRadii;
 use peniko::Brush;
 use vizir_core::{ColId, InputRef, Mark, MarkId, TableId};
 
+use crate::describe::describe_row;
+use crate::rect_mark::{OutlineStyle, with_rect_stroke};
 use crate::scale::{ScaleBand, ScaleContinuous};
 
 /// A vertical bar mark derived from a table.
 ///
 /// This generates one [`vizir_core::MarkKind::Rect`] mark per row key, with bar geometry
-/// derived from a numeric value and a baseline.
+/// derived from a numeric value and a baseline. Each bar is tagged as a `graphics-symbol` and
+/// described by its category label (when [`BarMarkSpec::category`] is set) and value.
 #[derive(Clone, Debug)]
 pub struct BarMarkSpec {
     /// Source table id.
     pub table: TableId,
     /// Column for bar values.
     pub y: ColId,
+    /// Optional column labelling each bar's category, used in the bar description.
+    pub category: Option<ColId>,
     /// Band scale used for bar positions along x.
     pub band: ScaleBand,
     /// Linear scale used for bar positions along y.
@@ -30,6 +36,10 @@ pub struct BarMarkSpec {
     pub baseline: f64,
     /// Fill paint for bars.
     pub fill: Brush,
+    /// Optional outline.
+    pub stroke: Option<OutlineStyle>,
+    /// Per-corner radii (top-left, top-right, bottom-right, bottom-left).
+    pub corner_radius: RoundedRectRadii,
     /// Rendering order hint (`vizir_core::Mark::z_index`).
     pub z_index: i32,
 }
@@ -40,14 +50,23 @@ impl BarMarkSpec {
         Self {
             table,
             y,
+            category: None,
             band,
             y_scale,
             baseline: 0.0,
             fill: Brush::default(),
+            stroke: None,
+            corner_radius: RoundedRectRadii::from_single_radius(0.0),
             z_index: crate::z_order::SERIES_FILL,
         }
     }
 
+    /// Sets the column labelling each bar's category (string, categorical, or numeric).
+    pub fn with_category(mut self, category: ColId) -> Self {
+        self.category = Some(category);
+        self
+    }
+
     /// Sets the baseline in data units.
     pub fn with_baseline(mut self, baseline: f64) -> Self {
         self.baseline = baseline;
@@ -60,6 +79,18 @@ impl BarMarkSpec {
         self
     }
 
+    /// Sets the outline.
+    pub fn with_stroke(mut self, stroke: OutlineStyle) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    /// Sets the corner radius: a single radius, or per-corner `(tl, tr, br, bl)` radii.
+    pub fn with_corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
     /// Sets the z-index used for render ordering.
     pub fn with_z_index(mut self, z_index: i32) -> Self {
         self.z_index = z_index;
@@ -78,41 +109,50 @@ impl BarMarkSpec {
         let baseline = self.baseline;
         let y0 = y_scale.map(baseline);
         let fill = self.fill.clone();
+        let stroke = self.stroke.clone();
+        let corner_radius = self.corner_radius;
         let z_index = self.z_index;
+        let described: Vec<ColId> = self.category.into_iter().chain([y_col]).collect();
 
         row_keys
             .iter()
-            .copied()
-            .enumerate()
-            .map(|(row, row_key)| {
+            .map(|&row_key| {
                 let id = MarkId::for_row(table_id, row_key);
-                Mark::builder(id)
+                let builder = Mark::builder(id)
                     .rect()
                     .z_index(z_index)
-                    .x_const(band.x(row))
+                    .x_compute([InputRef::Table { table: table_id }], move |ctx, _| {
+                        band.x(ctx.table_row_index(table_id, row_key).unwrap_or(0))
+                    })
                     .y_compute(
-                        [InputRef::TableCol {
+                        [InputRef::TableRowCol {
                             table: table_id,
                             col: y_col,
                         }],
                         move |ctx, _| {
-                            let v = ctx.table_f64(table_id, row, y_col).unwrap_or(baseline);
+                            let v = ctx
+                                .table_f64_by_key(table_id, row_key, y_col)
+                                .unwrap_or(baseline);
                             y_scale.map(v).min(y0)
                         },
                     )
                     .w_const(bw)
                     .h_compute(
-                        [InputRef::TableCol {
+                        [InputRef::TableRowCol {
                             table: table_id,
                             col: y_col,
                         }],
                         move |ctx, _| {
-                            let v = ctx.table_f64(table_id, row, y_col).unwrap_or(baseline);
+                            let v = ctx
+                                .table_f64_by_key(table_id, row_key, y_col)
+                                .unwrap_or(baseline);
                             (y_scale.map(v) - y0).abs()
                         },
                     )
                     .fill_brush_const(fill.clone())
-                    .build()
+                    .corner_radius_const(corner_radius);
+                let builder = describe_row(builder, table_id, row_key, described.clone());
+                with_rect_stroke(builder, stroke.as_ref()).build()
             })
             .collect()
     }
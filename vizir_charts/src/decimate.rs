@@ -0,0 +1,254 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Vertex decimation for dense line and area series.
+//!
+//! Both modes run on points already mapped into scene coordinates, inside the path compute
+//! encoding, so they see the resolved x scale. One scene unit is treated as one pixel.
+
+#[cfg(not(feature = "std"))]
+use crate::float::FloatExt;
+
+use kurbo::Point;
+
+use crate::scale::ScaleContinuous;
+
+/// How a line or area series reduces its vertices before building a path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Decimation {
+    /// Emit one vertex per row.
+    #[default]
+    None,
+    /// Per pixel column, keep the first, lowest, highest, and last vertex (M4).
+    ///
+    /// At one scene unit per pixel, the reduced line covers the same pixels as the full line
+    /// (antialiased edges may differ slightly), so this is safe for any dense series. It emits
+    /// at most four vertices per column touched, in row order.
+    M4,
+    /// Largest-Triangle-Three-Buckets, targeting two vertices per pixel of x-scale range.
+    ///
+    /// Preserves the overall shape with fewer vertices than M4, but can drop narrow spikes.
+    Lttb,
+}
+
+impl Decimation {
+    /// Returns an upper bound on the vertices [`Decimation::for_each_point`] emits for `n` rows,
+    /// for reserving path capacity.
+    pub(crate) fn max_points(self, x_scale: &ScaleContinuous, n: usize) -> usize {
+        match self {
+            Self::None => n,
+            // Rows outside the x range land in extra columns, so this is only a hint.
+            Self::M4 => n.min(columns(x_scale).saturating_add(1).saturating_mul(4)),
+            Self::Lttb => n.min(columns(x_scale).saturating_mul(2).max(2)),
+        }
+    }
+
+    /// Calls `emit` with the vertices to draw for `n` rows, where `point(row)` maps a row into
+    /// scene coordinates.
+    pub(crate) fn for_each_point(
+        self,
+        x_scale: &ScaleContinuous,
+        n: usize,
+        point: impl Fn(usize) -> Point,
+        mut emit: impl FnMut(Point),
+    ) {
+        match self {
+            Self::None => (0..n).for_each(|row| emit(point(row))),
+            Self::M4 => m4(n, point, emit),
+            Self::Lttb => lttb(n, columns(x_scale).saturating_mul(2), point, emit),
+        }
+    }
+}
+
+/// Number of pixel columns spanned by the x scale's range.
+fn columns(x_scale: &ScaleContinuous) -> usize {
+    let width = (x_scale.map(x_scale.domain_max()) - x_scale.map(x_scale.domain_min()))
+        .abs()
+        .ceil()
+        .min(1.0e9);
+    #[allow(
+        clippy::cast_possible_truncation,
+        reason = "width is clamped to a non-negative range well below usize::MAX"
+    )]
+    if width.is_finite() { width as usize } else { 0 }
+}
+
+/// Index of the pixel column containing `x`.
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "column indices saturate; plots never span 2^63 pixels"
+)]
+fn column(x: f64) -> i64 {
+    x.floor() as i64
+}
+
+/// Min/max-per-column reduction (Jugel et al., "M4: A Visualization-Oriented Time Series Data
+/// Aggregation"), streamed in row order.
+fn m4(n: usize, point: impl Fn(usize) -> Point, mut emit: impl FnMut(Point)) {
+    // First, lowest, highest, and last (row, point) of the current column.
+    let mut bucket: Option<(i64, [(usize, Point); 4])> = None;
+    for row in 0..n {
+        let p = point(row);
+        let col = column(p.x);
+        match &mut bucket {
+            Some((c, picks)) if *c == col => {
+                if p.y < picks[1].1.y {
+                    picks[1] = (row, p);
+                }
+                if p.y > picks[2].1.y {
+                    picks[2] = (row, p);
+                }
+                picks[3] = (row, p);
+            }
+            _ => {
+                if let Some((_, picks)) = bucket.take() {
+                    emit_picks(picks, &mut emit);
+                }
+                bucket = Some((col, [(row, p); 4]));
+            }
+        }
+    }
+    if let Some((_, picks)) = bucket {
+        emit_picks(picks, &mut emit);
+    }
+}
+
+/// Emits the distinct picks of one M4 column in row order.
+fn emit_picks(mut picks: [(usize, Point); 4], emit: &mut impl FnMut(Point)) {
+    picks.sort_by_key(|(row, _)| *row);
+    let mut last_row = None;
+    for (row, p) in picks {
+        if last_row != Some(row) {
+            emit(p);
+            last_row = Some(row);
+        }
+    }
+}
+
+/// Largest-Triangle-Three-Buckets (Steinarsson, "Downsampling Time Series for Visual
+/// Representation"): keeps the first and last rows, and from each of `threshold - 2` equal row
+/// buckets the vertex forming the largest triangle with the previous pick and the next bucket's
+/// average.
+fn lttb(n: usize, threshold: usize, point: impl Fn(usize) -> Point, mut emit: impl FnMut(Point)) {
+    if threshold < 3 || threshold >= n {
+        (0..n).for_each(|row| emit(point(row)));
+        return;
+    }
+    let buckets = threshold - 2;
+    // Start row of inner bucket `k`; bucket `buckets` starts at the last row. The product is taken
+    // in `u128`: 5M rows across 1600 buckets already overflows a 32-bit `usize`.
+    let start = |k: usize| {
+        let row = k as u128 * (n - 2) as u128 / buckets as u128 + 1;
+        usize::try_from(row).expect("bucket starts never exceed `n`")
+    };
+
+    let mut a = point(0);
+    emit(a);
+    for k in 0..buckets {
+        let (next_start, next_end) = (start(k + 1), start(k + 2).min(n));
+        let mut avg = Point::ZERO;
+        for row in next_start..next_end {
+            avg += point(row).to_vec2();
+        }
+        let count = (next_end - next_start).max(1) as f64;
+        let avg = Point::new(avg.x / count, avg.y / count);
+
+        let mut best = (f64::NEG_INFINITY, a);
+        for row in start(k)..next_start {
+            let p = point(row);
+            let area = ((a.x - avg.x) * (p.y - a.y) - (a.x - p.x) * (avg.y - a.y)).abs();
+            if area > best.0 {
+                best = (area, p);
+            }
+        }
+        a = best.1;
+        emit(a);
+    }
+    emit(point(n - 1));
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::scale::ScaleLinear;
+
+    fn wave(row: usize) -> Point {
+        let x = row as f64 * 0.01;
+        let y = if row.is_multiple_of(97) {
+            100.0
+        } else {
+            (row % 7) as f64
+        };
+        Point::new(x, y)
+    }
+
+    fn collect(mode: Decimation, n: usize) -> Vec<Point> {
+        let x_scale = ScaleContinuous::Linear(ScaleLinear::new((0.0, 1.0), (0.0, 100.0)));
+        let mut out = Vec::new();
+        mode.for_each_point(&x_scale, n, wave, |p| out.push(p));
+        out
+    }
+
+    #[test]
+    fn m4_keeps_column_extremes_in_row_order() {
+        let full = collect(Decimation::None, 10_000);
+        let reduced = collect(Decimation::M4, 10_000);
+        assert_eq!(full.len(), 10_000);
+        // 100 rows per column, at most 4 vertices kept per column.
+        assert!(reduced.len() <= 4 * 100);
+        assert_eq!(reduced.first(), full.first());
+        assert_eq!(reduced.last(), full.last());
+        for col in 0..100 {
+            let in_col = |p: &&Point| column(p.x) == col;
+            let (full_col, reduced_col): (Vec<_>, Vec<_>) = (
+                full.iter().filter(in_col).collect(),
+                reduced.iter().filter(in_col).collect(),
+            );
+            let max = |ps: &[&Point]| ps.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+            let min = |ps: &[&Point]| ps.iter().map(|p| p.y).fold(f64::MAX, f64::min);
+            assert_eq!(max(&full_col), max(&reduced_col));
+            assert_eq!(min(&full_col), min(&reduced_col));
+            assert_eq!(full_col.first(), reduced_col.first());
+            assert_eq!(full_col.last(), reduced_col.last());
+        }
+        assert!(reduced.windows(2).all(|w| w[0].x <= w[1].x));
+    }
+
+    #[test]
+    fn lttb_targets_two_vertices_per_pixel() {
+        let reduced = collect(Decimation::Lttb, 10_000);
+        assert_eq!(reduced.len(), 200);
+        assert_eq!(reduced.first(), Some(&wave(0)));
+        assert_eq!(reduced.last(), Some(&wave(9_999)));
+        // Short series are left alone.
+        assert_eq!(
+            collect(Decimation::Lttb, 150),
+            collect(Decimation::None, 150)
+        );
+    }
+
+    #[test]
+    fn lttb_buckets_large_series_without_overflow() {
+        // 5M samples across 800 px: `k * (n - 2)` exceeds `u32::MAX`.
+        let n = 5_000_000;
+        let mut rows = Vec::new();
+        lttb(
+            n,
+            1_600,
+            |row| Point::new(row as f64, (row % 13) as f64),
+            |p| {
+                rows.push(p.x);
+            },
+        );
+        assert_eq!(rows.len(), 1_600);
+        assert_eq!(rows.first(), Some(&0.0));
+        assert_eq!(rows.last(), Some(&((n - 1) as f64)));
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+        // Each inner pick comes from its own bucket of ~3127 rows.
+        assert!(rows[1] > 0.0 && rows[1] <= 3_126.0);
+    }
+}
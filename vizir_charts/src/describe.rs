@@ -0,0 +1,52 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Default accessibility metadata for data-driven marks.
+//!
+//! Row-keyed mark specs tag each mark with the ARIA `graphics-symbol` role and a description
+//! listing the row's encoded values (e.g. `"Apples, 12"` for a bar), similar to Vega-Lite's
+//! default `description` channel.
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use vizir_core::{ColId, EvalCtx, InputRef, MarkBuilder, TableId};
+
+use crate::format::format_value;
+
+/// ARIA role for marks representing a single datum.
+pub(crate) const DATUM_ROLE: &str = "graphics-symbol";
+
+/// Formats one table cell: string and categorical columns as their label, numbers compactly.
+pub(crate) fn cell_text(ctx: &EvalCtx<'_>, table: TableId, row_key: u64, col: ColId) -> String {
+    if let Some(s) = ctx.table_str_by_key(table, row_key, col) {
+        return s.into();
+    }
+    ctx.table_f64_by_key(table, row_key, col)
+        .map(format_value)
+        .unwrap_or_default()
+}
+
+/// Sets the datum role and a `", "`-joined description of `cols` for the row keyed `row_key`.
+pub(crate) fn describe_row(
+    builder: MarkBuilder,
+    table: TableId,
+    row_key: u64,
+    cols: Vec<ColId>,
+) -> MarkBuilder {
+    let deps: Vec<InputRef> = cols
+        .iter()
+        .map(|&col| InputRef::TableRowCol { table, col })
+        .collect();
+    builder
+        .aria_role_const(DATUM_ROLE)
+        .description_compute(deps, move |ctx, _| {
+            let parts: Vec<String> = cols
+                .iter()
+                .map(|&col| cell_text(ctx, table, row_key, col))
+                .collect();
+            parts.join(", ")
+        })
+}
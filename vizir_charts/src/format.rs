@@ -1,7 +1,7 @@
 // Copyright 2025 the VizIR Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-//! Tick formatting helpers.
+//! Tick and value formatting helpers.
 
 extern crate alloc;
 
@@ -27,6 +27,16 @@ pub(crate) fn format_tick_with_step(v: f64, step: f64) -> String {
     }
 }
 
+/// Formats a data value compactly: rounded to at most six decimals, without trailing zeros.
+pub(crate) fn format_value(v: f64) -> String {
+    if !v.is_finite() {
+        return v.to_string();
+    }
+    let v = round_to_decimals(v, 6);
+    let v = if v == 0.0 { 0.0 } else { v }; // normalize `-0.0`
+    alloc::format!("{v}")
+}
+
 fn decimals_for_step(step: f64) -> usize {
     let step = step.abs();
     if step == 0.0 || !step.is_finite() {
@@ -69,7 +79,7 @@ fn round_to_decimals(x: f64, decimals: usize) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::format_tick_with_step;
+    use super::{format_tick_with_step, format_value};
 
     #[test]
     fn formats_integer_steps_without_decimals() {
@@ -90,4 +100,12 @@ mod tests {
         assert_eq!(format_tick_with_step(-0.0, 2.0), "0");
         assert_eq!(format_tick_with_step(-0.0, 0.2), "0.0");
     }
+
+    #[test]
+    fn formats_values_compactly() {
+        assert_eq!(format_value(12.0), "12");
+        assert_eq!(format_value(0.1 + 0.2), "0.3");
+        assert_eq!(format_value(-0.0), "0");
+        assert_eq!(format_value(-2.5), "-2.5");
+    }
 }
@@ -0,0 +1,245 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Image mark generation.
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use peniko::ImageQuality;
+use vizir_core::{ColId, ImageSource, InputRef, Mark, MarkId, TableId};
+
+use crate::describe::describe_row;
+use crate::scale::ScaleContinuous;
+
+/// An image mark derived from a table (Vega `image`).
+///
+/// This generates one [`vizir_core::MarkKind::Image`] mark per row key. Each image's top-left
+/// corner is placed at the scaled `(x, y)` of its row, matching Vega's default `align: left` and
+/// `baseline: top`. Each image is tagged as a `graphics-symbol` and described by its x and y
+/// values.
+#[derive(Clone, Debug)]
+pub struct ImageMarkSpec {
+    /// Source table id.
+    pub table: TableId,
+    /// Column for x values.
+    pub x: ColId,
+    /// Column for y values.
+    pub y: ColId,
+    /// X scale mapping data x into scene x.
+    pub x_scale: ScaleContinuous,
+    /// Y scale mapping data y into scene y.
+    pub y_scale: ScaleContinuous,
+    /// Image width in scene coordinates.
+    pub width: f64,
+    /// Image height in scene coordinates.
+    pub height: f64,
+    /// Image drawn for every row (or for rows without a `url` value).
+    pub image: ImageSource,
+    /// Optional string column holding a per-row image handle (Vega `url`).
+    pub url: Option<ColId>,
+    /// Sampling quality used when images are scaled.
+    pub quality: ImageQuality,
+    /// Rendering order hint (`vizir_core::Mark::z_index`).
+    pub z_index: i32,
+}
+
+impl ImageMarkSpec {
+    /// Creates an image mark spec with a 16×16 size and an empty image.
+    pub fn new(
+        table: TableId,
+        x: ColId,
+        y: ColId,
+        x_scale: ScaleContinuous,
+        y_scale: ScaleContinuous,
+    ) -> Self {
+        Self {
+            table,
+            x,
+            y,
+            x_scale,
+            y_scale,
+            width: 16.0,
+            height: 16.0,
+            image: ImageSource::default(),
+            url: None,
+            quality: ImageQuality::Medium,
+            z_index: crate::z_order::SERIES_POINTS,
+        }
+    }
+
+    /// Sets the image size.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the image drawn for every row.
+    pub fn with_image(mut self, image: impl Into<ImageSource>) -> Self {
+        self.image = image.into();
+        self
+    }
+
+    /// Reads a per-row image handle from a string column.
+    pub fn with_url(mut self, col: ColId) -> Self {
+        self.url = Some(col);
+        self
+    }
+
+    /// Sets the sampling quality.
+    pub fn with_quality(mut self, quality: ImageQuality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// Sets the z-index used for render ordering.
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Generates marks for the provided row keys.
+    ///
+    /// Mark identity is derived from `(table_id, row_key)` so it stays stable across frames.
+    pub fn marks(&self, row_keys: &[u64]) -> Vec<Mark> {
+        let table_id = self.table;
+        let x_col = self.x;
+        let y_col = self.y;
+        let x_scale = self.x_scale;
+        let y_scale = self.y_scale;
+
+        row_keys
+            .iter()
+            .map(|&row_key| {
+                let builder = Mark::builder(MarkId::for_row(table_id, row_key))
+                    .image()
+                    .z_index(self.z_index)
+                    .x_compute(
+                        [InputRef::TableRowCol {
+                            table: table_id,
+                            col: x_col,
+                        }],
+                        move |ctx, _| {
+                            x_scale.map(
+                                ctx.table_f64_by_key(table_id, row_key, x_col)
+                                    .unwrap_or(0.0),
+                            )
+                        },
+                    )
+                    .y_compute(
+                        [InputRef::TableRowCol {
+                            table: table_id,
+                            col: y_col,
+                        }],
+                        move |ctx, _| {
+                            y_scale.map(
+                                ctx.table_f64_by_key(table_id, row_key, y_col)
+                                    .unwrap_or(0.0),
+                            )
+                        },
+                    )
+                    .w_const(self.width)
+                    .h_const(self.height)
+                    .image_quality_const(self.quality);
+                let builder = describe_row(builder, table_id, row_key, vec![x_col, y_col]);
+                match self.url {
+                    Some(url_col) => {
+                        let fallback = self.image.clone();
+                        builder.image_compute(
+                            [InputRef::TableRowCol {
+                                table: table_id,
+                                col: url_col,
+                            }],
+                            move |ctx, _| match ctx.table_str_by_key(table_id, row_key, url_col) {
+                                Some(url) => ImageSource::Handle(url.into()),
+                                None => fallback.clone(),
+                            },
+                        )
+                    }
+                    None => builder.image_const(self.image.clone()),
+                }
+                .build()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::boxed::Box;
+    use alloc::vec;
+
+    use vizir_core::{MarkDiff, MarkPayload, Scene, Table, TableData};
+
+    use super::*;
+    use crate::scale::ScaleLinear;
+
+    #[derive(Debug)]
+    struct Thumbs;
+
+    impl TableData for Thumbs {
+        fn row_count(&self) -> usize {
+            2
+        }
+
+        fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+            match col.0 {
+                0 => Some(row as f64),
+                1 => Some(10.0),
+                _ => None,
+            }
+        }
+
+        fn str(&self, row: usize, col: ColId) -> Option<&str> {
+            (col.0 == 2 && row == 0).then_some("thumb-0.png")
+        }
+    }
+
+    #[test]
+    fn url_column_selects_per_row_images() {
+        let table_id = TableId(1);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys(vec![0, 1]);
+        table.data = Some(Box::new(Thumbs));
+        scene.insert_table(table);
+
+        let spec = ImageMarkSpec::new(
+            table_id,
+            ColId(0),
+            ColId(1),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 1.0), (0.0, 100.0))),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 10.0), (0.0, 50.0))),
+        )
+        .with_size(8.0, 6.0)
+        .with_image("placeholder.png")
+        .with_url(ColId(2));
+
+        let diffs = scene.tick(spec.marks(&[0, 1]));
+        let mut images: Vec<_> = diffs
+            .iter()
+            .filter_map(|d| match d {
+                MarkDiff::Enter { new, .. } => match &**new {
+                    MarkPayload::Image(i) => Some(i.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        images.sort_by(|a, b| a.rect.x0.total_cmp(&b.rect.x0));
+
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].rect, kurbo::Rect::new(0.0, 50.0, 8.0, 56.0));
+        assert_eq!(images[0].image, ImageSource::Handle("thumb-0.png".into()));
+        assert_eq!(images[1].rect.x0, 100.0);
+        assert_eq!(
+            images[1].image,
+            ImageSource::Handle("placeholder.png".into())
+        );
+    }
+}
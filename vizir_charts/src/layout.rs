@@ -12,6 +12,7 @@
 //! out of `vizir_core`.
 
 use kurbo::Rect;
+use vizir_core::TextFont;
 
 use crate::measure::TextMeasurer;
 
@@ -272,6 +273,8 @@ impl ChartLayout {
     }
 
     /// Convenience helper to compute a left-axis thickness using the provided measurer.
+    ///
+    /// Labels are measured with the default [`TextFont`].
     pub fn measure_axis_left(
         measurer: &impl TextMeasurer,
         tick_labels: &[&str],
@@ -282,13 +285,15 @@ impl ChartLayout {
     ) -> f64 {
         let mut max_w = 0.0_f64;
         for s in tick_labels {
-            let (w, _h) = measurer.measure(s, font_size);
+            let (w, _h) = measurer.measure(s, font_size, &TextFont::default());
             max_w = max_w.max(w);
         }
         tick_size.abs() + tick_padding.max(0.0) + label_padding.max(0.0) + max_w
     }
 
     /// Convenience helper to compute a bottom-axis thickness using the provided measurer.
+    ///
+    /// Labels are measured with the default [`TextFont`].
     pub fn measure_axis_bottom(
         measurer: &impl TextMeasurer,
         tick_size: f64,
@@ -296,7 +301,7 @@ impl ChartLayout {
         label_padding: f64,
         font_size: f64,
     ) -> f64 {
-        let (_w, h) = measurer.measure("Mg", font_size);
+        let (_w, h) = measurer.measure("Mg", font_size, &TextFont::default());
         tick_size.abs() + tick_padding.max(0.0) + label_padding.max(0.0) + h
     }
 }
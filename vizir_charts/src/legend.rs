@@ -14,10 +14,11 @@ use alloc::vec::Vec;
 use kurbo::Rect;
 use peniko::color::palette::css;
 use peniko::{Brush, Color};
-use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline};
+use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline, TextFont};
 
 use crate::layout::Size;
 use crate::measure::TextMeasurer;
+use crate::rect_mark::{OutlineStyle, with_rect_stroke};
 use crate::z_order;
 
 fn union_rect(a: Rect, b: Rect) -> Rect {
@@ -81,6 +82,10 @@ pub struct LegendSwatches {
     pub y: f64,
     /// Swatch square size.
     pub swatch_size: f64,
+    /// Swatch corner radius (`swatch_size / 2` gives circular swatches).
+    pub swatch_corner_radius: f64,
+    /// Optional swatch outline.
+    pub swatch_stroke: Option<OutlineStyle>,
     /// Vertical gap between rows.
     pub row_gap: f64,
     /// Horizontal gap between swatch and label.
@@ -93,6 +98,8 @@ pub struct LegendSwatches {
     pub column_gap: f64,
     /// Label font size.
     pub font_size: f64,
+    /// Label font family, weight, style, and line height.
+    pub font: TextFont,
     /// Label color.
     pub text_fill: Brush,
     /// Items in display order.
@@ -116,17 +123,16 @@ impl LegendSwatches {
             let label_y = y + row_height * 0.5;
 
             // Swatch.
-            out.push(
-                Mark::builder(MarkId::from_raw(self.id_base + i as u64))
-                    .rect()
-                    .z_index(z_order::LEGEND_SWATCHES)
-                    .x_const(x)
-                    .y_const(swatch_y)
-                    .w_const(self.swatch_size)
-                    .h_const(self.swatch_size)
-                    .fill_brush_const(item.fill.clone())
-                    .build(),
-            );
+            let swatch = Mark::builder(MarkId::from_raw(self.id_base + i as u64))
+                .rect()
+                .z_index(z_order::LEGEND_SWATCHES)
+                .x_const(x)
+                .y_const(swatch_y)
+                .w_const(self.swatch_size)
+                .h_const(self.swatch_size)
+                .fill_brush_const(item.fill.clone())
+                .corner_radius_const(self.swatch_corner_radius);
+            out.push(with_rect_stroke(swatch, self.swatch_stroke.as_ref()).build());
 
             // Label.
             out.push(
@@ -137,6 +143,7 @@ impl LegendSwatches {
                     .y_const(label_y)
                     .text_const(item.label.clone())
                     .font_size_const(self.font_size)
+                    .font_const(&self.font)
                     .fill_brush_const(self.text_fill.clone())
                     .text_anchor(TextAnchor::Start)
                     .text_baseline(TextBaseline::Middle)
@@ -192,11 +199,14 @@ impl LegendSwatches {
                     let vizir_core::Encoding::Const(baseline) = enc.baseline else {
                         continue;
                     };
-                    let (w, h) = measurer.measure(text, font_size);
+                    let (w, h) = measurer.measure(text, font_size, &self.font);
                     text_bounds(x, y, (w, h), anchor, baseline)
                 }
-                vizir_core::MarkEncodings::Path(_enc) => {
-                    // This legend doesn't currently emit paths.
+                vizir_core::MarkEncodings::Path(_)
+                | vizir_core::MarkEncodings::Group(_)
+                | vizir_core::MarkEncodings::Image(_)
+                | vizir_core::MarkEncodings::Instances(_) => {
+                    // This legend doesn't currently emit paths, groups, images, or instances.
                     continue;
                 }
             };
@@ -221,6 +231,10 @@ pub struct LegendSwatchesSpec {
     pub id_base: u64,
     /// Swatch square size.
     pub swatch_size: f64,
+    /// Swatch corner radius (`swatch_size / 2` gives circular swatches).
+    pub swatch_corner_radius: f64,
+    /// Optional swatch outline.
+    pub swatch_stroke: Option<OutlineStyle>,
     /// Vertical gap between rows.
     pub row_gap: f64,
     /// Horizontal gap between swatch and label.
@@ -233,6 +247,8 @@ pub struct LegendSwatchesSpec {
     pub column_gap: f64,
     /// Label font size.
     pub font_size: f64,
+    /// Label font family, weight, style, and line height.
+    pub font: TextFont,
     /// Label color.
     pub text_fill: Brush,
     /// Items in display order.
@@ -245,11 +261,14 @@ impl LegendSwatchesSpec {
         Self {
             id_base,
             swatch_size: 10.0,
+            swatch_corner_radius: 0.0,
+            swatch_stroke: None,
             row_gap: 6.0,
             label_dx: 6.0,
             columns: 1,
             column_gap: 12.0,
             font_size: 10.0,
+            font: TextFont::default(),
             text_fill: css::BLACK.into(),
             items,
         }
@@ -267,12 +286,30 @@ impl LegendSwatchesSpec {
         self
     }
 
+    /// Set the label font family, weight, style, and line height.
+    pub fn with_font(mut self, font: TextFont) -> Self {
+        self.font = font;
+        self
+    }
+
     /// Set the swatch size.
     pub fn with_swatch_size(mut self, swatch_size: f64) -> Self {
         self.swatch_size = swatch_size;
         self
     }
 
+    /// Set the swatch corner radius.
+    pub fn with_swatch_corner_radius(mut self, radius: f64) -> Self {
+        self.swatch_corner_radius = radius.max(0.0);
+        self
+    }
+
+    /// Set the swatch outline.
+    pub fn with_swatch_stroke(mut self, stroke: OutlineStyle) -> Self {
+        self.swatch_stroke = Some(stroke);
+        self
+    }
+
     /// Sets the number of columns.
     pub fn with_columns(mut self, columns: usize) -> Self {
         self.columns = columns.max(1);
@@ -302,11 +339,14 @@ impl LegendSwatchesSpec {
             x,
             y,
             swatch_size: self.swatch_size,
+            swatch_corner_radius: self.swatch_corner_radius,
+            swatch_stroke: self.swatch_stroke.clone(),
             row_gap: self.row_gap,
             label_dx: self.label_dx,
             columns: self.columns,
             column_gap: self.column_gap,
             font_size: self.font_size,
+            font: self.font.clone(),
             text_fill: self.text_fill.clone(),
             items: self.items.clone(),
         }
@@ -366,4 +406,29 @@ mod tests {
         assert!((b.width() - desired.width).abs() < 1e-6);
         assert!((b.height() - desired.height).abs() < 1e-6);
     }
+
+    #[test]
+    fn swatch_style_reaches_rect_marks() {
+        use vizir_core::{MarkDiff, MarkPayload, Scene};
+
+        let items = vec![LegendItem::solid("A", css::BLACK)];
+        let spec = LegendSwatchesSpec::new(1, items)
+            .with_swatch_corner_radius(5.0)
+            .with_swatch_stroke(OutlineStyle::new(css::WHITE, 1.5));
+
+        let mut scene = Scene::new();
+        let diffs = scene.tick(spec.marks(0.0, 0.0));
+        let swatch = diffs
+            .iter()
+            .find_map(|d| match d {
+                MarkDiff::Enter { new, .. } => match &**new {
+                    MarkPayload::Rect(r) => Some(r.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .expect("swatch rect");
+        assert_eq!(swatch.corner_radii.top_left, 5.0);
+        assert_eq!(swatch.stroke_width, 1.5);
+    }
 }
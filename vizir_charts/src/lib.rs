@@ -22,9 +22,12 @@ mod area_mark;
 mod axis;
 mod bar_mark;
 mod chart_spec;
+mod decimate;
+mod describe;
 #[cfg(not(feature = "std"))]
 mod float;
 mod format;
+mod image_mark;
 mod layout;
 mod legend;
 mod line_mark;
@@ -44,18 +47,21 @@ mod symbol;
 mod text_mark;
 mod time;
 mod title;
+mod view;
 mod z_order;
 
 pub use area_mark::AreaMarkSpec;
 pub use axis::{AxisOrient, AxisSpec, AxisStyle, GridStyle, StrokeStyle};
 pub use bar_mark::BarMarkSpec;
 pub use chart_spec::ChartSpec;
+pub use decimate::Decimation;
+pub use image_mark::ImageMarkSpec;
 pub use layout::{ChartLayout, ChartLayoutSpec, LegendOrient, LegendPlacement, Size};
 pub use legend::{LegendItem, LegendSwatches, LegendSwatchesSpec};
 pub use line_mark::LineMarkSpec;
 pub use measure::{HeuristicTextMeasurer, TextMeasurer};
 pub use point_mark::PointMarkSpec;
-pub use rect_mark::RectMarkSpec;
+pub use rect_mark::{OutlineStyle, RectMarkSpec};
 pub use rule_mark::RuleMarkSpec;
 pub use scale::{
     ScaleBand, ScaleBandSpec, ScaleContinuous, ScaleLinear, ScaleLinearSpec, ScaleLog,
@@ -71,4 +77,5 @@ pub use symbol::Symbol;
 pub use text_mark::TextMarkSpec;
 pub use time::{format_time_seconds, nice_time_ticks_seconds};
 pub use title::TitleSpec;
+pub use view::{PlotViewSpec, pan, settle_x, settle_y, zoom_about};
 pub use z_order::*;
@@ -7,11 +7,12 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
-use kurbo::BezPath;
+use kurbo::{BezPath, Point};
 use peniko::Color;
 use vizir_core::{ColId, InputRef, Mark, MarkId, TableId};
 
 use crate::axis::StrokeStyle;
+use crate::decimate::Decimation;
 use crate::scale::ScaleContinuous;
 
 /// A line mark derived from a table.
@@ -35,6 +36,8 @@ pub struct LineMarkSpec {
     pub stroke: StrokeStyle,
     /// Rendering order hint (`vizir_core::Mark::z_index`).
     pub z_index: i32,
+    /// Vertex reduction applied to dense series.
+    pub decimation: Decimation,
 }
 
 impl LineMarkSpec {
@@ -56,6 +59,7 @@ impl LineMarkSpec {
             y_scale,
             stroke: StrokeStyle::default(),
             z_index: crate::z_order::SERIES_STROKE,
+            decimation: Decimation::None,
         }
     }
 
@@ -65,12 +69,26 @@ impl LineMarkSpec {
         self
     }
 
+    /// Makes the line dashed (alternating on/off lengths, starting at `offset`).
+    ///
+    /// Useful for forecasts, projections, and other "not measured" segments.
+    pub fn with_dashes(mut self, offset: f64, pattern: impl IntoIterator<Item = f64>) -> Self {
+        self.stroke = self.stroke.with_dashes(offset, pattern);
+        self
+    }
+
     /// Sets the z-index used for render ordering.
     pub fn with_z_index(mut self, z_index: i32) -> Self {
         self.z_index = z_index;
         self
     }
 
+    /// Sets the vertex reduction for dense series (see [`Decimation`]).
+    pub fn with_decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
     /// Generates marks for this mark.
     pub fn marks(&self) -> Vec<Mark> {
         let table_id = self.table;
@@ -80,31 +98,113 @@ impl LineMarkSpec {
         let y_scale = self.y_scale;
         let stroke_brush = self.stroke.brush.clone();
         let stroke_width = self.stroke.stroke_width;
+        let stroke_style = self.stroke.to_stroke();
         let z_index = self.z_index;
+        let decimation = self.decimation;
 
         let line = Mark::builder(self.id)
             .path()
             .z_index(z_index)
             .path_compute([InputRef::Table { table: table_id }], move |ctx, _| {
                 let n = ctx.table_row_count(table_id).unwrap_or(0);
-                let mut p = BezPath::new();
-                for row in 0..n {
-                    let x = ctx.table_f64(table_id, row, x_col).unwrap_or(0.0);
-                    let y = ctx.table_f64(table_id, row, y_col).unwrap_or(0.0);
-                    let pt = (x_scale.map(x), y_scale.map(y));
-                    if row == 0 {
+                let xs = ctx.table_f64_column(table_id, x_col);
+                let ys = ctx.table_f64_column(table_id, y_col);
+                let mut p = BezPath::with_capacity(decimation.max_points(&x_scale, n));
+                let point = |row| {
+                    let x = xs.and_then(|c| c.get(row)).unwrap_or(0.0);
+                    let y = ys.and_then(|c| c.get(row)).unwrap_or(0.0);
+                    Point::new(x_scale.map(x), y_scale.map(y))
+                };
+                decimation.for_each_point(&x_scale, n, point, |pt| {
+                    if p.elements().is_empty() {
                         p.move_to(pt);
                     } else {
                         p.line_to(pt);
                     }
-                }
+                });
                 p
             })
             .fill_const(Color::TRANSPARENT)
             .stroke_brush_const(stroke_brush)
             .stroke_width_const(stroke_width)
+            .stroke_style_const(stroke_style)
             .build();
 
         alloc::vec![line]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::boxed::Box;
+
+    use kurbo::PathEl;
+    use vizir_core::{MarkDiff, MarkPayload, Scene, Table, TableData};
+
+    use super::*;
+    use crate::scale::ScaleLinear;
+
+    #[derive(Debug)]
+    struct Series;
+
+    impl TableData for Series {
+        fn row_count(&self) -> usize {
+            10_000
+        }
+
+        fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+            match col.0 {
+                0 => Some(row as f64 * 0.01),
+                1 => Some(if row == 4_321 { 50.0 } else { (row % 7) as f64 }),
+                _ => None,
+            }
+        }
+    }
+
+    fn line_path(decimation: Decimation) -> BezPath {
+        let table_id = TableId(1);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys((0..10_000).collect());
+        table.data = Some(Box::new(Series));
+        scene.insert_table(table);
+
+        let spec = LineMarkSpec::new(
+            MarkId(1),
+            table_id,
+            ColId(0),
+            ColId(1),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 100.0), (0.0, 100.0))),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 50.0), (100.0, 0.0))),
+        )
+        .with_decimation(decimation);
+        let diffs = scene.tick(spec.marks());
+        let [MarkDiff::Enter { new, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
+        };
+        let MarkPayload::Path(p) = &**new else {
+            panic!("expected path payload");
+        };
+        p.path.clone()
+    }
+
+    #[test]
+    fn decimated_line_keeps_endpoints_and_peak() {
+        assert_eq!(line_path(Decimation::None).elements().len(), 10_000);
+
+        for mode in [Decimation::M4, Decimation::Lttb] {
+            let path = line_path(mode);
+            let els = path.elements();
+            assert!(els.len() <= 404, "{mode:?} emitted {} vertices", els.len());
+            assert_eq!(els[0], PathEl::MoveTo(Point::new(0.0, 100.0)));
+            assert!(matches!(els.last(), Some(PathEl::LineTo(p)) if (p.x - 99.99).abs() < 1e-9));
+            // The single spike survives: it is the lowest scene y.
+            assert!(
+                els.iter()
+                    .any(|el| matches!(el, PathEl::LineTo(p) if p.y == 0.0))
+            );
+        }
+    }
+}
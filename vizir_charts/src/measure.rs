@@ -7,25 +7,61 @@
 //! shaping/layout downstream, so guides accept a measurer callback for rough
 //! bounds estimation.
 
+use vizir_core::{FontWeight, TextFont};
+
 /// A minimal text measurement interface used by guide generators.
 ///
 /// This is used by axes/legends to estimate their extents (margins) before the
-/// marks are generated. Callers can plug in a real text measurement backend
-/// (e.g. based on shaping), or use [`HeuristicTextMeasurer`].
-pub trait TextMeasurer {
-    /// Returns `(width, height)` in the same coordinate system as the marks.
-    fn measure(&self, text: &str, font_size: f64) -> (f64, f64);
-}
+/// marks are generated. It is the same trait [`vizir_core::Scene::set_text_measurer`] accepts, so
+/// one measurer can drive both guide layout and text damage.
+pub use vizir_core::TextMeasurer;
 
 /// A tiny heuristic text measurer suitable for demos and early layout.
 ///
-/// It assumes an average glyph width of ~0.6em and height of 1em.
+/// It assumes an average glyph width of ~0.6em (~0.66em for semi-bold and heavier weights) and a
+/// height of 1em for the first line plus one line height per additional line.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct HeuristicTextMeasurer;
 
 impl TextMeasurer for HeuristicTextMeasurer {
-    fn measure(&self, text: &str, font_size: f64) -> (f64, f64) {
-        let width = 0.6 * font_size * text.chars().count() as f64;
-        (width, font_size)
+    fn measure(&self, text: &str, font_size: f64, font: &TextFont) -> (f64, f64) {
+        let glyph_em = if font.weight >= FontWeight::SEMI_BOLD {
+            0.66
+        } else {
+            0.6
+        };
+        let mut lines = 0_usize;
+        let mut max_chars = 0_usize;
+        for line in text.split('\n') {
+            lines += 1;
+            max_chars = max_chars.max(line.chars().count());
+        }
+        let width = glyph_em * font_size * max_chars as f64;
+        let height = font_size + (lines - 1) as f64 * font.line_height * font_size;
+        (width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    #[test]
+    fn heuristic_accounts_for_weight_and_lines() {
+        let measurer = HeuristicTextMeasurer;
+        let regular = TextFont::default();
+        let bold = TextFont::default().with_weight(FontWeight::BOLD);
+
+        let (w, h) = measurer.measure("abcd", 10.0, &regular);
+        assert_eq!((w, h), (24.0, 10.0));
+
+        let (bold_w, _) = measurer.measure("abcd", 10.0, &bold);
+        assert!(bold_w > w);
+
+        let (w2, h2) = measurer.measure("ab\nabcdef", 10.0, &regular.with_line_height(1.5));
+        assert_eq!(w2, 36.0);
+        assert_eq!(h2, 25.0);
     }
 }
@@ -5,18 +5,24 @@
 
 extern crate alloc;
 
+use alloc::vec;
 use alloc::vec::Vec;
 
-use peniko::Brush;
-use vizir_core::{ColId, InputRef, Mark, MarkId, TableId};
+use kurbo::Point;
+use peniko::{Brush, Color};
+use vizir_core::{ColId, InputRef, InstanceData, InstanceShape, Mark, MarkId, TableId};
 
+use crate::describe::{DATUM_ROLE, describe_row};
 use crate::scale::ScaleContinuous;
 use crate::symbol::Symbol;
 
 /// A point mark derived from a table.
 ///
 /// This generates one [`vizir_core::MarkKind::Rect`] mark per row key, using a square as the
-/// point glyph.
+/// point glyph. Each point is tagged as a `graphics-symbol` and described by its x and y values.
+///
+/// For very large tables, [`PointMarkSpec::instanced_mark`] instead emits a single
+/// [`vizir_core::MarkKind::Instances`] mark whose instances are keyed by row key.
 #[derive(Clone, Debug)]
 pub struct PointMarkSpec {
     /// Source table id.
@@ -101,65 +107,268 @@ impl PointMarkSpec {
 
         row_keys
             .iter()
-            .copied()
-            .enumerate()
-            .map(|(row, row_key)| {
+            .map(|&row_key| {
                 let id = MarkId::for_row(table_id, row_key);
-                match symbol {
+                let builder = match symbol {
                     Symbol::Square => Mark::builder(id)
                         .rect()
                         .z_index(z_index)
                         .x_compute(
-                            [InputRef::TableCol {
+                            [InputRef::TableRowCol {
                                 table: table_id,
                                 col: x_col,
                             }],
                             move |ctx, _| {
-                                x_scale.map(ctx.table_f64(table_id, row, x_col).unwrap_or(0.0))
-                                    - size / 2.0
+                                x_scale.map(
+                                    ctx.table_f64_by_key(table_id, row_key, x_col)
+                                        .unwrap_or(0.0),
+                                ) - size / 2.0
                             },
                         )
                         .y_compute(
-                            [InputRef::TableCol {
+                            [InputRef::TableRowCol {
                                 table: table_id,
                                 col: y_col,
                             }],
                             move |ctx, _| {
-                                y_scale.map(ctx.table_f64(table_id, row, y_col).unwrap_or(0.0))
-                                    - size / 2.0
+                                y_scale.map(
+                                    ctx.table_f64_by_key(table_id, row_key, y_col)
+                                        .unwrap_or(0.0),
+                                ) - size / 2.0
                             },
                         )
                         .w_const(size)
                         .h_const(size)
-                        .fill_brush_const(fill.clone())
-                        .build(),
+                        .fill_brush_const(fill.clone()),
                     Symbol::Circle => Mark::builder(id)
                         .path()
                         .z_index(z_index)
                         .path_compute(
                             [
-                                InputRef::TableCol {
+                                InputRef::TableRowCol {
                                     table: table_id,
                                     col: x_col,
                                 },
-                                InputRef::TableCol {
+                                InputRef::TableRowCol {
                                     table: table_id,
                                     col: y_col,
                                 },
                             ],
                             move |ctx, _| {
-                                let x =
-                                    x_scale.map(ctx.table_f64(table_id, row, x_col).unwrap_or(0.0));
-                                let y =
-                                    y_scale.map(ctx.table_f64(table_id, row, y_col).unwrap_or(0.0));
+                                let x = x_scale.map(
+                                    ctx.table_f64_by_key(table_id, row_key, x_col)
+                                        .unwrap_or(0.0),
+                                );
+                                let y = y_scale.map(
+                                    ctx.table_f64_by_key(table_id, row_key, y_col)
+                                        .unwrap_or(0.0),
+                                );
                                 symbol.path(x, y, size)
                             },
                         )
                         .fill_brush_const(fill.clone())
-                        .stroke_width_const(0.0)
-                        .build(),
-                }
+                        .stroke_width_const(0.0),
+                };
+                describe_row(builder, table_id, row_key, vec![x_col, y_col]).build()
             })
             .collect()
     }
+
+    /// Generates a single instance mark with the given id that draws every row of the table.
+    ///
+    /// The instance mark reads all rows of the table on each evaluation, so row inserts and
+    /// removals show up as per-instance diffs ([`vizir_core::InstanceDelta`]) without regenerating
+    /// marks. The delta only names the changed instances; each update still carries the full
+    /// instance arrays. Points carry no per-row descriptions; use
+    /// [`vizir_core::HitIndex::hit_instance`] to find the row under a pointer.
+    ///
+    /// Instances carry one solid color each: a gradient fill is reduced to its middle stop color,
+    /// and an image fill cannot be instanced, so this returns `None` for it (use
+    /// [`PointMarkSpec::marks`] instead).
+    pub fn instanced_mark(&self, id: MarkId) -> Option<Mark> {
+        let table_id = self.table;
+        let x_col = self.x;
+        let y_col = self.y;
+        let x_scale = self.x_scale;
+        let y_scale = self.y_scale;
+        let size = self.size;
+        let color = match &self.fill {
+            Brush::Solid(color) => *color,
+            Brush::Gradient(g) => g
+                .stops
+                .get(g.stops.len() / 2)
+                .map_or(Color::TRANSPARENT, |stop| stop.color.to_alpha_color()),
+            Brush::Image(_) => return None,
+        };
+        let shape = match self.symbol {
+            Symbol::Square => InstanceShape::Square,
+            Symbol::Circle => InstanceShape::Circle,
+        };
+
+        Some(
+            Mark::builder(id)
+                .instances()
+                .z_index(self.z_index)
+                .instances_compute([InputRef::Table { table: table_id }], move |ctx, _| {
+                    let keys = ctx.table_row_keys(table_id).unwrap_or_default();
+                    let xs = ctx.table_f64_column(table_id, x_col);
+                    let ys = ctx.table_f64_column(table_id, y_col);
+                    let mut data = InstanceData::with_capacity(keys.len());
+                    for (row, key) in keys.iter().enumerate() {
+                        let x = xs.and_then(|c| c.get(row)).unwrap_or(0.0);
+                        let y = ys.and_then(|c| c.get(row)).unwrap_or(0.0);
+                        data.push(
+                            *key,
+                            Point::new(x_scale.map(x), y_scale.map(y)),
+                            size,
+                            color,
+                        );
+                    }
+                    data
+                })
+                .instance_shape_const(shape)
+                .aria_role_const(DATUM_ROLE)
+                .build(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::boxed::Box;
+
+    use vizir_core::{HitIndex, MarkDiff, MarkPayload, Scene, Table, TableData, TablePatch};
+
+    use super::*;
+    use crate::scale::ScaleLinear;
+
+    #[derive(Debug)]
+    struct Points;
+
+    impl TableData for Points {
+        fn row_count(&self) -> usize {
+            3
+        }
+
+        fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+            match col.0 {
+                0 => Some(row as f64),
+                1 => Some(0.0),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct Xs(Vec<f64>);
+
+    impl TableData for Xs {
+        fn row_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+            match col.0 {
+                0 => self.0.get(row).copied(),
+                1 => Some(0.0),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn per_row_points_follow_their_key_through_patches() {
+        let table_id = TableId(1);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys(vec![10, 20, 30]);
+        table.data = Some(Box::new(Xs(vec![0.0, 1.0, 2.0])));
+        scene.insert_table(table);
+
+        let spec = PointMarkSpec::new(
+            table_id,
+            ColId(0),
+            ColId(1),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 2.0), (0.0, 200.0))),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 1.0), (0.0, 10.0))),
+        )
+        .with_size(4.0);
+        let build = |_, key| spec.marks(&[key]).remove(0);
+        let _ = scene.tick_table_rows(table_id, |id, key, _row| build(id, key));
+        let x = |scene: &Scene, key| match &scene.marks[&MarkId::for_row(table_id, key)].cache {
+            Some(MarkPayload::Rect(r)) => r.rect.x0,
+            _ => panic!("expected a rect payload"),
+        };
+
+        // Slide the window: the surviving rows shift but are not re-evaluated.
+        let patch = TablePatch::new()
+            .with_deletes([10])
+            .with_inserts([40])
+            .with_data(Box::new(Xs(vec![1.0, 2.0, 3.0])));
+        let diffs = scene.tick_table_patch(table_id, patch, build);
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Exit { id: a, .. }, MarkDiff::Enter { id: b, .. }]
+                if *a == MarkId::for_row(table_id, 10) && *b == MarkId::for_row(table_id, 40)
+        ));
+        assert_eq!(x(&scene, 20), 98.0);
+        assert_eq!(x(&scene, 40), 298.0);
+
+        // Updating a shifted row reads its new position.
+        let patch = TablePatch::new()
+            .with_updates([30])
+            .with_changed_columns([ColId(0)])
+            .with_data(Box::new(Xs(vec![1.0, 2.5, 3.0])));
+        let diffs = scene.tick_table_patch(table_id, patch, build);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(x(&scene, 30), 248.0);
+        assert_eq!(x(&scene, 20), 98.0);
+    }
+
+    #[test]
+    fn instanced_points_are_one_mark_keyed_by_row() {
+        let table_id = TableId(1);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys(vec![10, 20]);
+        table.data = Some(Box::new(Points));
+        scene.insert_table(table);
+
+        let spec = PointMarkSpec::new(
+            table_id,
+            ColId(0),
+            ColId(1),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 2.0), (0.0, 200.0))),
+            ScaleContinuous::Linear(ScaleLinear::new((0.0, 1.0), (0.0, 10.0))),
+        )
+        .with_size(4.0);
+
+        let mut index = HitIndex::new();
+        index.apply(&scene.tick(spec.instanced_mark(MarkId(1))));
+        let hit = index.hit_instance(Point::new(101.0, 1.0)).unwrap();
+        assert_eq!((hit.mark, hit.index, hit.key), (MarkId(1), 1, 20));
+
+        // A new row enters as one instance of the same mark.
+        scene.set_table_row_keys(table_id, vec![10, 20, 30]);
+        let diffs = scene.update();
+        let [MarkDiff::Update { instances, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        let delta = instances.as_deref().unwrap();
+        assert_eq!(delta.entered, [2]);
+        assert!(delta.updated.is_empty() && delta.exited.is_empty());
+
+        // Image fills cannot be instanced.
+        let image = peniko::ImageData {
+            data: peniko::Blob::from(vec![0_u8; 4]),
+            format: peniko::ImageFormat::Rgba8,
+            alpha_type: peniko::ImageAlphaType::Alpha,
+            width: 1,
+            height: 1,
+        };
+        let spec = spec.with_fill(peniko::ImageBrush::new(image));
+        assert!(spec.instanced_mark(MarkId(1)).is_none());
+    }
 }
@@ -3,9 +3,31 @@
 
 //! Rectangle mark generation.
 
-use kurbo::Rect;
+use kurbo::{Rect, RoundedRectRadii};
 use peniko::Brush;
-use vizir_core::{Mark, MarkId};
+use vizir_core::{Mark, MarkBuilder, MarkId};
+
+/// Paint and width for the solid outline of rectangle marks (rects, bars, legend swatches).
+///
+/// Rect marks have no stroke style, so unlike [`crate::StrokeStyle`] this has no dash, cap, or
+/// join settings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutlineStyle {
+    /// Outline paint.
+    pub brush: Brush,
+    /// Outline width in scene coordinates.
+    pub stroke_width: f64,
+}
+
+impl OutlineStyle {
+    /// Creates an outline with the given paint and width.
+    pub fn new(brush: impl Into<Brush>, stroke_width: f64) -> Self {
+        Self {
+            brush: brush.into(),
+            stroke_width,
+        }
+    }
+}
 
 /// A rectangle mark spec.
 #[derive(Clone, Debug)]
@@ -16,6 +38,10 @@ pub struct RectMarkSpec {
     pub rect: Rect,
     /// Fill paint.
     pub fill: Brush,
+    /// Optional outline.
+    pub stroke: Option<OutlineStyle>,
+    /// Per-corner radii (top-left, top-right, bottom-right, bottom-left).
+    pub corner_radius: RoundedRectRadii,
     /// Rendering order hint (`vizir_core::Mark::z_index`).
     pub z_index: i32,
 }
@@ -27,6 +53,8 @@ impl RectMarkSpec {
             id,
             rect,
             fill: Brush::default(),
+            stroke: None,
+            corner_radius: RoundedRectRadii::from_single_radius(0.0),
             z_index: crate::z_order::SERIES_FILL,
         }
     }
@@ -37,6 +65,18 @@ impl RectMarkSpec {
         self
     }
 
+    /// Sets the outline.
+    pub fn with_stroke(mut self, stroke: OutlineStyle) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    /// Sets the corner radius: a single radius, or per-corner `(tl, tr, br, bl)` radii.
+    pub fn with_corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
     /// Sets the z-index used for render ordering.
     pub fn with_z_index(mut self, z_index: i32) -> Self {
         self.z_index = z_index;
@@ -45,7 +85,7 @@ impl RectMarkSpec {
 
     /// Generates the mark.
     pub fn mark(&self) -> Mark {
-        Mark::builder(self.id)
+        let builder = Mark::builder(self.id)
             .rect()
             .z_index(self.z_index)
             .x_const(self.rect.x0)
@@ -53,6 +93,17 @@ impl RectMarkSpec {
             .w_const(self.rect.width())
             .h_const(self.rect.height())
             .fill_brush_const(self.fill.clone())
-            .build()
+            .corner_radius_const(self.corner_radius);
+        with_rect_stroke(builder, self.stroke.as_ref()).build()
+    }
+}
+
+/// Applies an optional outline to a rect mark builder.
+pub(crate) fn with_rect_stroke(builder: MarkBuilder, stroke: Option<&OutlineStyle>) -> MarkBuilder {
+    match stroke {
+        Some(stroke) => builder
+            .stroke_brush_const(stroke.brush.clone())
+            .stroke_width_const(stroke.stroke_width),
+        None => builder,
     }
 }
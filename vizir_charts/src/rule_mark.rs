@@ -6,10 +6,11 @@
 //! A "rule" is a straight line segment (often used for baselines, gridlines, and axis domain
 //! lines). This is a Vega mark type and also a Swift Charts primitive.
 
-use kurbo::BezPath;
+use kurbo::{BezPath, Stroke};
 use peniko::{Brush, Color};
 use vizir_core::{Mark, MarkId};
 
+use crate::axis::StrokeStyle;
 use crate::z_order;
 
 /// A rule mark spec (a stroked line segment).
@@ -29,6 +30,8 @@ pub struct RuleMarkSpec {
     pub stroke: Brush,
     /// Stroke width in scene coordinates.
     pub stroke_width: f64,
+    /// Dashes, caps, and joins (the width is taken from `stroke_width`).
+    pub stroke_style: Stroke,
     /// Rendering order hint (`vizir_core::Mark::z_index`).
     pub z_index: i32,
 }
@@ -44,6 +47,7 @@ impl RuleMarkSpec {
             y1,
             stroke: Brush::default(),
             stroke_width: 1.0,
+            stroke_style: vizir_core::default_stroke_style(),
             z_index: z_order::SERIES_STROKE,
         }
     }
@@ -65,6 +69,14 @@ impl RuleMarkSpec {
         self
     }
 
+    /// Sets paint, width, dashes, caps, and joins from a [`StrokeStyle`].
+    pub fn with_stroke_style(mut self, stroke: &StrokeStyle) -> Self {
+        self.stroke = stroke.brush.clone();
+        self.stroke_width = stroke.stroke_width;
+        self.stroke_style = stroke.to_stroke();
+        self
+    }
+
     /// Sets the z-index used for render ordering.
     pub fn with_z_index(mut self, z_index: i32) -> Self {
         self.z_index = z_index;
@@ -83,6 +95,7 @@ impl RuleMarkSpec {
             .fill_const(Color::TRANSPARENT)
             .stroke_brush_const(self.stroke.clone())
             .stroke_width_const(self.stroke_width)
+            .stroke_style_const(self.stroke_style.clone())
             .build()
     }
 }
@@ -13,7 +13,7 @@ use alloc::vec::Vec;
 #[cfg(not(feature = "std"))]
 use crate::float::FloatExt;
 
-use vizir_core::{ColId, TableData};
+use vizir_core::{ColId, F64Column, TableData};
 
 use crate::time;
 
@@ -145,6 +145,34 @@ impl ScaleLinear {
         r0 + t * (r1 - r0)
     }
 
+    /// Maps a value from range space back into domain space.
+    pub fn invert(&self, y: f64) -> f64 {
+        let (d0, d1) = self.domain;
+        let (r0, r1) = self.range;
+        let denom = r1 - r0;
+        if denom == 0.0 {
+            return d0;
+        }
+        let t = (y - r0) / denom;
+        d0 + t * (d1 - d0)
+    }
+
+    /// Returns a scale over the same range whose mapping is `k * self.map(x) + offset`.
+    ///
+    /// This folds a 1D pan/zoom (in range units) into the domain, e.g. when a view transform
+    /// settles (see [`crate::settle_x`]). A zero `k` leaves the scale unchanged.
+    pub fn rescaled(&self, k: f64, offset: f64) -> Self {
+        if k == 0.0 {
+            return *self;
+        }
+        let (r0, r1) = self.range;
+        let domain = (
+            self.invert((r0 - offset) / k),
+            self.invert((r1 - offset) / k),
+        );
+        Self::new(domain, self.range)
+    }
+
     /// Returns the minimum of the configured domain (as authored).
     pub fn domain_min(&self) -> f64 {
         self.domain.0
@@ -605,19 +633,31 @@ impl ScaleTimeSpec {
 /// Infer a `(min, max)` domain for a numeric column.
 ///
 /// Non-finite values are ignored. Returns `None` if no finite values are present.
+///
+/// Uses [`TableData::f64_slice`] when the column is contiguous, and per-cell reads otherwise.
 pub fn infer_domain_f64(data: &dyn TableData, col: ColId) -> Option<(f64, f64)> {
     let mut min = f64::INFINITY;
     let mut max = f64::NEG_INFINITY;
     let n = data.row_count();
-    for row in 0..n {
-        let Some(v) = data.f64(row, col) else {
-            continue;
-        };
-        if !v.is_finite() {
-            continue;
+    let mut include = |v: f64| {
+        if v.is_finite() {
+            min = min.min(v);
+            max = max.max(v);
+        }
+    };
+    match F64Column::new(data, col) {
+        F64Column::Slice(values) => {
+            for &v in &values[..n.min(values.len())] {
+                include(v);
+            }
+        }
+        column @ F64Column::Cells { .. } => {
+            for row in 0..n {
+                if let Some(v) = column.get(row) {
+                    include(v);
+                }
+            }
         }
-        min = min.min(v);
-        max = max.max(v);
     }
     if min.is_finite() && max.is_finite() {
         Some((min, max))
@@ -642,6 +682,44 @@ mod tests {
         assert!(b < c);
     }
 
+    #[derive(Debug)]
+    struct Values {
+        v: Vec<f64>,
+        contiguous: bool,
+    }
+
+    impl TableData for Values {
+        fn row_count(&self) -> usize {
+            self.v.len()
+        }
+
+        fn f64(&self, row: usize, _col: ColId) -> Option<f64> {
+            self.v.get(row).copied()
+        }
+
+        fn f64_slice(&self, _col: ColId) -> Option<&[f64]> {
+            self.contiguous.then_some(self.v.as_slice())
+        }
+    }
+
+    #[test]
+    fn infer_domain_slice_path_matches_cell_path() {
+        let v = alloc::vec![3.0, f64::NAN, -2.0, 7.5, f64::INFINITY];
+        let cells = Values {
+            v: v.clone(),
+            contiguous: false,
+        };
+        let slice = Values {
+            v,
+            contiguous: true,
+        };
+        assert_eq!(infer_domain_f64(&cells, ColId(0)), Some((-2.0, 7.5)));
+        assert_eq!(
+            infer_domain_f64(&slice, ColId(0)),
+            infer_domain_f64(&cells, ColId(0))
+        );
+    }
+
     #[test]
     fn log_scale_maps_endpoints_to_range() {
         let s = ScaleLog::new((1.0, 100.0), (0.0, 10.0));
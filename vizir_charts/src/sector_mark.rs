@@ -112,6 +112,7 @@ impl SectorMarkSpec {
 
         if let Some(stroke) = self.stroke.clone() {
             builder = builder
+                .stroke_style_const(stroke.to_stroke())
                 .stroke_brush_const(stroke.brush)
                 .stroke_width_const(stroke.stroke_width);
         } else {
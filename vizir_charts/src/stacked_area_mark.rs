@@ -148,6 +148,7 @@ impl StackedAreaMarkSpec {
             let line_id = MarkId::from_raw(self.id_base + 1);
             let stroke_brush = stroke.brush.clone();
             let stroke_width = stroke.stroke_width;
+            let stroke_style = stroke.to_stroke();
             let line = Mark::builder(line_id)
                 .path()
                 .z_index(z_index.saturating_add(crate::z_order::SERIES_STROKE))
@@ -169,6 +170,7 @@ impl StackedAreaMarkSpec {
                 .fill_const(Color::TRANSPARENT)
                 .stroke_brush_const(stroke_brush)
                 .stroke_width_const(stroke_width)
+                .stroke_style_const(stroke_style)
                 .build();
             out.push(line);
         }
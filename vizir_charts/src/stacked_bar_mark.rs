@@ -5,21 +5,28 @@
 
 extern crate alloc;
 
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
+use kurbo::RoundedRectRadii;
 use peniko::Brush;
-use vizir_core::{ColId, InputRef, Mark, MarkId, TableId};
+use vizir_core::{ColId, InputRef, Mark, MarkBuilder, MarkId, TableId};
 
 #[cfg(not(feature = "std"))]
 use crate::float::FloatExt;
 
+use crate::describe::{DATUM_ROLE, cell_text};
+use crate::format::format_value;
+use crate::rect_mark::{OutlineStyle, with_rect_stroke};
 use crate::scale::{ScaleBand, ScaleContinuous};
 
 /// A vertical stacked bar mark derived from a table.
 ///
 /// This generates one [`vizir_core::MarkKind::Rect`] mark per row key, where the vertical span is
 /// read from `y0`/`y1` columns (typically produced by `vizir_transforms::Transform::Stack`).
+/// Each bar is tagged as a `graphics-symbol` and described by its category, series (if any), and
+/// stacked value (`y1 - y0`).
 #[derive(Clone)]
 pub struct StackedBarMarkSpec {
     /// Source table id.
@@ -48,6 +55,10 @@ pub struct StackedBarMarkSpec {
     pub series_fills: Option<Vec<Brush>>,
     /// Fill paint for bars when no `series_fills` palette is set.
     pub fill: Brush,
+    /// Optional outline.
+    pub stroke: Option<OutlineStyle>,
+    /// Per-corner radii (top-left, top-right, bottom-right, bottom-left).
+    pub corner_radius: RoundedRectRadii,
     /// Rendering order hint (`vizir_core::Mark::z_index`).
     pub z_index: i32,
 }
@@ -65,6 +76,8 @@ impl core::fmt::Debug for StackedBarMarkSpec {
             .field("category_index", &"<fn>")
             .field("series_fills", &self.series_fills.as_ref().map(|v| v.len()))
             .field("fill", &self.fill)
+            .field("stroke", &self.stroke)
+            .field("corner_radius", &self.corner_radius)
             .field("z_index", &self.z_index)
             .finish()
     }
@@ -92,6 +105,8 @@ impl StackedBarMarkSpec {
             category_index: Arc::new(move |v| default_index(v, count)),
             series_fills: None,
             fill: Brush::default(),
+            stroke: None,
+            corner_radius: RoundedRectRadii::from_single_radius(0.0),
             z_index: crate::z_order::SERIES_FILL,
         }
     }
@@ -119,6 +134,18 @@ impl StackedBarMarkSpec {
         self
     }
 
+    /// Sets the outline.
+    pub fn with_stroke(mut self, stroke: OutlineStyle) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    /// Sets the corner radius: a single radius, or per-corner `(tl, tr, br, bl)` radii.
+    pub fn with_corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
     /// Sets the z-index used for render ordering.
     pub fn with_z_index(mut self, z_index: i32) -> Self {
         self.z_index = z_index;
@@ -143,23 +170,25 @@ impl StackedBarMarkSpec {
         let series_col = self.series;
         let series_fills = self.series_fills.clone();
         let fill = self.fill.clone();
+        let stroke = self.stroke.clone();
+        let corner_radius = self.corner_radius;
 
         row_keys
             .iter()
-            .copied()
-            .enumerate()
-            .map(|(row, row_key)| {
+            .map(|&row_key| {
                 let id = MarkId::for_row(table_id, row_key);
 
                 let x = {
                     let category_index = category_index.clone();
                     Mark::builder(id).rect().z_index(z_index).x_compute(
-                        [InputRef::TableCol {
+                        [InputRef::TableRowCol {
                             table: table_id,
                             col: cat_col,
                         }],
                         move |ctx, _| {
-                            let cat = ctx.table_f64(table_id, row, cat_col).unwrap_or(0.0);
+                            let cat = ctx
+                                .table_f64_by_key(table_id, row_key, cat_col)
+                                .unwrap_or(0.0);
                             band.x(category_index(cat))
                         },
                     )
@@ -167,36 +196,44 @@ impl StackedBarMarkSpec {
 
                 let y = x.y_compute(
                     [
-                        InputRef::TableCol {
+                        InputRef::TableRowCol {
                             table: table_id,
                             col: y0_col,
                         },
-                        InputRef::TableCol {
+                        InputRef::TableRowCol {
                             table: table_id,
                             col: y1_col,
                         },
                     ],
                     move |ctx, _| {
-                        let a = ctx.table_f64(table_id, row, y0_col).unwrap_or(0.0);
-                        let b = ctx.table_f64(table_id, row, y1_col).unwrap_or(0.0);
+                        let a = ctx
+                            .table_f64_by_key(table_id, row_key, y0_col)
+                            .unwrap_or(0.0);
+                        let b = ctx
+                            .table_f64_by_key(table_id, row_key, y1_col)
+                            .unwrap_or(0.0);
                         y_scale.map(a.max(b))
                     },
                 );
 
                 let h = y.h_compute(
                     [
-                        InputRef::TableCol {
+                        InputRef::TableRowCol {
                             table: table_id,
                             col: y0_col,
                         },
-                        InputRef::TableCol {
+                        InputRef::TableRowCol {
                             table: table_id,
                             col: y1_col,
                         },
                     ],
                     move |ctx, _| {
-                        let a = ctx.table_f64(table_id, row, y0_col).unwrap_or(0.0);
-                        let b = ctx.table_f64(table_id, row, y1_col).unwrap_or(0.0);
+                        let a = ctx
+                            .table_f64_by_key(table_id, row_key, y0_col)
+                            .unwrap_or(0.0);
+                        let b = ctx
+                            .table_f64_by_key(table_id, row_key, y1_col)
+                            .unwrap_or(0.0);
                         (y_scale.map(a) - y_scale.map(b)).abs()
                     },
                 );
@@ -205,12 +242,14 @@ impl StackedBarMarkSpec {
 
                 if let (Some(series_col), Some(series_fills)) = (series_col, series_fills.clone()) {
                     m = m.fill_compute(
-                        [InputRef::TableCol {
+                        [InputRef::TableRowCol {
                             table: table_id,
                             col: series_col,
                         }],
                         move |ctx, _| {
-                            let v = ctx.table_f64(table_id, row, series_col).unwrap_or(0.0);
+                            let v = ctx
+                                .table_f64_by_key(table_id, row_key, series_col)
+                                .unwrap_or(0.0);
                             let i = default_index(v, series_fills.len());
                             series_fills.get(i).cloned().unwrap_or_else(Brush::default)
                         },
@@ -219,12 +258,49 @@ impl StackedBarMarkSpec {
                     m = m.fill_brush_const(fill.clone());
                 }
 
-                m.build()
+                m = m.corner_radius_const(corner_radius);
+                m = describe_stacked(
+                    m,
+                    table_id,
+                    row_key,
+                    [cat_col].into_iter().chain(series_col),
+                    y0_col,
+                    y1_col,
+                );
+                with_rect_stroke(m, stroke.as_ref()).build()
             })
             .collect()
     }
 }
 
+fn describe_stacked(
+    builder: MarkBuilder,
+    table: TableId,
+    row_key: u64,
+    labels: impl Iterator<Item = ColId>,
+    y0: ColId,
+    y1: ColId,
+) -> MarkBuilder {
+    let labels: Vec<ColId> = labels.collect();
+    let deps: Vec<InputRef> = labels
+        .iter()
+        .chain([&y0, &y1])
+        .map(|&col| InputRef::TableRowCol { table, col })
+        .collect();
+    builder
+        .aria_role_const(DATUM_ROLE)
+        .description_compute(deps, move |ctx, _| {
+            let a = ctx.table_f64_by_key(table, row_key, y0).unwrap_or(0.0);
+            let b = ctx.table_f64_by_key(table, row_key, y1).unwrap_or(0.0);
+            let mut parts: Vec<String> = labels
+                .iter()
+                .map(|&col| cell_text(ctx, table, row_key, col))
+                .collect();
+            parts.push(format_value(b - a));
+            parts.join(", ")
+        })
+}
+
 fn default_index(v: f64, count: usize) -> usize {
     if count == 0 {
         return 0;
@@ -69,7 +69,7 @@ fn stacked_bar_uses_category_for_x_and_y0_y1_for_vertical_span() {
 
     let mut scene = Scene::new();
     let mut t = Table::new(table_id);
-    t.row_keys = vec![10, 11];
+    t.set_row_keys(vec![10, 11]);
     t.data = Some(Box::new(StackedValues {
         cat: vec![0.0, 1.0],
         y0: vec![0.0, 2.0],
@@ -89,7 +89,7 @@ fn stacked_bar_uses_category_for_x_and_y0_y1_for_vertical_span() {
         ScaleContinuous::Linear(y_scale),
     )
     .with_fill(css::CORNFLOWER_BLUE)
-    .marks(&scene.tables[&table_id].row_keys);
+    .marks(scene.tables[&table_id].row_keys());
 
     let diffs = scene.tick(marks);
 
@@ -102,6 +102,11 @@ fn stacked_bar_uses_category_for_x_and_y0_y1_for_vertical_span() {
     let id1 = vizir_core::MarkId::for_row(table_id, 11);
     let b1 = find_enter_bounds(&diffs, id1);
     assert_rect_close(b1, Rect::new(20.0, 0.0, 30.0, 60.0));
+
+    // Each bar is described by its category and stacked value.
+    let meta = scene.marks[&id1].cache.as_ref().unwrap().meta();
+    assert_eq!(meta.aria_role, "graphics-symbol");
+    assert_eq!(meta.description, "1, 3");
 }
 
 #[test]
@@ -9,7 +9,7 @@ use alloc::string::String;
 
 use kurbo::Point;
 use peniko::Brush;
-use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline};
+use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline, TextFont};
 
 /// A text mark spec.
 #[derive(Clone, Debug)]
@@ -22,6 +22,8 @@ pub struct TextMarkSpec {
     pub text: String,
     /// Font size in scene coordinates.
     pub font_size: f64,
+    /// Font family, weight, style, and line height.
+    pub font: TextFont,
     /// Text rotation angle in degrees.
     pub angle: f64,
     /// Horizontal anchor.
@@ -42,6 +44,7 @@ impl TextMarkSpec {
             pos,
             text: text.into(),
             font_size: 12.0,
+            font: TextFont::default(),
             angle: 0.0,
             anchor: TextAnchor::Start,
             baseline: TextBaseline::Middle,
@@ -56,6 +59,12 @@ impl TextMarkSpec {
         self
     }
 
+    /// Sets the font family, weight, style, and line height.
+    pub fn with_font(mut self, font: TextFont) -> Self {
+        self.font = font;
+        self
+    }
+
     /// Sets the fill paint.
     pub fn with_fill(mut self, fill: impl Into<Brush>) -> Self {
         self.fill = fill.into();
@@ -95,6 +104,7 @@ impl TextMarkSpec {
             .y_const(self.pos.y)
             .text_const(self.text.clone())
             .font_size_const(self.font_size)
+            .font_const(&self.font)
             .fill_brush_const(self.fill.clone())
             .text_anchor(self.anchor)
             .text_baseline(self.baseline)
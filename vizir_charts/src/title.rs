@@ -14,7 +14,7 @@ use alloc::vec::Vec;
 
 use kurbo::Rect;
 use peniko::Brush;
-use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline};
+use vizir_core::{Mark, MarkId, TextAnchor, TextBaseline, TextFont};
 
 use crate::measure::TextMeasurer;
 use crate::z_order;
@@ -32,6 +32,10 @@ pub struct TitleSpec {
     pub font_size: f64,
     /// Subtitle font size in scene coordinates.
     pub subtitle_font_size: f64,
+    /// Title font family, weight, style, and line height.
+    pub font: TextFont,
+    /// Subtitle font family, weight, style, and line height.
+    pub subtitle_font: TextFont,
     /// Fill paint.
     pub fill: Brush,
     /// Subtitle fill paint.
@@ -57,6 +61,8 @@ impl TitleSpec {
             subtitle: None,
             font_size: 12.0,
             subtitle_font_size: 11.0,
+            font: TextFont::default(),
+            subtitle_font: TextFont::default(),
             fill: Brush::default(),
             subtitle_fill: Brush::default(),
             padding: 6.0,
@@ -70,10 +76,10 @@ impl TitleSpec {
     /// Returns the thickness (height) reserved by this title in chart layout.
     pub fn measure(&self, measurer: &impl TextMeasurer) -> f64 {
         let pad = self.padding.max(0.0);
-        let (_w, h) = measurer.measure(&self.text, self.font_size);
+        let (_w, h) = measurer.measure(&self.text, self.font_size, &self.font);
         let mut total = 2.0 * pad + h;
         if let Some(sub) = &self.subtitle {
-            let (_sw, sh) = measurer.measure(sub, self.subtitle_font_size);
+            let (_sw, sh) = measurer.measure(sub, self.subtitle_font_size, &self.subtitle_font);
             total += self.subtitle_gap.max(0.0) + sh;
         }
         total.max(0.0)
@@ -97,6 +103,18 @@ impl TitleSpec {
         self
     }
 
+    /// Sets the title font family, weight, style, and line height.
+    pub fn with_font(mut self, font: TextFont) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the subtitle font family, weight, style, and line height.
+    pub fn with_subtitle_font(mut self, font: TextFont) -> Self {
+        self.subtitle_font = font;
+        self
+    }
+
     /// Sets the fill paint.
     pub fn with_fill(mut self, fill: impl Into<Brush>) -> Self {
         self.fill = fill.into();
@@ -136,7 +154,7 @@ impl TitleSpec {
         };
 
         let pad = self.padding.max(0.0);
-        let (_tw, th) = measurer.measure(&self.text, self.font_size);
+        let (_tw, th) = measurer.measure(&self.text, self.font_size, &self.font);
 
         let y_title = title_rect.y0 + pad + 0.5 * th;
         let mark = Mark::builder(self.id)
@@ -146,6 +164,7 @@ impl TitleSpec {
             .y_const(y_title)
             .text_const(self.text.clone())
             .font_size_const(self.font_size)
+            .font_const(&self.font)
             .fill_brush_const(self.fill.clone())
             .text_anchor(self.anchor)
             .text_baseline(self.baseline)
@@ -156,7 +175,8 @@ impl TitleSpec {
         out.push(mark);
 
         if let Some(subtitle) = &self.subtitle {
-            let (_sw, sh) = measurer.measure(subtitle, self.subtitle_font_size);
+            let (_sw, sh) =
+                measurer.measure(subtitle, self.subtitle_font_size, &self.subtitle_font);
             let y_sub = y_title + 0.5 * th + self.subtitle_gap.max(0.0) + 0.5 * sh;
             out.push(
                 Mark::builder(MarkId::from_raw(self.id.0.wrapping_add(1)))
@@ -166,6 +186,7 @@ impl TitleSpec {
                     .y_const(y_sub)
                     .text_const(subtitle.clone())
                     .font_size_const(self.subtitle_font_size)
+                    .font_const(&self.subtitle_font)
                     .fill_brush_const(self.subtitle_fill.clone())
                     .text_anchor(self.anchor)
                     .text_baseline(self.baseline)
@@ -0,0 +1,175 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Pan/zoom views over the plot area.
+//!
+//! A [`PlotViewSpec`] is a group mark whose transform reads an [`Affine`] signal. Series marks
+//! parented to it (`Mark::parent`) keep their scale-mapped coordinates, so panning or zooming
+//! only changes the signal: `Scene::update` reports a single group update and re-runs no series
+//! encodings. Once the interaction settles, fold the view into the scales ([`settle_x`],
+//! [`settle_y`]), re-tick the marks, and reset the signal to [`Affine::IDENTITY`].
+//!
+//! Guides (axes, gridlines) are usually left outside the view and rebuilt on settle.
+
+use kurbo::{Affine, Point, Rect, Vec2};
+use vizir_core::{GroupClip, InputRef, Mark, MarkId, SignalId};
+
+use crate::scale::ScaleLinear;
+use crate::z_order;
+
+/// A clipped group that applies a signal-driven view transform to its children.
+///
+/// The view is a plain group transform, so while zoomed it also scales everything its children
+/// draw: stroke widths, point sizes, and text grow or shrink with the zoom factor. They return to
+/// their encoded sizes once the view is settled into the scales and the signal is reset.
+#[derive(Clone, Debug)]
+pub struct PlotViewSpec {
+    /// Stable mark id (use it as the children's parent).
+    pub id: MarkId,
+    /// Signal holding the view transform (an [`Affine`], in plot coordinates).
+    pub signal: SignalId,
+    /// Plot rectangle children are clipped to, in scene coordinates.
+    pub plot: Rect,
+    /// Rendering order hint (`vizir_core::Mark::z_index`).
+    pub z_index: i32,
+}
+
+impl PlotViewSpec {
+    /// Creates a new view over `plot`, driven by `signal`.
+    pub fn new(id: MarkId, signal: SignalId, plot: Rect) -> Self {
+        Self {
+            id,
+            signal,
+            plot,
+            z_index: z_order::SERIES_FILL,
+        }
+    }
+
+    /// Sets the z-index used for render ordering.
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Generates the group mark.
+    ///
+    /// The clip is applied in the group's transformed space, so it is inverted through the view
+    /// to stay fixed on the plot rectangle.
+    pub fn mark(&self) -> Mark {
+        let signal = self.signal;
+        let plot = self.plot;
+        Mark::builder(self.id)
+            .group()
+            .z_index(self.z_index)
+            .transform_signal(signal)
+            .clip_compute([InputRef::Signal { signal }], move |ctx, _| {
+                let view = ctx.signal::<Affine>(signal).unwrap_or(Affine::IDENTITY);
+                let clip = if view.determinant() == 0.0 {
+                    plot
+                } else {
+                    view.inverse().transform_rect_bbox(plot)
+                };
+                Some(GroupClip::Rect(clip))
+            })
+            .build()
+    }
+}
+
+/// Returns `view` followed by a translation of `delta` (in plot coordinates).
+pub fn pan(view: Affine, delta: Vec2) -> Affine {
+    view.then_translate(delta)
+}
+
+/// Returns `view` followed by a uniform zoom of `factor` about `center` (in plot coordinates).
+pub fn zoom_about(view: Affine, center: Point, factor: f64) -> Affine {
+    view.then_scale_about(factor, center)
+}
+
+/// Folds the horizontal part of an axis-aligned `view` into an x scale.
+///
+/// Rotation and skew are ignored.
+pub fn settle_x(scale: &ScaleLinear, view: Affine) -> ScaleLinear {
+    let [a, _, _, _, e, _] = view.as_coeffs();
+    scale.rescaled(a, e)
+}
+
+/// Folds the vertical part of an axis-aligned `view` into a y scale.
+///
+/// Rotation and skew are ignored.
+pub fn settle_y(scale: &ScaleLinear, view: Affine) -> ScaleLinear {
+    let [_, _, _, d, _, f] = view.as_coeffs();
+    scale.rescaled(d, f)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use vizir_core::{ChannelMask, MarkDiff, MarkPayload, Scene};
+
+    use super::*;
+
+    #[test]
+    fn view_changes_update_only_the_group() {
+        let view_id = MarkId::from_raw(1);
+        let signal = SignalId(1);
+        let plot = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let mut scene = Scene::new();
+        scene.insert_signal(signal, Affine::IDENTITY);
+        let marks = [
+            PlotViewSpec::new(view_id, signal, plot).mark(),
+            Mark::builder(MarkId::from_raw(2))
+                .parent(view_id)
+                .x_const(10.0)
+                .w_const(5.0)
+                .h_const(5.0)
+                .build(),
+        ];
+        assert_eq!(scene.tick(marks).len(), 2);
+
+        let view = zoom_about(Affine::IDENTITY, Point::new(50.0, 25.0), 2.0);
+        scene.set_signal(signal, view).unwrap();
+        let diffs = scene.update();
+        let [
+            MarkDiff::Update {
+                id,
+                changed,
+                new,
+                new_bounds,
+                ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single group update");
+        };
+        assert_eq!(*id, view_id);
+        assert_eq!(*changed, ChannelMask::GEOMETRY);
+        let MarkPayload::Group(g) = &**new else {
+            panic!("expected a group payload");
+        };
+        assert_eq!(g.transform, view);
+        // The clip stays on the plot rectangle in scene space.
+        assert_eq!(*new_bounds, Some(plot));
+    }
+
+    #[test]
+    fn settling_folds_the_view_into_the_domain() {
+        let x = ScaleLinear::new((0.0, 10.0), (0.0, 100.0));
+        let y = ScaleLinear::new((0.0, 1.0), (50.0, 0.0));
+        let view = pan(
+            zoom_about(Affine::IDENTITY, Point::new(50.0, 25.0), 2.0),
+            Vec2::new(10.0, 0.0),
+        );
+
+        let sx = settle_x(&x, view);
+        assert_eq!((sx.domain_min(), sx.domain_max()), (2.0, 7.0));
+        // Every data value lands where the view put it.
+        for v in [2.0, 4.5, 7.0] {
+            assert!((sx.map(v) - (view * Point::new(x.map(v), 0.0)).x).abs() < 1e-9);
+        }
+
+        let sy = settle_y(&y, view);
+        assert_eq!((sy.domain_min(), sy.domain_max()), (0.25, 0.75));
+        assert_eq!(settle_x(&x, Affine::IDENTITY).domain_max(), 10.0);
+    }
+}
@@ -3,19 +3,23 @@
 
 //! Chart demos for `vizir_core`.
 mod html;
+mod png;
 mod svg;
 
 use kurbo::{Point, Rect};
-use peniko::Color;
 use peniko::color::palette::css;
+use peniko::{Color, ImageAlphaType, ImageData, ImageFormat, ImageQuality};
 use vizir_charts::{
     AxisSpec, AxisStyle, BarMarkSpec, ChartLayout, ChartLayoutSpec, ChartSpec, GridStyle,
-    HeuristicTextMeasurer, LegendItem, LegendOrient, LegendPlacement, LegendSwatchesSpec,
-    PLOT_BACKGROUND, RectMarkSpec, RuleMarkSpec, ScaleBand, ScaleLinearSpec, ScaleLogSpec,
-    ScaleTimeSpec, SectorMarkSpec, Size, StackedAreaChartSpec, StackedAreaMarkSpec,
+    HeuristicTextMeasurer, ImageMarkSpec, LegendItem, LegendOrient, LegendPlacement,
+    LegendSwatchesSpec, PLOT_BACKGROUND, RectMarkSpec, RuleMarkSpec, ScaleBand, ScaleLinearSpec,
+    ScaleLogSpec, ScaleTimeSpec, SectorMarkSpec, Size, StackedAreaChartSpec, StackedAreaMarkSpec,
     StackedBarChartSpec, StrokeStyle, Symbol, TextMarkSpec, TitleSpec,
 };
-use vizir_core::{ColId, Mark, Scene, Table, TableData, TableId};
+use vizir_core::{
+    ColId, DiffRecording, DiffSink, FontWeight, InputRef, Mark, MarkDiff, Scene, SignalId, Table,
+    TableData, TableId, TextFont,
+};
 use vizir_transforms::{
     AggregateField, AggregateOp, CompareOp, Predicate, Program, StackOffset, Transform,
 };
@@ -55,6 +59,8 @@ fn main() {
         line_demo(),
         area_demo(),
         sector_demo(),
+        image_demo(),
+        replay_demo(),
     ];
 
     let html = html::render_report("VizIR charts demo", &sections);
@@ -70,10 +76,15 @@ fn render_chart(
 ) -> (ChartLayout, String) {
     let (layout, marks) = chart.marks(measurer, build_series);
     let diffs = scene.tick(marks);
+    let svg = render_svg(layout.view, &diffs);
+    (layout, svg)
+}
+
+fn render_svg(view: Rect, diffs: &[MarkDiff]) -> String {
     let mut svg_scene = svg::SvgScene::default();
-    svg_scene.set_view_box(layout.view);
-    svg_scene.apply_diffs(&diffs);
-    (layout, svg_scene.to_svg_string())
+    svg_scene.set_view_box(view);
+    svg_scene.apply_diffs(diffs);
+    svg_scene.to_svg_string()
 }
 
 fn log_time_axes_demo() -> html::HtmlSection {
@@ -95,7 +106,7 @@ fn log_time_axes_demo() -> html::HtmlSection {
     let x = vec![0.0, 30.0, 60.0, 120.0, 180.0, 240.0, 300.0];
     let y = vec![1.0, 3.0, 10.0, 30.0, 100.0, 300.0, 1000.0];
     let mut table = Table::new(table_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(ScatterValues { x, y }));
     scene.insert_table(table);
 
@@ -138,7 +149,7 @@ fn log_time_axes_demo() -> html::HtmlSection {
         legend: None,
     };
 
-    let keys = scene.tables[&table_id].row_keys.clone();
+    let keys = scene.tables[&table_id].row_keys().to_vec();
 
     let (_layout, svg) = render_chart(&mut scene, &measurer, &chart, |chart, plot| {
         let x_scale = chart.x_scale_continuous(plot).expect("expected x scale");
@@ -196,7 +207,7 @@ fn transforms_demo() -> html::HtmlSection {
     let x = vec![0.0, 2.0, 5.0, 7.0, 9.0, 10.0];
     let y = vec![1.0, 2.0, 6.0, 3.0, 7.5, 9.0];
     let mut table = Table::new(source_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(ScatterValues {
         x: x.clone(),
         y: y.clone(),
@@ -270,8 +281,8 @@ fn transforms_demo() -> html::HtmlSection {
         legend: None,
     };
 
-    let keys_src = scene.tables[&source_id].row_keys.clone();
-    let keys_sorted = scene.tables[&sorted_id].row_keys.clone();
+    let keys_src = scene.tables[&source_id].row_keys().to_vec();
+    let keys_sorted = scene.tables[&sorted_id].row_keys().to_vec();
 
     let (_layout, svg) = render_chart(&mut scene, &measurer, &chart, |chart, plot| {
         let x_scale = chart.x_scale_continuous(plot).expect("expected x scale");
@@ -343,7 +354,7 @@ fn aggregate_demo() -> html::HtmlSection {
     let cat = vec![0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 3.0];
     let v = vec![1.0, 2.0, 3.0, 4.0, 5.0, 2.0, 1.0, 6.0];
     let mut table = Table::new(source_id);
-    table.row_keys = (0..cat.len() as u64).collect();
+    table.set_row_keys((0..cat.len() as u64).collect());
     table.data = Some(Box::new(CategoryValues { cat, v }));
     scene.insert_table(table);
 
@@ -366,7 +377,7 @@ fn aggregate_demo() -> html::HtmlSection {
         height: 120.0,
     };
 
-    let keys = scene.tables[&agg_id].row_keys.clone();
+    let keys = scene.tables[&agg_id].row_keys().to_vec();
     let n = keys.len();
 
     let mut max_sum = 0.0_f64;
@@ -437,7 +448,9 @@ fn aggregate_demo() -> html::HtmlSection {
     let (_layout, svg) = render_chart(&mut scene, &measurer, &chart, move |chart, plot| {
         let band = ScaleBand::new((plot.x0, plot.x1), n).with_padding(0.2, 0.1);
         let y_scale = chart.y_scale_continuous(plot).expect("expected y scale");
-        let bars = BarMarkSpec::new(agg_id, sum_col, band, y_scale).with_fill(css::CORNFLOWER_BLUE);
+        let bars = BarMarkSpec::new(agg_id, sum_col, band, y_scale)
+            .with_category(cat_col)
+            .with_fill(css::CORNFLOWER_BLUE);
 
         let mut marks: Vec<Mark> = bars.marks(&keys);
         marks.push(
@@ -493,7 +506,7 @@ fn histogram_demo() -> html::HtmlSection {
     ];
 
     let mut table = Table::new(source_id);
-    table.row_keys = (0..values.len() as u64).collect();
+    table.set_row_keys((0..values.len() as u64).collect());
     table.data = Some(Box::new(HistogramValues { v: values }));
     scene.insert_table(table);
 
@@ -531,7 +544,7 @@ fn histogram_demo() -> html::HtmlSection {
         height: 120.0,
     };
 
-    let keys = scene.tables[&sorted_id].row_keys.clone();
+    let keys = scene.tables[&sorted_id].row_keys().to_vec();
     let n = keys.len();
 
     let mut max_count = 0.0_f64;
@@ -668,7 +681,7 @@ fn stack_demo() -> html::HtmlSection {
     let v = vec![3.0, 2.0, 1.0, 4.0, 1.5, 2.5, 2.0, -1.0, 3.0, 1.0, 2.0, 2.0];
 
     let mut table = Table::new(source_id);
-    table.row_keys = (0..cat.len() as u64).collect();
+    table.set_row_keys((0..cat.len() as u64).collect());
     table.data = Some(Box::new(StackValues { cat, series, v }));
     scene.insert_table(table);
 
@@ -686,7 +699,7 @@ fn stack_demo() -> html::HtmlSection {
         height: 120.0,
     };
 
-    let keys = scene.tables[&stacked_id].row_keys.clone();
+    let keys = scene.tables[&stacked_id].row_keys().to_vec();
     let n_rows = keys.len();
 
     let mut min_y = 0.0_f64;
@@ -874,7 +887,7 @@ fn stacked_area_demo() -> html::HtmlSection {
     }
 
     let mut table = Table::new(source_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(StackedAreaSourceValues { x, series, y }));
     scene.insert_table(table);
 
@@ -901,7 +914,7 @@ fn stacked_area_demo() -> html::HtmlSection {
 
     let mut max_y1 = 0.0_f64;
     if let Some(data) = scene.tables[&stacked_id].data.as_deref() {
-        let n = scene.tables[&stacked_id].row_keys.len();
+        let n = scene.tables[&stacked_id].row_count();
         for row in 0..n {
             let y1 = data.f64(row, y1_col).unwrap_or(f64::NAN);
             if y1.is_finite() {
@@ -1061,7 +1074,7 @@ fn percent_stack_demo() -> html::HtmlSection {
     let v = vec![3.0, 2.0, 1.0, 4.0, 1.0, 2.0, 2.0, 1.0, 3.0, 1.0, 2.0, 2.0];
 
     let mut table = Table::new(source_id);
-    table.row_keys = (0..cat.len() as u64).collect();
+    table.set_row_keys((0..cat.len() as u64).collect());
     table.data = Some(Box::new(PercentStackValues { cat, series, v }));
     scene.insert_table(table);
 
@@ -1081,7 +1094,7 @@ fn percent_stack_demo() -> html::HtmlSection {
         height: 120.0,
     };
 
-    let keys = scene.tables[&stacked_id].row_keys.clone();
+    let keys = scene.tables[&stacked_id].row_keys().to_vec();
 
     let axis_bottom = AxisSpec::bottom(0x70_000, ScaleLinearSpec::new((0.0, 3.0)))
         .with_tick_count(4)
@@ -1216,7 +1229,7 @@ fn streamgraph_demo() -> html::HtmlSection {
         }
 
         let mut table = Table::new(source_id);
-        table.row_keys = (0..x.len() as u64).collect();
+        table.set_row_keys((0..x.len() as u64).collect());
         table.data = Some(Box::new(StreamValues { x, series, y }));
         scene.insert_table(table);
 
@@ -1245,7 +1258,7 @@ fn streamgraph_demo() -> html::HtmlSection {
         let mut min_y = f64::INFINITY;
         let mut max_y = f64::NEG_INFINITY;
         if let Some(data) = scene.tables[&stacked_id].data.as_deref() {
-            let n = scene.tables[&stacked_id].row_keys.len();
+            let n = scene.tables[&stacked_id].row_count();
             for row in 0..n {
                 let y0 = data.f64(row, y0_col).unwrap_or(f64::NAN);
                 let y1 = data.f64(row, y1_col).unwrap_or(f64::NAN);
@@ -1433,7 +1446,7 @@ fn axis_label_angle_demo() -> html::HtmlSection {
     let x: Vec<f64> = (0..=5).map(|v| v as f64).collect();
     let y: Vec<f64> = [2.0, 5.0, 3.0, 7.0, 4.0, 6.0].into();
     let mut table = Table::new(table_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(AngleValues { x, y }));
     scene.insert_table(table);
 
@@ -1444,6 +1457,7 @@ fn axis_label_angle_demo() -> html::HtmlSection {
         label_font_size: 10.0,
         title_fill: rule.brush.clone(),
         title_font_size: 11.0,
+        ..AxisStyle::default()
     };
 
     let axis_bottom = AxisSpec::bottom(0x61_000, ScaleLinearSpec::new((0.0, 5.0)))
@@ -1474,7 +1488,7 @@ fn axis_label_angle_demo() -> html::HtmlSection {
     )
     .with_font_size(12.0)
     .with_fill(css::BLACK);
-    let keys = scene.tables[&table_id].row_keys.clone();
+    let keys = scene.tables[&table_id].row_keys().to_vec();
     let chart_spec = ChartSpec {
         title: Some(title),
         plot_size,
@@ -1682,14 +1696,11 @@ fn scales_demo() -> html::HtmlSection {
 
     // Evaluate.
     let diffs = scene.tick(marks);
-    let mut svg_scene = svg::SvgScene::default();
-    svg_scene.set_view_box(view);
-    svg_scene.apply_diffs(&diffs);
 
     html::HtmlSection {
         title: "Scales",
         description: "A quick visualization of new scale types. (Time is numeric seconds with nice ticks/formatting.)",
-        svg: svg_scene.to_svg_string(),
+        svg: render_svg(view, &diffs),
     }
 }
 
@@ -1707,7 +1718,7 @@ fn bar_demo() -> html::HtmlSection {
 
     let y = vec![3.0, -4.0, 10.0, 6.0, -1.0];
     let mut table = Table::new(table_id);
-    table.row_keys = (0..y.len() as u64).collect();
+    table.set_row_keys((0..y.len() as u64).collect());
     table.data = Some(Box::new(BarValues { y }));
     scene.insert_table(table);
 
@@ -1718,6 +1729,7 @@ fn bar_demo() -> html::HtmlSection {
         label_font_size: 10.0,
         title_fill: rule.brush.clone(),
         title_font_size: 11.0,
+        ..AxisStyle::default()
     };
 
     let axis_bottom = AxisSpec::bottom(0x10_000, ScaleLinearSpec::new((0.0, 10.0)))
@@ -1751,7 +1763,7 @@ fn bar_demo() -> html::HtmlSection {
         vec![LegendItem::solid("bars", css::CORNFLOWER_BLUE)],
     )
     .with_text_fill(css::BLACK);
-    let keys = scene.tables[&table_id].row_keys.clone();
+    let keys = scene.tables[&table_id].row_keys().to_vec();
     let n = keys.len();
 
     let chart_spec = ChartSpec {
@@ -1859,7 +1871,7 @@ fn scatter_demo() -> html::HtmlSection {
     let y = vec![1.0, 2.0, 6.0, 3.0, 7.5, 9.0];
 
     let mut table = Table::new(table_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(ScatterValues { x, y }));
     scene.insert_table(table);
 
@@ -1870,6 +1882,7 @@ fn scatter_demo() -> html::HtmlSection {
         label_font_size: 10.0,
         title_fill: rule.brush.clone(),
         title_font_size: 11.0,
+        ..AxisStyle::default()
     };
     let grid = GridStyle {
         stroke: StrokeStyle::solid(css::BLACK.with_alpha(40.0 / 255.0), 1.0),
@@ -1898,7 +1911,7 @@ fn scatter_demo() -> html::HtmlSection {
         .with_font_size(12.0)
         .with_fill(css::BLACK);
 
-    let keys = scene.tables[&table_id].row_keys.clone();
+    let keys = scene.tables[&table_id].row_keys().to_vec();
     let chart_spec = ChartSpec {
         title: Some(title),
         plot_size,
@@ -1972,7 +1985,7 @@ fn line_demo() -> html::HtmlSection {
     let y = vec![1.0, 2.0, 6.0, 3.0, 7.5, 9.0];
 
     let mut table = Table::new(table_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(ScatterValues { x, y }));
     scene.insert_table(table);
 
@@ -1983,6 +1996,7 @@ fn line_demo() -> html::HtmlSection {
         label_font_size: 10.0,
         title_fill: rule.brush.clone(),
         title_font_size: 11.0,
+        ..AxisStyle::default()
     };
 
     let axis_bottom = AxisSpec::bottom(0x30_000, ScaleLinearSpec::new((0.0, 10.0)))
@@ -2084,7 +2098,7 @@ fn area_demo() -> html::HtmlSection {
     let y = vec![1.0, 2.0, 6.0, 3.0, 7.5, 9.0];
 
     let mut table = Table::new(table_id);
-    table.row_keys = (0..x.len() as u64).collect();
+    table.set_row_keys((0..x.len() as u64).collect());
     table.data = Some(Box::new(ScatterValues { x, y }));
     scene.insert_table(table);
 
@@ -2095,6 +2109,7 @@ fn area_demo() -> html::HtmlSection {
         label_font_size: 10.0,
         title_fill: rule.brush.clone(),
         title_font_size: 11.0,
+        ..AxisStyle::default()
     };
 
     let axis_bottom = AxisSpec::bottom(0x40_000, ScaleLinearSpec::new((0.0, 10.0)))
@@ -2273,3 +2288,179 @@ fn sector_demo() -> html::HtmlSection {
         svg,
     }
 }
+
+/// Builds an RGBA bitmap by evaluating `pixel` at each `(column, row)`.
+fn bitmap(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [u8; 4]) -> ImageData {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for row in 0..height {
+        for col in 0..width {
+            data.extend_from_slice(&pixel(col, row));
+        }
+    }
+    ImageData {
+        data: data.into(),
+        format: ImageFormat::Rgba8,
+        alpha_type: ImageAlphaType::Alpha,
+        width,
+        height,
+    }
+}
+
+fn image_demo() -> html::HtmlSection {
+    // A heatmap bitmap stretched over the plot, with an `ImageMarkSpec` badge per row on top.
+    let mut scene = Scene::new();
+    let table_id = TableId(7);
+    let x_col = ColId(0);
+    let y_col = ColId(1);
+
+    let measurer = HeuristicTextMeasurer;
+    let plot_size = Size {
+        width: 180.0,
+        height: 100.0,
+    };
+
+    let x = vec![1.0, 3.0, 5.5, 8.0];
+    let y = vec![2.0, 7.0, 4.0, 8.5];
+    let mut table = Table::new(table_id);
+    table.set_row_keys((0..x.len() as u64).collect());
+    table.data = Some(Box::new(ScatterValues { x, y }));
+    scene.insert_table(table);
+
+    let heatmap = bitmap(12, 8, |col, row| {
+        let t = u8::try_from((col + row) * 255 / 18).unwrap_or(u8::MAX);
+        [t, 96, 255 - t, 255]
+    });
+    let badge = bitmap(8, 8, |col, row| {
+        let (dx, dy) = (col as i32 * 2 - 7, row as i32 * 2 - 7);
+        if dx * dx + dy * dy <= 49 {
+            [255, 255, 255, 255]
+        } else {
+            [0, 0, 0, 0]
+        }
+    });
+
+    let rule = StrokeStyle::solid(css::BLACK, 1.0);
+    let axis_style = AxisStyle {
+        rule: rule.clone(),
+        label_fill: rule.brush.clone(),
+        label_font_size: 10.0,
+        title_fill: rule.brush.clone(),
+        title_font_size: 11.0,
+        ..AxisStyle::default()
+    };
+    let axis_bottom = AxisSpec::bottom(0x70_000, ScaleLinearSpec::new((0.0, 10.0)))
+        .with_tick_count(6)
+        .with_style(axis_style.clone());
+    let axis_left = AxisSpec::left(0x71_000, ScaleLinearSpec::new((0.0, 10.0)))
+        .with_tick_count(6)
+        .with_style(axis_style);
+
+    let title = TitleSpec::new(vizir_core::MarkId::from_raw(0x7F_200), "Images")
+        .with_font_size(12.0)
+        .with_font(TextFont::default().with_weight(FontWeight::BOLD))
+        .with_fill(css::BLACK);
+
+    let keys = scene.tables[&table_id].row_keys().to_vec();
+    let chart_spec = ChartSpec {
+        title: Some(title),
+        plot_size,
+        layout: ChartLayoutSpec {
+            view_size: None,
+            outer_padding: 10.0,
+            plot_padding: 0.0,
+            ..ChartLayoutSpec::default()
+        },
+        axis_left: Some(axis_left),
+        axis_right: None,
+        axis_top: None,
+        axis_bottom: Some(axis_bottom),
+        legend: None,
+    };
+
+    let (_layout, svg) = render_chart(
+        &mut scene,
+        &measurer,
+        &chart_spec,
+        move |chart_spec, plot| {
+            let x_scale = chart_spec
+                .x_scale_continuous(plot)
+                .expect("expected x scale");
+            let y_scale = chart_spec
+                .y_scale_continuous(plot)
+                .expect("expected y scale");
+
+            let mut marks = ImageMarkSpec::new(table_id, x_col, y_col, x_scale, y_scale)
+                .with_size(8.0, 8.0)
+                .with_image(badge)
+                .marks(&keys);
+            marks.push(
+                Mark::builder(vizir_core::MarkId::from_raw(0x7F_000))
+                    .image()
+                    .z_index(PLOT_BACKGROUND)
+                    .x_const(plot.x0)
+                    .y_const(plot.y0)
+                    .w_const(plot.width())
+                    .h_const(plot.height())
+                    .image_const(heatmap)
+                    .image_quality_const(ImageQuality::Low)
+                    .build(),
+            );
+            marks
+        },
+    );
+    html::HtmlSection {
+        title: "Images",
+        description: "Image marks: a pixelated heatmap bitmap and per-row ImageMarkSpec badges, embedded as PNG data URIs.",
+        svg,
+    }
+}
+
+fn replay_demo() -> html::HtmlSection {
+    // Record two diff batches (enter, then a signal-driven update), ship them through JSON, and
+    // rebuild the SVG from the replayed stream, the way a UI process fed by a worker would.
+    let mut scene = Scene::new();
+    let level = SignalId(0xA0);
+    scene.insert_signal(level, 0.5_f64);
+    let colors = [css::CORNFLOWER_BLUE, css::TOMATO, css::GOLD, css::SEA_GREEN];
+    let marks: Vec<Mark> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            let weight = (i + 1) as f64;
+            Mark::builder(vizir_core::MarkId::from_raw(0xA0_000 + i as u64))
+                .x_const(20.0 + 50.0 * i as f64)
+                .w_const(40.0)
+                .y_compute([InputRef::Signal { signal: level }], move |ctx, _| {
+                    110.0 - 25.0 * weight * ctx.signal::<f64>(level).unwrap_or(0.0)
+                })
+                .h_compute([InputRef::Signal { signal: level }], move |ctx, _| {
+                    25.0 * weight * ctx.signal::<f64>(level).unwrap_or(0.0)
+                })
+                .fill_const(*color)
+                .build()
+        })
+        .collect();
+
+    let mut recording = DiffRecording::new();
+    recording.record(&scene.tick(marks));
+    scene.set_signal(level, 1.0_f64).expect("level signal");
+    recording.record(&scene.update());
+
+    let json = serde_json::to_vec(&recording).expect("serialize diff recording");
+    let replayed: DiffRecording = serde_json::from_slice(&json).expect("deserialize recording");
+    assert_eq!(
+        replayed, recording,
+        "diff recordings survive a JSON round trip"
+    );
+
+    let view = Rect::new(0.0, 0.0, 230.0, 120.0);
+    let mut svg_scene = svg::SvgScene::default();
+    svg_scene.set_view_box(view);
+    replayed.replay(&mut svg_scene);
+
+    html::HtmlSection {
+        title: "Record/replay",
+        description: "Two diff batches (enter, then a signal update) recorded with DiffRecording, serialized to JSON, deserialized, and replayed into the SVG renderer.",
+        svg: svg_scene.to_svg_string(),
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Minimal PNG encoding for embedding image marks in the demo SVG.
+//!
+//! The encoder writes uncompressed ("stored") deflate blocks: files are larger than a real PNG
+//! encoder would produce, but this keeps the demo free of image dependencies.
+
+use peniko::{ImageAlphaType, ImageData, ImageFormat};
+
+/// Encode `image` as a `data:image/png;base64,...` URI.
+///
+/// Returns `None` for empty images, unsupported pixel formats, or mismatched buffer sizes.
+pub(crate) fn data_uri(image: &ImageData) -> Option<String> {
+    let png = encode(image)?;
+    Some(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+fn encode(image: &ImageData) -> Option<Vec<u8>> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let bgra = match image.format {
+        ImageFormat::Rgba8 => false,
+        ImageFormat::Bgra8 => true,
+        _ => return None,
+    };
+    let pixels = image.data.data();
+    if pixels.len() != image.format.size_in_bytes(image.width, image.height)? {
+        return None;
+    }
+
+    // Scanlines, each prefixed with filter type 0 (none), as straight-alpha RGBA.
+    let mut raw = Vec::with_capacity(height * (1 + width * 4));
+    for row in pixels.chunks_exact(width * 4) {
+        raw.push(0);
+        for px in row.chunks_exact(4) {
+            let [mut r, g, mut b, a] = [px[0], px[1], px[2], px[3]];
+            if bgra {
+                core::mem::swap(&mut r, &mut b);
+            }
+            let mut rgba = [r, g, b, a];
+            if image.alpha_type == ImageAlphaType::AlphaPremultiplied && a != 0 && a != 255 {
+                for c in &mut rgba[..3] {
+                    *c = ((u32::from(*c) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8;
+                }
+            }
+            raw.extend_from_slice(&rgba);
+        }
+    }
+
+    let mut out = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&image.width.to_be_bytes());
+    ihdr.extend_from_slice(&image.height.to_be_bytes());
+    // 8-bit RGBA, deflate, adaptive filtering, no interlace.
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut out, b"IHDR", &ihdr);
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, b"IEND", &[]);
+    Some(out)
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let len = u32::try_from(data.len()).expect("PNG chunk length fits in u32");
+    out.extend_from_slice(&len.to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream made of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = u16::try_from(block.len()).expect("stored blocks are at most 0xffff bytes");
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_known_answers() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn base64_matches_rfc_4648_vectors() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(input.as_bytes()), expected);
+        }
+    }
+
+    /// Splits a PNG into `(kind, data)` chunks, checking the signature and every CRC.
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], &[u8])> {
+        assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+        let mut out = Vec::new();
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (body, tail) = rest[4..].split_at(4 + len);
+            let crc = u32::from_be_bytes(tail[..4].try_into().unwrap());
+            assert_eq!(crc, crc32(body));
+            out.push((body[..4].try_into().unwrap(), &body[4..]));
+            rest = &tail[4..];
+        }
+        out
+    }
+
+    #[test]
+    fn one_pixel_image_decodes() {
+        // Premultiplied BGRA: half-transparent red.
+        let image = ImageData {
+            data: vec![0, 0, 64, 128].into(),
+            format: ImageFormat::Bgra8,
+            alpha_type: ImageAlphaType::AlphaPremultiplied,
+            width: 1,
+            height: 1,
+        };
+        let png = encode(&image).unwrap();
+        let chunks = chunks(&png);
+        let kinds: Vec<_> = chunks.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+
+        // 1×1, 8-bit RGBA, no interlace.
+        assert_eq!(chunks[0].1, [0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+
+        // A zlib header, one final stored block holding the scanline, and its Adler-32.
+        let zlib = chunks[1].1;
+        assert_eq!((u16::from(zlib[0]) << 8 | u16::from(zlib[1])) % 31, 0);
+        assert_eq!(zlib[2], 1);
+        assert_eq!(zlib[3..7], [5, 0, !5, !0]);
+        let scanline = &zlib[7..12];
+        assert_eq!(scanline, [0, 128, 0, 0, 128]);
+        assert_eq!(zlib[12..], adler32(scanline).to_be_bytes());
+
+        assert!(chunks[2].1.is_empty());
+        assert!(
+            data_uri(&image)
+                .unwrap()
+                .starts_with("data:image/png;base64,iVBORw0KGgo")
+        );
+    }
+}
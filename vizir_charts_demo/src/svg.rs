@@ -5,36 +5,63 @@
 
 use std::collections::HashMap;
 
-use kurbo::Rect;
-use peniko::Brush;
-use vizir_core::{MarkDiff, MarkId, MarkPayload, TextAnchor, TextBaseline};
+use kurbo::{Affine, Cap, Join, Rect, RoundedRect, Shape, Stroke};
+use peniko::{BlendMode, Brush, ImageQuality, Mix};
+use vizir_core::{
+    DiffSink, FontStyle, FontWeight, GroupClip, ImageSource, InstanceShape, MarkDiff, MarkId,
+    MarkMeta, MarkPayload, TextAnchor, TextBaseline,
+};
+
+use crate::png;
+
+#[derive(Debug)]
+struct SvgItem {
+    z_index: i32,
+    parent: Option<MarkId>,
+    payload: MarkPayload,
+}
 
 #[derive(Debug, Default)]
 pub(crate) struct SvgScene {
-    marks: HashMap<MarkId, (i32, MarkPayload)>,
+    marks: HashMap<MarkId, SvgItem>,
     view_box: Option<Rect>,
 }
 
-impl SvgScene {
-    pub(crate) fn set_view_box(&mut self, view_box: Rect) {
-        self.view_box = Some(view_box);
-    }
-
-    pub(crate) fn apply_diffs(&mut self, diffs: &[MarkDiff]) {
+impl DiffSink for SvgScene {
+    fn apply_diffs(&mut self, diffs: &[MarkDiff]) {
         for diff in diffs {
             match diff {
                 MarkDiff::Enter {
-                    id, z_index, new, ..
+                    id,
+                    z_index,
+                    parent,
+                    new,
+                    ..
                 } => {
-                    self.marks.insert(*id, (*z_index, (**new).clone()));
+                    self.marks.insert(
+                        *id,
+                        SvgItem {
+                            z_index: *z_index,
+                            parent: *parent,
+                            payload: (**new).clone(),
+                        },
+                    );
                 }
                 MarkDiff::Update {
                     id,
                     new_z_index,
+                    new_parent,
                     new,
                     ..
                 } => {
-                    self.marks.insert(*id, (*new_z_index, (**new).clone()));
+                    self.marks.insert(
+                        *id,
+                        SvgItem {
+                            z_index: *new_z_index,
+                            parent: *new_parent,
+                            payload: (**new).clone(),
+                        },
+                    );
                 }
                 MarkDiff::Exit { id, .. } => {
                     self.marks.remove(id);
@@ -42,6 +69,12 @@ impl SvgScene {
             }
         }
     }
+}
+
+impl SvgScene {
+    pub(crate) fn set_view_box(&mut self, view_box: Rect) {
+        self.view_box = Some(view_box);
+    }
 
     pub(crate) fn to_svg_string(&self) -> String {
         let computed = self.view_box();
@@ -71,94 +104,93 @@ impl SvgScene {
         ));
         out.push('\n');
 
-        let mut ids: Vec<_> = self.marks.keys().copied().collect();
-        ids.sort_by_key(|id| {
-            let (z, _payload) = self.marks.get(id).expect("id from keys");
-            (*z, id.0)
-        });
+        let children = self.children();
+        if let Some(roots) = children.get(&None) {
+            self.write_items(&mut out, roots, &children);
+        }
 
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Group mark ids by parent, sorted by z-index. Marks whose parent is missing are roots.
+    fn children(&self) -> HashMap<Option<MarkId>, Vec<MarkId>> {
+        let mut children: HashMap<Option<MarkId>, Vec<MarkId>> = HashMap::new();
+        for (id, item) in &self.marks {
+            let parent = item.parent.filter(|p| self.marks.contains_key(p));
+            children.entry(parent).or_default().push(*id);
+        }
+        for ids in children.values_mut() {
+            ids.sort_by_key(|id| (self.marks[id].z_index, id.0));
+        }
+        children
+    }
+
+    fn write_items(
+        &self,
+        out: &mut String,
+        ids: &[MarkId],
+        children: &HashMap<Option<MarkId>, Vec<MarkId>>,
+    ) {
         for id in ids {
-            let (_z, payload) = self.marks.get(&id).expect("id from keys");
+            let payload = &self.marks[id].payload;
+            let meta = payload.meta();
+            let href = &meta.href;
+            if !href.is_empty() {
+                out.push_str(&format!(r#"<a href="{}">"#, escape_xml(href)));
+                out.push('\n');
+            }
             match payload {
-                MarkPayload::Rect(r) => {
-                    out.push_str(&format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}""#,
-                        r.rect.x0,
-                        r.rect.y0,
-                        r.rect.width(),
-                        r.rect.height(),
-                    ));
-                    write_paint_attr(&mut out, "fill", &r.fill);
-                    out.push_str("/>\n");
-                }
-                MarkPayload::Text(t) => {
-                    let baseline = match t.baseline {
-                        TextBaseline::Middle => "middle",
-                        TextBaseline::Alphabetic => "alphabetic",
-                        TextBaseline::Hanging => "hanging",
-                        TextBaseline::Ideographic => "ideographic",
-                    };
-                    out.push_str(&format!(
-                        r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="{}""#,
-                        t.pos.x, t.pos.y, t.font_size, baseline
-                    ));
-                    if t.angle != 0.0 {
-                        out.push_str(&format!(
-                            r#" transform="rotate({} {} {})""#,
-                            t.angle, t.pos.x, t.pos.y
-                        ));
+                MarkPayload::Group(g) => {
+                    out.push_str("<g");
+                    if g.transform != Affine::IDENTITY {
+                        let [a, b, c, d, e, f] = g.transform.as_coeffs();
+                        out.push_str(&format!(r#" transform="matrix({a} {b} {c} {d} {e} {f})""#));
                     }
-                    out.push_str(match t.anchor {
-                        TextAnchor::Start => r#" text-anchor="start""#,
-                        TextAnchor::Middle => r#" text-anchor="middle""#,
-                        TextAnchor::End => r#" text-anchor="end""#,
-                    });
-                    write_paint_attr(&mut out, "fill", &t.fill);
-                    out.push('>');
-                    out.push_str(&escape_xml(&t.text));
-                    out.push_str("</text>\n");
-                }
-                MarkPayload::Path(p) => {
-                    let d = p.path.to_svg();
-                    out.push_str(&format!(r#"<path d="{d}""#));
-                    write_paint_attr(&mut out, "fill", &p.fill);
-                    if p.stroke_width > 0.0 {
-                        write_paint_attr(&mut out, "stroke", &p.stroke);
-                        out.push_str(&format!(r#" stroke-width="{}""#, p.stroke_width));
+                    write_meta_attrs(out, meta);
+                    out.push_str(">\n");
+                    write_meta_children(out, meta);
+                    if let Some(clip) = &g.clip {
+                        out.push_str(&format!(r#"<clipPath id="clip-{}">"#, id.0));
+                        match clip {
+                            GroupClip::Rect(r) => out.push_str(&format!(
+                                r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#,
+                                r.x0,
+                                r.y0,
+                                r.width(),
+                                r.height(),
+                            )),
+                            GroupClip::Path(p) => {
+                                out.push_str(&format!(r#"<path d="{}"/>"#, p.to_svg()));
+                            }
+                        }
+                        out.push_str("</clipPath>\n");
+                        out.push_str(&format!(r#"<g clip-path="url(#clip-{})">"#, id.0));
+                        out.push('\n');
+                    }
+                    if let Some(ids) = children.get(&Some(*id)) {
+                        self.write_items(out, ids, children);
                     }
-                    out.push_str("/>\n");
+                    if g.clip.is_some() {
+                        out.push_str("</g>\n");
+                    }
+                    out.push_str("</g>\n");
                 }
+                payload => write_payload(out, payload),
+            }
+            if !href.is_empty() {
+                out.push_str("</a>\n");
             }
         }
-
-        out.push_str("</svg>\n");
-        out
     }
 
     fn view_box(&self) -> Option<Rect> {
-        let mut rect: Option<Rect> = None;
-        for (_z, payload) in self.marks.values() {
-            let b = match payload {
-                MarkPayload::Text(t) => Some(estimate_text_bounds_anchored(
-                    t.pos.x,
-                    t.pos.y,
-                    t.font_size,
-                    t.anchor,
-                    t.baseline,
-                    &t.text,
-                )),
-                _ => payload.bounds(),
-            }?;
-            rect = Some(match rect {
-                None => b,
-                Some(r) => Rect::new(
-                    r.x0.min(b.x0),
-                    r.y0.min(b.y0),
-                    r.x1.max(b.x1),
-                    r.y1.max(b.y1),
-                ),
-            });
-        }
+        let children = self.children();
+        let rect = children
+            .get(&None)?
+            .iter()
+            .filter_map(|id| self.item_bounds(*id, &children))
+            .reduce(|r, b| r.union(b));
 
         rect.map(|r| {
             // Add a small padding margin.
@@ -166,6 +198,196 @@ impl SvgScene {
             Rect::new(r.x0 - pad, r.y0 - pad, r.x1 + pad, r.y1 + pad)
         })
     }
+
+    /// Approximate bounds of a mark (including group children) in its parent's coordinates.
+    fn item_bounds(
+        &self,
+        id: MarkId,
+        children: &HashMap<Option<MarkId>, Vec<MarkId>>,
+    ) -> Option<Rect> {
+        match &self.marks[&id].payload {
+            MarkPayload::Text(t) => Some(estimate_text_bounds_anchored(
+                t.pos.x,
+                t.pos.y,
+                t.font_size,
+                t.anchor,
+                t.baseline,
+                &t.text,
+                t.line_height,
+            )),
+            MarkPayload::Group(g) => {
+                let inner = children
+                    .get(&Some(id))?
+                    .iter()
+                    .filter_map(|child| self.item_bounds(*child, children))
+                    .reduce(|r, b| r.union(b))?;
+                let inner = match &g.clip {
+                    Some(clip) => inner.intersect(clip.bounds()),
+                    None => inner,
+                };
+                Some(g.transform.transform_rect_bbox(inner))
+            }
+            payload => payload.bounds(),
+        }
+    }
+}
+
+fn write_payload(out: &mut String, payload: &MarkPayload) {
+    let meta = payload.meta();
+    match payload {
+        MarkPayload::Rect(r) => {
+            let radii = r.corner_radii;
+            let uniform = radii.as_single_radius();
+            let tag = if uniform.is_some() { "rect" } else { "path" };
+            match uniform {
+                Some(_) => out.push_str(&format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+                    r.rect.x0,
+                    r.rect.y0,
+                    r.rect.width(),
+                    r.rect.height(),
+                )),
+                // SVG `<rect>` only supports a single radius; emit other shapes as paths.
+                None => out.push_str(&format!(
+                    r#"<path d="{}""#,
+                    RoundedRect::from_rect(r.rect, radii).to_path(0.1).to_svg()
+                )),
+            }
+            if let Some(radius) = uniform.filter(|r| *r > 0.0) {
+                let radius = radius.min(r.rect.width() * 0.5).min(r.rect.height() * 0.5);
+                out.push_str(&format!(r#" rx="{radius}""#));
+            }
+            write_paint_attr(out, "fill", &r.fill, r.fill_opacity);
+            if r.stroke_width > 0.0 {
+                write_paint_attr(out, "stroke", &r.stroke, r.stroke_opacity);
+                out.push_str(&format!(r#" stroke-width="{}""#, r.stroke_width));
+            }
+            write_compositing_attrs(out, r.opacity, r.blend_mode);
+            write_meta_attrs(out, meta);
+            close_element(out, tag, meta);
+        }
+        MarkPayload::Text(t) => {
+            let baseline = match t.baseline {
+                TextBaseline::Middle => "middle",
+                TextBaseline::Alphabetic => "alphabetic",
+                TextBaseline::Hanging => "hanging",
+                TextBaseline::Ideographic => "ideographic",
+            };
+            out.push_str(&format!(
+                r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="{}""#,
+                t.pos.x, t.pos.y, t.font_size, baseline
+            ));
+            if t.angle != 0.0 {
+                out.push_str(&format!(
+                    r#" transform="rotate({} {} {})""#,
+                    t.angle, t.pos.x, t.pos.y
+                ));
+            }
+            out.push_str(match t.anchor {
+                TextAnchor::Start => r#" text-anchor="start""#,
+                TextAnchor::Middle => r#" text-anchor="middle""#,
+                TextAnchor::End => r#" text-anchor="end""#,
+            });
+            if !t.font_family.is_empty() {
+                out.push_str(&format!(r#" font-family="{}""#, escape_xml(&t.font_family)));
+            }
+            if t.font_weight != FontWeight::NORMAL {
+                out.push_str(&format!(r#" font-weight="{}""#, t.font_weight.0));
+            }
+            match t.font_style {
+                FontStyle::Normal => {}
+                FontStyle::Italic => out.push_str(r#" font-style="italic""#),
+                FontStyle::Oblique => out.push_str(r#" font-style="oblique""#),
+            }
+            write_paint_attr(out, "fill", &t.fill, t.fill_opacity);
+            write_compositing_attrs(out, t.opacity, t.blend_mode);
+            write_meta_attrs(out, meta);
+            out.push('>');
+            write_meta_children(out, meta);
+            if t.text.contains('\n') {
+                // SVG text has no line breaking; emit one `<tspan>` per line.
+                for (i, line) in t.text.split('\n').enumerate() {
+                    let dy = if i == 0 { 0.0 } else { t.line_height };
+                    out.push_str(&format!(
+                        r#"<tspan x="{}" dy="{dy}em">{}</tspan>"#,
+                        t.pos.x,
+                        escape_xml(line)
+                    ));
+                }
+            } else {
+                out.push_str(&escape_xml(&t.text));
+            }
+            out.push_str("</text>\n");
+        }
+        MarkPayload::Path(p) => {
+            let d = p.path.to_svg();
+            out.push_str(&format!(r#"<path d="{d}""#));
+            write_paint_attr(out, "fill", &p.fill, p.fill_opacity);
+            write_compositing_attrs(out, p.opacity, p.blend_mode);
+            if p.stroke_width > 0.0 {
+                write_paint_attr(out, "stroke", &p.stroke, p.stroke_opacity);
+                out.push_str(&format!(r#" stroke-width="{}""#, p.stroke_width));
+                write_stroke_style_attrs(out, &p.stroke_style);
+            }
+            write_meta_attrs(out, meta);
+            close_element(out, "path", meta);
+        }
+        MarkPayload::Image(i) => {
+            let href = match &i.image {
+                ImageSource::Handle(handle) if handle.is_empty() => return,
+                ImageSource::Handle(handle) => escape_xml(handle),
+                ImageSource::Data(data) => {
+                    let Some(uri) = png::data_uri(data) else {
+                        return;
+                    };
+                    uri
+                }
+            };
+            out.push_str(&format!(
+                r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" href="{href}""#,
+                i.rect.x0,
+                i.rect.y0,
+                i.rect.width(),
+                i.rect.height(),
+            ));
+            match i.quality {
+                ImageQuality::Low => out.push_str(r#" image-rendering="pixelated""#),
+                ImageQuality::Medium => {}
+                ImageQuality::High => out.push_str(r#" image-rendering="optimizeQuality""#),
+            }
+            write_compositing_attrs(out, i.opacity, None);
+            write_meta_attrs(out, meta);
+            close_element(out, "image", meta);
+        }
+        MarkPayload::Instances(i) => {
+            out.push_str("<g");
+            write_compositing_attrs(out, i.opacity, None);
+            write_meta_attrs(out, meta);
+            out.push_str(">\n");
+            write_meta_children(out, meta);
+            let data = &i.instances;
+            for ((p, size), color) in data.positions.iter().zip(&data.sizes).zip(&data.colors) {
+                let half = size.abs() * 0.5;
+                match i.shape {
+                    InstanceShape::Square => out.push_str(&format!(
+                        r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+                        p.x - half,
+                        p.y - half,
+                        2.0 * half,
+                        2.0 * half,
+                    )),
+                    InstanceShape::Circle => {
+                        out.push_str(&format!(r#"<circle cx="{}" cy="{}" r="{half}""#, p.x, p.y));
+                    }
+                }
+                write_paint_attr(out, "fill", &Brush::Solid(*color), 1.0);
+                out.push_str("/>\n");
+            }
+            out.push_str("</g>\n");
+        }
+        // Groups are written by `SvgScene::write_items`.
+        MarkPayload::Group(_) => {}
+    }
 }
 
 fn estimate_text_bounds_anchored(
@@ -175,12 +397,19 @@ fn estimate_text_bounds_anchored(
     anchor: TextAnchor,
     baseline: TextBaseline,
     text: &str,
+    line_height: f64,
 ) -> Rect {
     // Very rough heuristic: assume ~0.6em average glyph width.
     //
     // `y` is interpreted according to the given baseline; we approximate a midline from it.
     let glyph_w = 0.6 * font_size;
-    let width = glyph_w * text.chars().count() as f64;
+    let max_chars = text
+        .split('\n')
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let extra_lines = text.matches('\n').count() as f64;
+    let width = glyph_w * max_chars as f64;
     let half_height = 0.5 * font_size;
     let y_midline = match baseline {
         TextBaseline::Middle => y,
@@ -194,7 +423,14 @@ fn estimate_text_bounds_anchored(
         TextAnchor::Middle => (x - width / 2.0, x + width / 2.0),
         TextAnchor::End => (x - width, x),
     };
-    Rect::new(x0, y_midline - half_height, x1, y_midline + half_height)
+    // Additional lines flow downwards from the first one.
+    let extra = extra_lines * line_height * font_size;
+    Rect::new(
+        x0,
+        y_midline - half_height,
+        x1,
+        y_midline + half_height + extra,
+    )
 }
 
 fn svg_paint(brush: &Brush) -> (String, Option<f64>) {
@@ -213,14 +449,102 @@ fn svg_paint(brush: &Brush) -> (String, Option<f64>) {
     }
 }
 
-fn write_paint_attr(out: &mut String, name: &str, brush: &Brush) {
-    let (value, opacity) = svg_paint(brush);
+/// Writes a paint attribute, folding the brush alpha and the channel opacity into `{name}-opacity`.
+fn write_paint_attr(out: &mut String, name: &str, brush: &Brush, opacity: f64) {
+    let (value, alpha) = svg_paint(brush);
     out.push_str(&format!(r#" {name}="{value}""#));
-    if let Some(o) = opacity {
+    let o = alpha.unwrap_or(1.0) * opacity;
+    if o != 1.0 {
         out.push_str(&format!(r#" {name}-opacity="{o}""#));
     }
 }
 
+fn write_compositing_attrs(out: &mut String, opacity: f64, blend_mode: Option<BlendMode>) {
+    if opacity != 1.0 {
+        out.push_str(&format!(r#" opacity="{opacity}""#));
+    }
+    // SVG/CSS only expose separable and non-separable mix modes; composition is ignored.
+    let mix = match blend_mode.map(|b| b.mix) {
+        None | Some(Mix::Normal) => return,
+        Some(Mix::Multiply) => "multiply",
+        Some(Mix::Screen) => "screen",
+        Some(Mix::Overlay) => "overlay",
+        Some(Mix::Darken) => "darken",
+        Some(Mix::Lighten) => "lighten",
+        Some(Mix::ColorDodge) => "color-dodge",
+        Some(Mix::ColorBurn) => "color-burn",
+        Some(Mix::HardLight) => "hard-light",
+        Some(Mix::SoftLight) => "soft-light",
+        Some(Mix::Difference) => "difference",
+        Some(Mix::Exclusion) => "exclusion",
+        Some(Mix::Hue) => "hue",
+        Some(Mix::Saturation) => "saturation",
+        Some(Mix::Color) => "color",
+        Some(Mix::Luminosity) => "luminosity",
+    };
+    out.push_str(&format!(r#" style="mix-blend-mode:{mix}""#));
+}
+
+/// Writes stroke properties that differ from the SVG defaults (butt caps, miter joins, limit 4).
+fn write_stroke_style_attrs(out: &mut String, style: &Stroke) {
+    if !style.dash_pattern.is_empty() {
+        let dashes: Vec<String> = style.dash_pattern.iter().map(f64::to_string).collect();
+        out.push_str(&format!(r#" stroke-dasharray="{}""#, dashes.join(" ")));
+        if style.dash_offset != 0.0 {
+            out.push_str(&format!(r#" stroke-dashoffset="{}""#, style.dash_offset));
+        }
+    }
+    // SVG has a single `stroke-linecap`; use the start cap.
+    match style.start_cap {
+        Cap::Butt => {}
+        Cap::Round => out.push_str(r#" stroke-linecap="round""#),
+        Cap::Square => out.push_str(r#" stroke-linecap="square""#),
+    }
+    match style.join {
+        Join::Miter => {
+            if style.miter_limit != 4.0 {
+                out.push_str(&format!(r#" stroke-miterlimit="{}""#, style.miter_limit));
+            }
+        }
+        Join::Round => out.push_str(r#" stroke-linejoin="round""#),
+        Join::Bevel => out.push_str(r#" stroke-linejoin="bevel""#),
+    }
+}
+
+/// Writes `role` and `aria-label` (from the description) attributes.
+fn write_meta_attrs(out: &mut String, meta: &MarkMeta) {
+    if !meta.aria_role.is_empty() {
+        out.push_str(&format!(r#" role="{}""#, escape_xml(&meta.aria_role)));
+    }
+    if !meta.description.is_empty() {
+        out.push_str(&format!(
+            r#" aria-label="{}""#,
+            escape_xml(&meta.description)
+        ));
+    }
+}
+
+/// Writes `<title>` (the tooltip, shown on hover by browsers) and `<desc>` child elements.
+fn write_meta_children(out: &mut String, meta: &MarkMeta) {
+    if !meta.tooltip.is_empty() {
+        out.push_str(&format!("<title>{}</title>", escape_xml(&meta.tooltip)));
+    }
+    if !meta.description.is_empty() {
+        out.push_str(&format!("<desc>{}</desc>", escape_xml(&meta.description)));
+    }
+}
+
+/// Closes an open start tag, self-closing it unless it needs metadata children.
+fn close_element(out: &mut String, tag: &str, meta: &MarkMeta) {
+    if meta.tooltip.is_empty() && meta.description.is_empty() {
+        out.push_str("/>\n");
+    } else {
+        out.push('>');
+        write_meta_children(out, meta);
+        out.push_str(&format!("</{tag}>\n"));
+    }
+}
+
 fn escape_xml(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     for c in s.chars() {
@@ -0,0 +1,687 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Animated transitions driven by the [`MarkDiff`] stream.
+//!
+//! [`interpolate`] blends two payloads of the same kind. [`Animator`] consumes diffs, starts a
+//! timed transition per mark (from the old payload, or from a faded/collapsed state for enters),
+//! and samples intermediate payloads each frame.
+//!
+//! Numeric channels (geometry, colors, widths, opacities, transforms) are interpolated. Other
+//! channels (text content, fonts, anchors, blend modes, gradients, images) take their target
+//! value immediately, as do paths whose element structure differs. Instances are matched by key;
+//! entering instances appear at their target state.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use hashbrown::HashMap;
+use kurbo::{Affine, BezPath, PathEl, Point, Rect, RoundedRectRadii, Shape};
+use peniko::Brush;
+
+use crate::{GroupClip, InstanceData, MarkDiff, MarkId, MarkKind, MarkPayload};
+
+/// An easing curve mapping linear progress in `0..=1` to eased progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Quadratic, accelerating from zero velocity.
+    QuadIn,
+    /// Quadratic, decelerating to zero velocity.
+    QuadOut,
+    /// Quadratic, accelerating then decelerating.
+    QuadInOut,
+    /// Cubic, accelerating from zero velocity.
+    CubicIn,
+    /// Cubic, decelerating to zero velocity.
+    CubicOut,
+    /// Cubic, accelerating then decelerating (Vega's default).
+    #[default]
+    CubicInOut,
+}
+
+impl Easing {
+    /// Applies the curve to `t`, which is clamped to `0..=1`.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = if t.is_nan() { 1.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Self::Linear => t,
+            Self::QuadIn => t * t,
+            Self::QuadOut => t * (2.0 - t),
+            Self::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - u * u / 2.0
+                }
+            }
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - u * u * u / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// How marks appear on enter or disappear on exit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TransitionEffect {
+    /// Appear or disappear immediately.
+    Snap,
+    /// Animate opacity from/to zero.
+    #[default]
+    Fade,
+    /// Grow from / shrink to the center of the mark's bounds (font size, for text).
+    Scale,
+}
+
+/// Timing and effects for [`Animator`] transitions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TransitionConfig {
+    /// Duration of each transition.
+    pub duration: Duration,
+    /// Easing applied to transition progress.
+    pub easing: Easing,
+    /// Effect used for entering marks.
+    pub enter: TransitionEffect,
+    /// Effect used for exiting marks.
+    pub exit: TransitionEffect,
+}
+
+impl Default for TransitionConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(250),
+            easing: Easing::CubicInOut,
+            enter: TransitionEffect::Fade,
+            exit: TransitionEffect::Fade,
+        }
+    }
+}
+
+impl TransitionConfig {
+    /// Sets the transition duration.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the enter effect.
+    pub fn with_enter(mut self, effect: TransitionEffect) -> Self {
+        self.enter = effect;
+        self
+    }
+
+    /// Sets the exit effect.
+    pub fn with_exit(mut self, effect: TransitionEffect) -> Self {
+        self.exit = effect;
+        self
+    }
+}
+
+/// Which diff started a transition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionPhase {
+    /// The mark is entering.
+    Enter,
+    /// The mark's channels are changing.
+    Update,
+    /// The mark is exiting; renderers should drop it once the transition is done.
+    Exit,
+}
+
+/// A mark's sampled state for one animation frame (see [`Animator::sample`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimatedMark {
+    /// Stable identifier.
+    pub id: MarkId,
+    /// Z-ordering for rendering.
+    pub z_index: i32,
+    /// Containing group, if any.
+    pub parent: Option<MarkId>,
+    /// The mark kind.
+    pub kind: MarkKind,
+    /// Interpolated channels to draw this frame.
+    pub payload: MarkPayload,
+    /// The diff that started the transition.
+    pub phase: TransitionPhase,
+    /// Whether this is the transition's final frame.
+    pub done: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Transition {
+    z_index: i32,
+    parent: Option<MarkId>,
+    from: MarkPayload,
+    to: MarkPayload,
+    start: Duration,
+    phase: TransitionPhase,
+    /// `from == to` (e.g. a [`TransitionEffect::Snap`] enter or exit): finishes immediately.
+    instant: bool,
+}
+
+impl Transition {
+    fn payload_at(&self, t: f64) -> MarkPayload {
+        interpolate(&self.from, &self.to, t)
+    }
+}
+
+/// Turns [`MarkDiff`]s into timed transitions and samples them per frame.
+///
+/// Apply each batch of diffs with [`Animator::apply`], then call [`Animator::sample`] once per
+/// frame while [`Animator::is_animating`] is `true`, drawing each returned payload in place of the
+/// mark's stored one. Diffs that arrive mid-transition retarget from the currently displayed
+/// state, so rapid refreshes do not jump.
+#[derive(Clone, Debug, Default)]
+pub struct Animator {
+    config: TransitionConfig,
+    transitions: HashMap<MarkId, Transition>,
+}
+
+impl Animator {
+    /// Creates an animator with the given transition config.
+    pub fn new(config: TransitionConfig) -> Self {
+        Self {
+            config,
+            transitions: HashMap::new(),
+        }
+    }
+
+    /// Returns the transition config.
+    pub fn config(&self) -> &TransitionConfig {
+        &self.config
+    }
+
+    /// Replaces the transition config; running transitions keep their start time.
+    pub fn set_config(&mut self, config: TransitionConfig) {
+        self.config = config;
+    }
+
+    /// Returns `true` while any transition is running.
+    pub fn is_animating(&self) -> bool {
+        !self.transitions.is_empty()
+    }
+
+    /// Drops all running transitions.
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+
+    /// Starts transitions for a batch of diffs, at time `now`.
+    pub fn apply(&mut self, diffs: &[MarkDiff], now: Duration) {
+        for diff in diffs {
+            self.apply_diff(diff, now);
+        }
+    }
+
+    /// Starts a transition for a single diff, at time `now`.
+    pub fn apply_diff(&mut self, diff: &MarkDiff, now: Duration) {
+        let (id, z_index, parent, phase, from, to) = match diff {
+            MarkDiff::Enter {
+                id,
+                z_index,
+                parent,
+                new,
+                ..
+            } => {
+                let from = self
+                    .current(*id, now)
+                    .unwrap_or_else(|| collapse(new, self.config.enter));
+                (
+                    *id,
+                    *z_index,
+                    *parent,
+                    TransitionPhase::Enter,
+                    from,
+                    (**new).clone(),
+                )
+            }
+            MarkDiff::Update {
+                id,
+                new_z_index,
+                new_parent,
+                old,
+                new,
+                ..
+            } => {
+                // Without an old payload (and no transition in flight), there is nothing to
+                // interpolate from, so the mark snaps to its new state.
+                let from = self
+                    .current(*id, now)
+                    .or_else(|| old.as_deref().cloned())
+                    .unwrap_or_else(|| (**new).clone());
+                (
+                    *id,
+                    *new_z_index,
+                    *new_parent,
+                    TransitionPhase::Update,
+                    from,
+                    (**new).clone(),
+                )
+            }
+            MarkDiff::Exit {
+                id,
+                z_index,
+                parent,
+                old,
+                ..
+            } => {
+                let Some(from) = self.current(*id, now).or_else(|| old.as_deref().cloned()) else {
+                    // Never evaluated, so never drawn: nothing to animate.
+                    self.transitions.remove(id);
+                    return;
+                };
+                let to = collapse(&from, self.config.exit);
+                (*id, *z_index, *parent, TransitionPhase::Exit, from, to)
+            }
+        };
+        let instant = from == to;
+        self.transitions.insert(
+            id,
+            Transition {
+                z_index,
+                parent,
+                from,
+                to,
+                start: now,
+                phase,
+                instant,
+            },
+        );
+    }
+
+    /// Samples every running transition at time `now`, ordered by [`MarkId`].
+    ///
+    /// Finished transitions are reported once with `done: true` and then dropped.
+    pub fn sample(&mut self, now: Duration) -> Vec<AnimatedMark> {
+        let mut out: Vec<AnimatedMark> = self
+            .transitions
+            .iter()
+            .map(|(id, tr)| {
+                let t = self.progress(tr, now);
+                let done = t >= 1.0;
+                let payload = if done {
+                    tr.to.clone()
+                } else {
+                    tr.payload_at(self.config.easing.apply(t))
+                };
+                AnimatedMark {
+                    id: *id,
+                    z_index: tr.z_index,
+                    parent: tr.parent,
+                    kind: tr.to.kind(),
+                    payload,
+                    phase: tr.phase,
+                    done,
+                }
+            })
+            .collect();
+        out.sort_by_key(|m| m.id);
+        for m in &out {
+            if m.done {
+                self.transitions.remove(&m.id);
+            }
+        }
+        out
+    }
+
+    fn progress(&self, tr: &Transition, now: Duration) -> f64 {
+        let duration = self.config.duration.as_secs_f64();
+        if tr.instant || duration <= 0.0 {
+            return 1.0;
+        }
+        (now.saturating_sub(tr.start).as_secs_f64() / duration).min(1.0)
+    }
+
+    /// The payload currently displayed for a mark with a running transition.
+    fn current(&self, id: MarkId, now: Duration) -> Option<MarkPayload> {
+        let tr = self.transitions.get(&id)?;
+        let t = self.progress(tr, now);
+        Some(tr.payload_at(self.config.easing.apply(t)))
+    }
+}
+
+/// Returns the enter start state (or exit end state) of `payload` for an effect.
+///
+/// Groups have no visual of their own and are returned unchanged.
+pub fn collapse(payload: &MarkPayload, effect: TransitionEffect) -> MarkPayload {
+    let mut out = payload.clone();
+    match (&mut out, effect) {
+        (_, TransitionEffect::Snap) | (MarkPayload::Group(_), _) => {}
+        (MarkPayload::Instances(i), TransitionEffect::Fade) => i.opacity = 0.0,
+        (MarkPayload::Rect(r), TransitionEffect::Fade) => r.opacity = 0.0,
+        (MarkPayload::Text(t), TransitionEffect::Fade) => t.opacity = 0.0,
+        (MarkPayload::Path(p), TransitionEffect::Fade) => p.opacity = 0.0,
+        (MarkPayload::Image(i), TransitionEffect::Fade) => i.opacity = 0.0,
+        (MarkPayload::Rect(r), TransitionEffect::Scale) => {
+            r.rect = Rect::from_center_size(r.rect.center(), (0.0, 0.0));
+            r.stroke_width = 0.0;
+            r.corner_radii = RoundedRectRadii::from_single_radius(0.0);
+        }
+        (MarkPayload::Text(t), TransitionEffect::Scale) => t.font_size = 0.0,
+        (MarkPayload::Path(p), TransitionEffect::Scale) => {
+            let center = p.path.bounding_box().center();
+            p.path.apply_affine(Affine::scale_about(0.0, center));
+            p.stroke_width = 0.0;
+            p.stroke_style.width = 0.0;
+        }
+        (MarkPayload::Image(i), TransitionEffect::Scale) => {
+            i.rect = Rect::from_center_size(i.rect.center(), (0.0, 0.0));
+        }
+        (MarkPayload::Instances(i), TransitionEffect::Scale) => {
+            let mut data = (*i.instances).clone();
+            data.sizes.fill(0.0);
+            i.instances = Arc::new(data);
+        }
+    }
+    out
+}
+
+/// Interpolates between two payloads at eased progress `t` (`0` is `from`, `1` is `to`).
+///
+/// Payloads of different kinds cannot be blended; `to` is returned.
+pub fn interpolate(from: &MarkPayload, to: &MarkPayload, t: f64) -> MarkPayload {
+    let mut out = to.clone();
+    match (from, &mut out) {
+        (MarkPayload::Rect(a), MarkPayload::Rect(b)) => {
+            b.rect = lerp_rect(a.rect, b.rect, t);
+            b.fill = lerp_brush(&a.fill, &b.fill, t);
+            b.stroke = lerp_brush(&a.stroke, &b.stroke, t);
+            b.stroke_width = lerp(a.stroke_width, b.stroke_width, t);
+            b.corner_radii = RoundedRectRadii::new(
+                lerp(a.corner_radii.top_left, b.corner_radii.top_left, t),
+                lerp(a.corner_radii.top_right, b.corner_radii.top_right, t),
+                lerp(a.corner_radii.bottom_right, b.corner_radii.bottom_right, t),
+                lerp(a.corner_radii.bottom_left, b.corner_radii.bottom_left, t),
+            );
+            b.opacity = lerp(a.opacity, b.opacity, t);
+            b.fill_opacity = lerp(a.fill_opacity, b.fill_opacity, t);
+            b.stroke_opacity = lerp(a.stroke_opacity, b.stroke_opacity, t);
+        }
+        (MarkPayload::Text(a), MarkPayload::Text(b)) => {
+            b.pos = a.pos.lerp(b.pos, t);
+            b.font_size = lerp(a.font_size, b.font_size, t);
+            b.angle = lerp(a.angle, b.angle, t);
+            b.fill = lerp_brush(&a.fill, &b.fill, t);
+            b.opacity = lerp(a.opacity, b.opacity, t);
+            b.fill_opacity = lerp(a.fill_opacity, b.fill_opacity, t);
+        }
+        (MarkPayload::Path(a), MarkPayload::Path(b)) => {
+            if let Some(path) = lerp_path(&a.path, &b.path, t) {
+                b.path = path;
+            }
+            b.fill = lerp_brush(&a.fill, &b.fill, t);
+            b.stroke = lerp_brush(&a.stroke, &b.stroke, t);
+            b.stroke_width = lerp(a.stroke_width, b.stroke_width, t);
+            b.stroke_style.width = b.stroke_width;
+            b.opacity = lerp(a.opacity, b.opacity, t);
+            b.fill_opacity = lerp(a.fill_opacity, b.fill_opacity, t);
+            b.stroke_opacity = lerp(a.stroke_opacity, b.stroke_opacity, t);
+        }
+        (MarkPayload::Group(a), MarkPayload::Group(b)) => {
+            let (ca, cb) = (a.transform.as_coeffs(), b.transform.as_coeffs());
+            b.transform = Affine::new(core::array::from_fn(|i| lerp(ca[i], cb[i], t)));
+            match (&a.clip, &mut b.clip) {
+                (Some(GroupClip::Rect(ra)), Some(GroupClip::Rect(rb))) => {
+                    *rb = lerp_rect(*ra, *rb, t);
+                }
+                (Some(GroupClip::Path(pa)), Some(GroupClip::Path(pb))) => {
+                    if let Some(path) = lerp_path(pa, pb, t) {
+                        *pb = path;
+                    }
+                }
+                _ => {}
+            }
+        }
+        (MarkPayload::Image(a), MarkPayload::Image(b)) => {
+            b.rect = lerp_rect(a.rect, b.rect, t);
+            b.opacity = lerp(a.opacity, b.opacity, t);
+        }
+        (MarkPayload::Instances(a), MarkPayload::Instances(b)) => {
+            b.instances = Arc::new(lerp_instances(&a.instances, &b.instances, t));
+            b.opacity = lerp(a.opacity, b.opacity, t);
+        }
+        _ => {}
+    }
+    out
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Blends instances matched by key; instances only in `b` appear at their final state.
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "colors interpolate in f32; the precision loss in `t` is invisible"
+)]
+fn lerp_instances(a: &InstanceData, b: &InstanceData, t: f64) -> InstanceData {
+    let mut out = b.clone();
+    let lerp_one = |out: &mut InstanceData, i: usize, j: usize| {
+        if let (Some(from), Some(to)) = (a.positions.get(i), out.positions.get_mut(j)) {
+            *to = from.lerp(*to, t);
+        }
+        if let (Some(from), Some(to)) = (a.sizes.get(i), out.sizes.get_mut(j)) {
+            *to = lerp(*from, *to, t);
+        }
+        if let (Some(from), Some(to)) = (a.colors.get(i), out.colors.get_mut(j)) {
+            *to = from.lerp_rect(*to, t as f32);
+        }
+    };
+    if a.keys == b.keys {
+        for i in 0..b.len() {
+            lerp_one(&mut out, i, i);
+        }
+    } else {
+        let index: HashMap<u64, usize> = a.keys.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+        for (j, key) in b.keys.iter().enumerate() {
+            if let Some(&i) = index.get(key) {
+                lerp_one(&mut out, i, j);
+            }
+        }
+    }
+    out
+}
+
+fn lerp_rect(a: Rect, b: Rect, t: f64) -> Rect {
+    Rect::new(
+        lerp(a.x0, b.x0, t),
+        lerp(a.y0, b.y0, t),
+        lerp(a.x1, b.x1, t),
+        lerp(a.y1, b.y1, t),
+    )
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "colors interpolate in f32; the precision loss in `t` is invisible"
+)]
+fn lerp_brush(a: &Brush, b: &Brush, t: f64) -> Brush {
+    match (a, b) {
+        (Brush::Solid(a), Brush::Solid(b)) => Brush::Solid(a.lerp_rect(*b, t as f32)),
+        _ => b.clone(),
+    }
+}
+
+/// Interpolates paths point-by-point, or returns `None` if their element structure differs.
+fn lerp_path(a: &BezPath, b: &BezPath, t: f64) -> Option<BezPath> {
+    let (a, b) = (a.elements(), b.elements());
+    if a.len() != b.len() {
+        return None;
+    }
+    let p = |a: Point, b: Point| a.lerp(b, t);
+    a.iter()
+        .zip(b)
+        .map(|(ea, eb)| match (*ea, *eb) {
+            (PathEl::MoveTo(a), PathEl::MoveTo(b)) => Some(PathEl::MoveTo(p(a, b))),
+            (PathEl::LineTo(a), PathEl::LineTo(b)) => Some(PathEl::LineTo(p(a, b))),
+            (PathEl::QuadTo(a1, a2), PathEl::QuadTo(b1, b2)) => {
+                Some(PathEl::QuadTo(p(a1, b1), p(a2, b2)))
+            }
+            (PathEl::CurveTo(a1, a2, a3), PathEl::CurveTo(b1, b2, b3)) => {
+                Some(PathEl::CurveTo(p(a1, b1), p(a2, b2), p(a3, b3)))
+            }
+            (PathEl::ClosePath, PathEl::ClosePath) => Some(PathEl::ClosePath),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(BezPath::from_vec)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::vec;
+
+    use peniko::Color;
+    use peniko::color::palette;
+
+    use super::*;
+    use crate::{Mark, Scene, SignalId};
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn interpolates_rects_colors_and_matching_paths() {
+        let a = crate::RectChannels {
+            rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+            fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
+            ..Default::default()
+        };
+        let b = crate::RectChannels {
+            rect: Rect::new(10.0, 20.0, 30.0, 40.0),
+            fill: Brush::Solid(Color::from_rgba8(200, 100, 0, 255)),
+            ..a.clone()
+        };
+
+        let MarkPayload::Rect(mid) =
+            interpolate(&MarkPayload::Rect(a.clone()), &MarkPayload::Rect(b), 0.5)
+        else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(mid.rect, Rect::new(5.0, 10.0, 20.0, 25.0));
+        assert_eq!(mid.fill, Brush::Solid(Color::from_rgba8(100, 50, 0, 255)));
+
+        let line = |y: f64| {
+            let mut p = BezPath::new();
+            p.move_to((0.0, y));
+            p.line_to((10.0, y));
+            p
+        };
+        let pa = crate::PathChannels {
+            path: line(0.0),
+            ..Default::default()
+        };
+        let mut pb = crate::PathChannels {
+            path: line(10.0),
+            ..pa.clone()
+        };
+        let MarkPayload::Path(mid) = interpolate(
+            &MarkPayload::Path(pa.clone()),
+            &MarkPayload::Path(pb.clone()),
+            0.25,
+        ) else {
+            panic!("expected path payload");
+        };
+        assert_eq!(mid.path, line(2.5));
+
+        // Different structure snaps to the target.
+        pb.path.line_to((20.0, 10.0));
+        let snapped = interpolate(&MarkPayload::Path(pa), &MarkPayload::Path(pb.clone()), 0.25);
+        assert_eq!(snapped, MarkPayload::Path(pb));
+
+        assert_eq!(Easing::CubicInOut.apply(0.5), 0.5);
+        assert_eq!(Easing::QuadOut.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn animator_fades_enter_tweens_update_and_retires_exit() {
+        let mut scene = Scene::new();
+        let width = SignalId(1);
+        scene.insert_signal(width, 10.0_f64);
+        let id = MarkId(7);
+        let mark = || {
+            Mark::builder(id)
+                .x_const(0.0)
+                .y_const(0.0)
+                .h_const(5.0)
+                .fill_const(palette::css::RED)
+                .w_compute(
+                    [crate::InputRef::Signal { signal: width }],
+                    move |ctx, _| ctx.signal::<f64>(width).unwrap_or(0.0),
+                )
+                .build()
+        };
+        let mut animator = Animator::new(
+            TransitionConfig::default()
+                .with_duration(ms(100))
+                .with_easing(Easing::Linear),
+        );
+
+        animator.apply(&scene.tick(vec![mark()]), ms(0));
+        let frame = animator.sample(ms(50));
+        assert_eq!(frame.len(), 1);
+        assert_eq!(frame[0].phase, TransitionPhase::Enter);
+        let MarkPayload::Rect(r) = &frame[0].payload else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.opacity, 0.5);
+        assert!(animator.sample(ms(100))[0].done);
+        assert!(!animator.is_animating());
+
+        // Update, then retarget halfway through.
+        scene.set_signal(width, 30.0_f64).unwrap();
+        animator.apply(&scene.update(), ms(200));
+        scene.set_signal(width, 50.0_f64).unwrap();
+        animator.apply(&scene.update(), ms(250));
+        let MarkPayload::Rect(r) = &animator.sample(ms(250))[0].payload else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.rect.width(), 20.0);
+        let MarkPayload::Rect(r) = &animator.sample(ms(300))[0].payload else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.rect.width(), 35.0);
+
+        // Exit keeps the mark until the fade completes.
+        animator.apply(&scene.tick(Vec::new()), ms(400));
+        let frame = animator.sample(ms(450));
+        assert_eq!(frame[0].phase, TransitionPhase::Exit);
+        assert!(!frame[0].done);
+        let frame = animator.sample(ms(500));
+        assert!(frame[0].done);
+        let MarkPayload::Rect(r) = &frame[0].payload else {
+            panic!("expected rect payload");
+        };
+        assert_eq!((r.opacity, r.rect.width()), (0.0, 50.0));
+
+        // Snap exits finish on the next sample.
+        animator.set_config(animator.config().with_exit(TransitionEffect::Snap));
+        animator.apply(&scene.tick(vec![mark()]), ms(600));
+        animator.sample(ms(700));
+        animator.apply(&scene.tick(Vec::new()), ms(800));
+        assert!(animator.sample(ms(800))[0].done);
+    }
+}
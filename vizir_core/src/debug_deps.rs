@@ -0,0 +1,273 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checking what compute encodings read against their declared deps (`debug-deps` feature).
+//!
+//! With the feature on, [`crate::Scene::update`] evaluates compute encodings under a recording
+//! [`EvalCtx`] (each closure still runs once), then compares the recorded reads to the declared
+//! [`InputRef`]s. Derived signals are audited the same way when they refresh. An undeclared read
+//! means the value silently goes stale when that input changes; an unused declared dep only costs
+//! wasted re-evaluation.
+
+extern crate std;
+
+use alloc::vec::Vec;
+use core::fmt;
+use hashbrown::HashMap;
+use std::sync::Mutex;
+
+use crate::{Encoding, EvalCtx, InputRef, MarkEncodings, MarkId, SignalId, Threading};
+
+/// What owns the compute closure a [`DepViolation`] was found in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DepSource {
+    /// An encoding of a mark.
+    Mark(MarkId),
+    /// A derived signal (see [`crate::Scene::insert_derived_signal`]).
+    DerivedSignal(SignalId),
+}
+
+/// How a compute closure's reads disagree with its declared dependencies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DepViolationKind {
+    /// The closure read an input its deps do not cover, so changes to it are missed.
+    Undeclared(InputRef),
+    /// The closure declared an input it did not read on this evaluation.
+    Unused(InputRef),
+}
+
+/// A mismatch between one compute closure's declared deps and what it read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DepViolation {
+    /// The mark or derived signal that was checked.
+    pub source: DepSource,
+    /// The encoding channel, e.g. `"fill"` or `"tooltip"`; `"value"` for derived signals.
+    pub channel: &'static str,
+    /// What went wrong.
+    pub kind: DepViolationKind,
+}
+
+impl fmt::Display for DepViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source {
+            DepSource::Mark(mark) => write!(f, "mark {mark:?} channel `{}`", self.channel)?,
+            DepSource::DerivedSignal(signal) => write!(f, "derived signal {signal:?}")?,
+        }
+        match self.kind {
+            DepViolationKind::Undeclared(input) => write!(f, " read undeclared input {input:?}"),
+            DepViolationKind::Unused(input) => write!(f, " declared unused input {input:?}"),
+        }
+    }
+}
+
+/// Inputs read through an [`EvalCtx`] while recording one closure.
+#[derive(Debug, Default)]
+pub(crate) struct ReadLog(Mutex<Vec<InputRef>>);
+
+impl ReadLog {
+    pub(crate) fn record(&self, input: InputRef) {
+        let mut reads = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !reads.contains(&input) {
+            reads.push(input);
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<InputRef> {
+        core::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// The reads of every compute encoding evaluated during one update, keyed by mark and encoding.
+///
+/// Encodings are identified by address; they live in boxed per-kind structs, so the address is
+/// stable between evaluation and [`check_mark`].
+#[derive(Debug, Default)]
+pub(crate) struct DepAudit(Mutex<HashMap<(MarkId, usize), Vec<InputRef>>>);
+
+impl DepAudit {
+    pub(crate) fn take(&self) -> HashMap<(MarkId, usize), Vec<InputRef>> {
+        core::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+fn encoding_key<T, M: Threading>(enc: &Encoding<T, M>) -> usize {
+    core::ptr::from_ref(enc).addr()
+}
+
+/// Evaluates a compute encoding under a recording context and logs its reads to `audit`.
+pub(crate) fn eval_recorded<T, M: Threading>(
+    audit: &DepAudit,
+    enc: &Encoding<T, M>,
+    f: &M::ComputeFn<T>,
+    ctx: &EvalCtx<'_, M>,
+    mark: MarkId,
+) -> T {
+    let log = ReadLog::default();
+    let recording = EvalCtx {
+        reads: Some(&log),
+        audit: None,
+        ..*ctx
+    };
+    let value = f(&recording, mark);
+    audit
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert((mark, encoding_key(enc)), log.take());
+    value
+}
+
+/// Checks the compute encodings of `encodings` that were evaluated this update against `reads`.
+///
+/// Encodings skipped by an incremental update have no recorded reads and are not checked.
+pub(crate) fn check_mark<M: Threading>(
+    encodings: &MarkEncodings<M>,
+    mark: MarkId,
+    reads: &HashMap<(MarkId, usize), Vec<InputRef>>,
+    out: &mut Vec<DepViolation>,
+) {
+    for_each_compute(encodings, &mut |channel, deps, key| {
+        if let Some(reads) = reads.get(&(mark, key)) {
+            compare(DepSource::Mark(mark), channel, deps, reads, out);
+        }
+    });
+}
+
+/// Checks a derived signal's declared deps against the reads of its last evaluation.
+pub(crate) fn check_derived(
+    signal: SignalId,
+    deps: &[InputRef],
+    reads: &[InputRef],
+    out: &mut Vec<DepViolation>,
+) {
+    compare(DepSource::DerivedSignal(signal), "value", deps, reads, out);
+}
+
+fn compare(
+    source: DepSource,
+    channel: &'static str,
+    deps: &[InputRef],
+    reads: &[InputRef],
+    out: &mut Vec<DepViolation>,
+) {
+    for read in reads {
+        if !deps.iter().any(|dep| covers(*dep, *read)) {
+            out.push(DepViolation {
+                source,
+                channel,
+                kind: DepViolationKind::Undeclared(*read),
+            });
+        }
+    }
+    for dep in deps {
+        if !reads.iter().any(|read| covers(*dep, *read)) {
+            out.push(DepViolation {
+                source,
+                channel,
+                kind: DepViolationKind::Unused(*dep),
+            });
+        }
+    }
+}
+
+/// Whether declaring `dep` re-evaluates a closure when the input behind `read` changes.
+///
+/// Row-count and row-key reads are recorded as [`InputRef::Table`]. Inserting or deleting rows
+/// bumps every column version, so a column dep covers them. A row-local dep only covers row-local
+/// reads of its column: [`crate::Scene::apply_table_patch`] skips it for rows it did not touch.
+fn covers(dep: InputRef, read: InputRef) -> bool {
+    match (dep, read) {
+        (InputRef::Signal { signal: a }, InputRef::Signal { signal: b }) => a == b,
+        (
+            InputRef::Table { table: a },
+            InputRef::Table { table: b }
+            | InputRef::TableCol { table: b, .. }
+            | InputRef::TableRowCol { table: b, .. },
+        ) => a == b,
+        (InputRef::TableCol { table: a, .. }, InputRef::Table { table: b }) => a == b,
+        (
+            InputRef::TableCol { table: a, col: c },
+            InputRef::TableCol { table: b, col: d } | InputRef::TableRowCol { table: b, col: d },
+        ) => a == b && c == d,
+        (InputRef::TableRowCol { .. }, InputRef::TableRowCol { .. }) => dep == read,
+        _ => false,
+    }
+}
+
+type Visit<'v> = dyn FnMut(&'static str, &[InputRef], usize) + 'v;
+
+/// Calls `visit` with the channel name, deps, and audit key of each compute encoding.
+fn for_each_compute<M: Threading>(encodings: &MarkEncodings<M>, visit: &mut Visit<'_>) {
+    fn one<T, M: Threading>(visit: &mut Visit<'_>, channel: &'static str, enc: &Encoding<T, M>) {
+        if let Encoding::Compute { deps, .. } = enc {
+            visit(channel, deps, encoding_key(enc));
+        }
+    }
+    let meta = encodings.meta();
+    one(visit, "aria_role", &meta.aria_role);
+    one(visit, "description", &meta.description);
+    one(visit, "tooltip", &meta.tooltip);
+    one(visit, "href", &meta.href);
+    match encodings {
+        MarkEncodings::Rect(e) => {
+            one(visit, "x", &e.x);
+            one(visit, "y", &e.y);
+            one(visit, "w", &e.w);
+            one(visit, "h", &e.h);
+            one(visit, "fill", &e.fill);
+            one(visit, "stroke", &e.stroke);
+            one(visit, "stroke_width", &e.stroke_width);
+            one(visit, "corner_radii", &e.corner_radii);
+            one(visit, "opacity", &e.opacity);
+            one(visit, "fill_opacity", &e.fill_opacity);
+            one(visit, "stroke_opacity", &e.stroke_opacity);
+            one(visit, "blend_mode", &e.blend_mode);
+        }
+        MarkEncodings::Text(e) => {
+            one(visit, "x", &e.x);
+            one(visit, "y", &e.y);
+            one(visit, "text", &e.text);
+            one(visit, "font_size", &e.font_size);
+            one(visit, "font_family", &e.font_family);
+            one(visit, "font_weight", &e.font_weight);
+            one(visit, "font_style", &e.font_style);
+            one(visit, "line_height", &e.line_height);
+            one(visit, "angle", &e.angle);
+            one(visit, "anchor", &e.anchor);
+            one(visit, "baseline", &e.baseline);
+            one(visit, "fill", &e.fill);
+            one(visit, "opacity", &e.opacity);
+            one(visit, "fill_opacity", &e.fill_opacity);
+            one(visit, "blend_mode", &e.blend_mode);
+        }
+        MarkEncodings::Path(e) => {
+            one(visit, "path", &e.path);
+            one(visit, "fill", &e.fill);
+            one(visit, "stroke", &e.stroke);
+            one(visit, "stroke_width", &e.stroke_width);
+            one(visit, "stroke_style", &e.stroke_style);
+            one(visit, "opacity", &e.opacity);
+            one(visit, "fill_opacity", &e.fill_opacity);
+            one(visit, "stroke_opacity", &e.stroke_opacity);
+            one(visit, "blend_mode", &e.blend_mode);
+        }
+        MarkEncodings::Group(e) => {
+            one(visit, "transform", &e.transform);
+            one(visit, "clip", &e.clip);
+        }
+        MarkEncodings::Image(e) => {
+            one(visit, "x", &e.x);
+            one(visit, "y", &e.y);
+            one(visit, "w", &e.w);
+            one(visit, "h", &e.h);
+            one(visit, "image", &e.image);
+            one(visit, "quality", &e.quality);
+            one(visit, "opacity", &e.opacity);
+        }
+        MarkEncodings::Instances(e) => {
+            one(visit, "instances", &e.instances);
+            one(visit, "shape", &e.shape);
+            one(visit, "opacity", &e.opacity);
+        }
+    }
+}
@@ -0,0 +1,930 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Hit testing over evaluated marks.
+//!
+//! [`HitIndex`] is a downstream consumer of the [`MarkDiff`] stream, like a renderer: it keeps a
+//! copy of each mark's payload and bounds, buckets marks into a uniform grid in scene
+//! coordinates, and answers point and rectangle queries.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use hashbrown::{HashMap, HashSet};
+use kurbo::{Affine, ParamCurveNearest, Point, Rect, RoundedRect, RoundedRectRadii, Shape};
+use peniko::Brush;
+use smallvec::SmallVec;
+
+use crate::{GroupClip, InstanceData, MarkDiff, MarkId, MarkPayload, Table, TableId, Threading};
+
+/// Default grid cell size, in scene coordinates.
+const DEFAULT_CELL_SIZE: f64 = 64.0;
+
+/// Marks whose bounds cover more grid cells than this are kept in a separate list that every
+/// query scans, instead of being inserted into each cell.
+const MAX_CELLS_PER_MARK: i64 = 256;
+
+/// Maximum group nesting followed when resolving transforms; guards against parent cycles.
+const MAX_DEPTH: usize = 64;
+
+/// A table row that produced a mark (see [`MarkId::for_row`]).
+///
+/// Rows are identified by key, since row indices shift when a [`crate::TablePatch`] deletes
+/// earlier rows; resolve the current index with [`Table::row_index`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RowRef {
+    /// Source table.
+    pub table: TableId,
+    /// Stable row key.
+    pub key: u64,
+}
+
+/// One instance of a [`crate::MarkKind::Instances`] mark (see [`HitIndex::hit_instance`]).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InstanceHit {
+    /// The instance mark.
+    pub mark: MarkId,
+    /// Index into the mark's [`crate::InstanceData`] as of the last applied diff.
+    pub index: usize,
+    /// The instance's key.
+    pub key: u64,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    z_index: i32,
+    parent: Option<MarkId>,
+    payload: MarkPayload,
+    /// Bounds in the parent group's coordinates, as reported by the diff.
+    bounds: Option<Rect>,
+    /// Scene-space bounds and grid cells the mark is currently indexed under.
+    indexed: Option<(Rect, Cells)>,
+    /// Per-instance grid, for instance marks.
+    instances: Option<InstanceGrid>,
+}
+
+/// Buckets the instances of one instance mark by glyph bounds, in the mark's parent coordinates,
+/// so point queries test a handful of instances instead of all of them.
+#[derive(Clone, Debug, Default)]
+struct InstanceGrid {
+    cells: HashMap<(i64, i64), SmallVec<[usize; 4]>>,
+    /// Instances covering more than [`MAX_CELLS_PER_MARK`] cells; every query tests these.
+    large: Vec<usize>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Cells {
+    Range { x0: i64, y0: i64, x1: i64, y1: i64 },
+    Large,
+}
+
+/// A spatial index over evaluated marks, maintained by applying [`MarkDiff`]s.
+///
+/// Group marks are not hit targets themselves; their transforms and clips are applied to their
+/// descendants. Rect, path, and image marks are tested exactly (rects and paths by their fill
+/// area when the fill is visible, plus the outline when stroked); instance marks are hit if any
+/// instance's glyph contains the point (use [`HitIndex::hit_instance`] to find which), using a
+/// grid of their own; text marks are tested against their bounds, so they are only indexed when
+/// diffs carry bounds (see [`crate::Scene::set_text_measurer`]).
+///
+/// Results are ordered like a renderer draws: by the `(z_index, MarkId)` of each ancestor group
+/// from the root down, then of the mark itself.
+#[derive(Clone, Debug)]
+pub struct HitIndex {
+    cell_size: f64,
+    entries: HashMap<MarkId, Entry>,
+    grid: HashMap<(i64, i64), SmallVec<[MarkId; 4]>>,
+    large: HashSet<MarkId>,
+    children: HashMap<MarkId, HashSet<MarkId>>,
+    rows: HashMap<MarkId, RowRef>,
+}
+
+impl Default for HitIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HitIndex {
+    /// Creates an empty index with the default grid cell size.
+    pub fn new() -> Self {
+        Self::with_cell_size(DEFAULT_CELL_SIZE)
+    }
+
+    /// Creates an empty index whose grid cells are `cell_size` scene units wide and tall.
+    ///
+    /// Non-finite or non-positive sizes fall back to the default.
+    pub fn with_cell_size(cell_size: f64) -> Self {
+        let cell_size = if cell_size.is_finite() && cell_size > 0.0 {
+            cell_size
+        } else {
+            DEFAULT_CELL_SIZE
+        };
+        Self {
+            cell_size,
+            entries: HashMap::new(),
+            grid: HashMap::new(),
+            large: HashSet::new(),
+            children: HashMap::new(),
+            rows: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of marks known to the index (including groups).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no marks are known to the index.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all marks and row registrations.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.grid.clear();
+        self.large.clear();
+        self.children.clear();
+        self.rows.clear();
+    }
+
+    /// Applies a batch of diffs, as returned by [`crate::Scene::tick`].
+    pub fn apply(&mut self, diffs: &[MarkDiff]) {
+        for diff in diffs {
+            self.apply_diff(diff);
+        }
+    }
+
+    /// Applies a single diff.
+    pub fn apply_diff(&mut self, diff: &MarkDiff) {
+        match diff {
+            MarkDiff::Enter {
+                id,
+                z_index,
+                parent,
+                new,
+                bounds,
+                ..
+            } => self.upsert(*id, *z_index, *parent, new, *bounds),
+            MarkDiff::Update {
+                id,
+                new_z_index,
+                new_parent,
+                new,
+                new_bounds,
+                ..
+            } => self.upsert(*id, *new_z_index, *new_parent, new, *new_bounds),
+            MarkDiff::Exit { id, .. } => self.remove(*id),
+        }
+    }
+
+    /// Returns the scene-space bounds a mark is indexed under, if any.
+    pub fn bounds(&self, id: MarkId) -> Option<Rect> {
+        self.entries.get(&id)?.indexed.map(|(rect, _)| rect)
+    }
+
+    /// Returns the topmost mark containing `point` (in scene coordinates).
+    pub fn hit(&self, point: Point) -> Option<MarkId> {
+        self.candidates_at(point)
+            .into_iter()
+            .filter(|id| self.contains(*id, point))
+            .max_by(|a, b| self.cmp_paint_order(*a, *b))
+    }
+
+    /// Returns every mark containing `point`, topmost first.
+    pub fn hit_all(&self, point: Point) -> Vec<MarkId> {
+        let mut hits: Vec<MarkId> = self
+            .candidates_at(point)
+            .into_iter()
+            .filter(|id| self.contains(*id, point))
+            .collect();
+        hits.sort_by(|a, b| self.cmp_paint_order(*b, *a));
+        hits
+    }
+
+    /// Returns every mark whose scene-space bounds intersect `rect`, bottommost first.
+    ///
+    /// This is intended for brushing: it tests bounds (after group clips) rather than exact
+    /// geometry.
+    pub fn query_rect(&self, rect: Rect) -> Vec<MarkId> {
+        let rect = rect.abs();
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        let (x0, y0) = self.cell_of(Point::new(rect.x0, rect.y0));
+        let (x1, y1) = self.cell_of(Point::new(rect.x1, rect.y1));
+        let count = (x1 - x0 + 1).saturating_mul(y1 - y0 + 1);
+        let mut cells: Vec<&SmallVec<[MarkId; 4]>> = Vec::new();
+        if usize::try_from(count).is_ok_and(|count| count <= self.grid.len()) {
+            for cx in x0..=x1 {
+                cells.extend((y0..=y1).filter_map(|cy| self.grid.get(&(cx, cy))));
+            }
+        } else {
+            // The query covers more cells than are occupied; scan the occupied ones.
+            cells.extend(self.grid.values());
+        }
+        let ids = self
+            .large
+            .iter()
+            .copied()
+            .chain(cells.into_iter().flatten().copied());
+        for id in ids {
+            if !seen.insert(id) {
+                continue;
+            }
+            let Some((bounds, _)) = self.entries.get(&id).and_then(|e| e.indexed) else {
+                continue;
+            };
+            if overlaps(bounds, rect) {
+                out.push(id);
+            }
+        }
+        out.sort_by(|a, b| self.cmp_paint_order(*a, *b));
+        out
+    }
+
+    /// Records the marks of `table`'s rows so hits can be mapped back via [`HitIndex::row`].
+    ///
+    /// Mark ids are derived with [`MarkId::for_row`]. The index does not observe tables, so call
+    /// this again whenever the table's rows change (e.g. after
+    /// [`Scene::apply_table_patch`](crate::Scene::apply_table_patch) or
+    /// [`Scene::set_table_row_keys`](crate::Scene::set_table_row_keys)); marks of rows added since
+    /// the last call have no [`HitIndex::row`]. Previous registrations for the same table are
+    /// replaced.
+    pub fn register_rows<M: Threading>(&mut self, table: &Table<M>) {
+        self.unregister_rows(table.id);
+        for &key in table.row_keys() {
+            self.rows.insert(
+                MarkId::for_row(table.id, key),
+                RowRef {
+                    table: table.id,
+                    key,
+                },
+            );
+        }
+    }
+
+    /// Forgets the row registrations of a table.
+    pub fn unregister_rows(&mut self, table: TableId) {
+        self.rows.retain(|_, r| r.table != table);
+    }
+
+    /// Returns the table row that produced `id`, if its table was registered.
+    pub fn row(&self, id: MarkId) -> Option<RowRef> {
+        self.rows.get(&id).copied()
+    }
+
+    /// Returns the row of the topmost row-backed mark containing `point`.
+    ///
+    /// Marks without a registered row (axes, legends, groups) are skipped, so a label drawn over
+    /// a bar does not hide the bar's row.
+    pub fn hit_row(&self, point: Point) -> Option<(MarkId, RowRef)> {
+        self.candidates_at(point)
+            .into_iter()
+            .filter_map(|id| Some((id, self.row(id)?)))
+            .filter(|(id, _)| self.contains(*id, point))
+            .max_by(|a, b| self.cmp_paint_order(a.0, b.0))
+    }
+
+    /// Returns the topmost instance of an instance mark containing `point`.
+    ///
+    /// Other mark kinds are skipped. Within one mark, later instances are above earlier ones.
+    pub fn hit_instance(&self, point: Point) -> Option<InstanceHit> {
+        self.candidates_at(point)
+            .into_iter()
+            .filter_map(|id| {
+                let (entry, local) = self.local_point(id, point)?;
+                let MarkPayload::Instances(i) = &entry.payload else {
+                    return None;
+                };
+                let index = self.instance_at(entry, local)?;
+                Some(InstanceHit {
+                    mark: id,
+                    index,
+                    key: *i.instances.keys.get(index)?,
+                })
+            })
+            .max_by(|a, b| self.cmp_paint_order(a.mark, b.mark))
+    }
+
+    fn upsert(
+        &mut self,
+        id: MarkId,
+        z_index: i32,
+        parent: Option<MarkId>,
+        payload: &MarkPayload,
+        bounds: Option<Rect>,
+    ) {
+        let old_parent = self.entries.get(&id).and_then(|e| e.parent);
+        if old_parent != parent {
+            self.unlink(id, old_parent);
+        }
+        if let Some(parent) = parent {
+            self.children.entry(parent).or_default().insert(id);
+        }
+        let bounds = bounds.or_else(|| payload.bounds());
+        let indexed = self.entries.get(&id).and_then(|e| e.indexed);
+        let instances = match payload {
+            MarkPayload::Instances(i) => Some(self.instance_grid(&i.instances)),
+            _ => None,
+        };
+        self.entries.insert(
+            id,
+            Entry {
+                z_index,
+                parent,
+                payload: payload.clone(),
+                bounds,
+                indexed,
+                instances,
+            },
+        );
+        if matches!(payload, MarkPayload::Group(_)) {
+            // A group's transform or clip moves every descendant.
+            self.reindex_subtree(id);
+        } else {
+            self.reindex(id);
+        }
+    }
+
+    fn remove(&mut self, id: MarkId) {
+        self.unindex(id);
+        if let Some(entry) = self.entries.remove(&id) {
+            self.unlink(id, entry.parent);
+            if matches!(entry.payload, MarkPayload::Group(_)) {
+                // Orphaned children (if any) now resolve without this group's transform.
+                self.reindex_subtree(id);
+            }
+        }
+    }
+
+    fn unlink(&mut self, id: MarkId, parent: Option<MarkId>) {
+        if let Some(parent) = parent
+            && let Some(siblings) = self.children.get_mut(&parent)
+        {
+            siblings.remove(&id);
+            if siblings.is_empty() {
+                self.children.remove(&parent);
+            }
+        }
+    }
+
+    fn reindex_subtree(&mut self, root: MarkId) {
+        let mut stack = Vec::new();
+        let mut visited = HashSet::new();
+        stack.push(root);
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            self.reindex(id);
+            if let Some(children) = self.children.get(&id) {
+                stack.extend(children.iter().copied());
+            }
+        }
+    }
+
+    fn reindex(&mut self, id: MarkId) {
+        self.unindex(id);
+        let Some(entry) = self.entries.get(&id) else {
+            return;
+        };
+        if matches!(entry.payload, MarkPayload::Group(_)) {
+            return;
+        }
+        let Some(local) = entry.bounds else {
+            return;
+        };
+        let Some((transform, clip)) = self.resolve(entry.parent) else {
+            return;
+        };
+        let mut rect = transform.transform_rect_bbox(local);
+        if let Some(clip) = clip {
+            rect = rect.intersect(clip);
+        }
+        if !(rect.is_finite() && rect.width() >= 0.0 && rect.height() >= 0.0) {
+            return;
+        }
+        let (x0, y0) = self.cell_of(Point::new(rect.x0, rect.y0));
+        let (x1, y1) = self.cell_of(Point::new(rect.x1, rect.y1));
+        let count = (x1 - x0 + 1).saturating_mul(y1 - y0 + 1);
+        let cells = if count > MAX_CELLS_PER_MARK {
+            self.large.insert(id);
+            Cells::Large
+        } else {
+            for cx in x0..=x1 {
+                for cy in y0..=y1 {
+                    self.grid.entry((cx, cy)).or_default().push(id);
+                }
+            }
+            Cells::Range { x0, y0, x1, y1 }
+        };
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.indexed = Some((rect, cells));
+        }
+    }
+
+    fn unindex(&mut self, id: MarkId) {
+        let Some((_, cells)) = self.entries.get_mut(&id).and_then(|e| e.indexed.take()) else {
+            return;
+        };
+        match cells {
+            Cells::Large => {
+                self.large.remove(&id);
+            }
+            Cells::Range { x0, y0, x1, y1 } => {
+                for cx in x0..=x1 {
+                    for cy in y0..=y1 {
+                        if let Some(ids) = self.grid.get_mut(&(cx, cy)) {
+                            ids.retain(|other| *other != id);
+                            if ids.is_empty() {
+                                self.grid.remove(&(cx, cy));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves the transform from a group's local coordinates to scene coordinates, along with
+    /// the scene-space bounding box of all enclosing clips.
+    ///
+    /// Missing groups resolve as the identity (their diff may not have been applied yet). Returns
+    /// `None` if the parent chain is cyclic or too deep.
+    fn resolve(&self, mut parent: Option<MarkId>) -> Option<(Affine, Option<Rect>)> {
+        let mut groups = SmallVec::<[&crate::GroupChannels; 8]>::new();
+        while let Some(id) = parent {
+            if groups.len() >= MAX_DEPTH {
+                return None;
+            }
+            let Some(entry) = self.entries.get(&id) else {
+                break;
+            };
+            if let MarkPayload::Group(group) = &entry.payload {
+                groups.push(group);
+            }
+            parent = entry.parent;
+        }
+        let mut transform = Affine::IDENTITY;
+        let mut clip: Option<Rect> = None;
+        for group in groups.iter().rev() {
+            transform *= group.transform;
+            if let Some(c) = &group.clip {
+                let c = transform.transform_rect_bbox(c.bounds());
+                clip = Some(clip.map_or(c, |clip| clip.intersect(c)));
+            }
+        }
+        Some((transform, clip))
+    }
+
+    fn cell_of(&self, point: Point) -> (i64, i64) {
+        (
+            floor_to_cell(point.x / self.cell_size),
+            floor_to_cell(point.y / self.cell_size),
+        )
+    }
+
+    fn instance_grid(&self, data: &InstanceData) -> InstanceGrid {
+        let mut grid = InstanceGrid::default();
+        for index in 0..data.len() {
+            let Some(rect) = data.instance_bounds(index).filter(|r| r.is_finite()) else {
+                continue;
+            };
+            let (x0, y0) = self.cell_of(Point::new(rect.x0, rect.y0));
+            let (x1, y1) = self.cell_of(Point::new(rect.x1, rect.y1));
+            if (x1 - x0 + 1).saturating_mul(y1 - y0 + 1) > MAX_CELLS_PER_MARK {
+                grid.large.push(index);
+                continue;
+            }
+            for cx in x0..=x1 {
+                for cy in y0..=y1 {
+                    grid.cells.entry((cx, cy)).or_default().push(index);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Returns the topmost instance of an instance mark containing `local` (in the mark's parent
+    /// coordinates).
+    fn instance_at(&self, entry: &Entry, local: Point) -> Option<usize> {
+        let (MarkPayload::Instances(i), Some(grid)) = (&entry.payload, &entry.instances) else {
+            return None;
+        };
+        let cell = grid.cells.get(&self.cell_of(local));
+        grid.large
+            .iter()
+            .chain(cell.into_iter().flatten())
+            .copied()
+            .filter(|&index| i.instances.instance_contains(i.shape, index, local))
+            .max()
+    }
+
+    fn candidates_at(&self, point: Point) -> SmallVec<[MarkId; 16]> {
+        let mut out: SmallVec<[MarkId; 16]> = self.large.iter().copied().collect();
+        if let Some(ids) = self.grid.get(&self.cell_of(point)) {
+            out.extend(ids.iter().copied());
+        }
+        out
+    }
+
+    /// Exact containment test for `point` in scene coordinates.
+    fn contains(&self, id: MarkId, point: Point) -> bool {
+        let Some((entry, local)) = self.local_point(id, point) else {
+            return false;
+        };
+        match &entry.payload {
+            MarkPayload::Rect(r) => {
+                let rect = r.rect.abs();
+                let half = r.stroke_width.max(0.0) / 2.0;
+                if !RoundedRect::from_rect(rect.inflate(half, half), r.corner_radii).contains(local)
+                {
+                    return false;
+                }
+                if paints(&r.fill, r.fill_opacity) {
+                    return true;
+                }
+                // Unfilled: only the outline band hits.
+                let radii = r.corner_radii;
+                let inner = RoundedRectRadii::new(
+                    (radii.top_left - half).max(0.0),
+                    (radii.top_right - half).max(0.0),
+                    (radii.bottom_right - half).max(0.0),
+                    (radii.bottom_left - half).max(0.0),
+                );
+                half > 0.0
+                    && !RoundedRect::from_rect(rect.inflate(-half, -half), inner).contains(local)
+            }
+            MarkPayload::Path(p) => {
+                if paints(&p.fill, p.fill_opacity) && p.path.contains(local) {
+                    return true;
+                }
+                let half = p.stroke_width.max(0.0) / 2.0;
+                half > 0.0
+                    && p.path
+                        .segments()
+                        .any(|seg| seg.nearest(local, 1e-3).distance_sq <= half * half)
+            }
+            MarkPayload::Image(i) => i.rect.contains(local),
+            MarkPayload::Instances(_) => self.instance_at(entry, local).is_some(),
+            MarkPayload::Text(_) => entry.bounds.is_some_and(|b| b.contains(local)),
+            MarkPayload::Group(_) => false,
+        }
+    }
+
+    /// Maps `point` (in scene coordinates) into the mark's parent coordinates, or returns `None`
+    /// if it falls outside the mark's indexed bounds or an enclosing clip.
+    fn local_point(&self, id: MarkId, point: Point) -> Option<(&Entry, Point)> {
+        let entry = self.entries.get(&id)?;
+        let (bounds, _) = entry.indexed?;
+        // Inclusive, so zero-height rules and points on the far edges still hit.
+        if !(bounds.x0 <= point.x
+            && point.x <= bounds.x1
+            && bounds.y0 <= point.y
+            && point.y <= bounds.y1)
+        {
+            return None;
+        }
+        // Enclosing clips, tested in each group's local coordinates.
+        let mut parent = entry.parent;
+        let mut depth = 0;
+        while let Some(group_id) = parent {
+            depth += 1;
+            if depth > MAX_DEPTH {
+                return None;
+            }
+            let Some(group) = self.entries.get(&group_id) else {
+                break;
+            };
+            if let MarkPayload::Group(channels) = &group.payload
+                && let Some(clip) = &channels.clip
+            {
+                let (transform, _) = self.resolve(Some(group_id))?;
+                let local = transform.inverse() * point;
+                let inside = match clip {
+                    GroupClip::Rect(r) => r.contains(local),
+                    GroupClip::Path(p) => p.contains(local),
+                };
+                if !inside {
+                    return None;
+                }
+            }
+            parent = group.parent;
+        }
+        let (transform, _) = self.resolve(entry.parent)?;
+        Some((entry, transform.inverse() * point))
+    }
+
+    /// Orders two marks by paint order: ancestors' `(z_index, MarkId)` from the root down, then
+    /// the marks' own.
+    fn cmp_paint_order(&self, a: MarkId, b: MarkId) -> Ordering {
+        self.paint_key(a).cmp(&self.paint_key(b))
+    }
+
+    fn paint_key(&self, id: MarkId) -> SmallVec<[(i32, MarkId); 4]> {
+        let mut key = SmallVec::<[(i32, MarkId); 4]>::new();
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if key.len() >= MAX_DEPTH {
+                break;
+            }
+            let Some(entry) = self.entries.get(&id) else {
+                break;
+            };
+            key.push((entry.z_index, id));
+            current = entry.parent;
+        }
+        key.reverse();
+        key
+    }
+}
+
+/// Whether a fill with this brush and opacity covers anything.
+fn paints(brush: &Brush, opacity: f64) -> bool {
+    opacity > 0.0
+        && match brush {
+            Brush::Solid(color) => color.components[3] > 0.0,
+            _ => true,
+        }
+}
+
+/// Rectangle overlap that, unlike [`Rect::overlaps`], treats touching edges and zero-size
+/// bounds (e.g. a horizontal rule) as overlapping.
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "cell coordinates saturate; scenes never span 2^63 cells"
+)]
+fn floor_to_cell(v: f64) -> i64 {
+    if !v.is_finite() {
+        return if v > 0.0 { i64::MAX / 2 } else { i64::MIN / 2 };
+    }
+    // `f64::floor` is unavailable in `no_std`; `as` truncates toward zero and saturates.
+    let t = v as i64;
+    if (t as f64) > v { t - 1 } else { t }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::vec;
+
+    use kurbo::BezPath;
+    use peniko::Color;
+
+    use super::*;
+    use crate::{InputRef, InstanceData, InstanceShape, Mark, Scene, SignalId};
+
+    #[test]
+    fn hit_instance_reports_topmost_instance_key() {
+        let mut data = InstanceData::new();
+        data.push(7, Point::new(10.0, 10.0), 10.0, Color::BLACK);
+        data.push(9, Point::new(14.0, 10.0), 10.0, Color::WHITE);
+        data.push(11, Point::new(100.0, 10.0), 10.0, Color::BLACK);
+        let mut scene = Scene::new();
+        let diffs = scene.tick([
+            Mark::builder(MarkId(1))
+                .group()
+                .transform_const(Affine::translate((50.0, 0.0)))
+                .build(),
+            Mark::builder(MarkId(2))
+                .parent(MarkId(1))
+                .instances()
+                .instances_const(data)
+                .instance_shape_const(InstanceShape::Circle)
+                .build(),
+        ]);
+        let mut index = HitIndex::new();
+        index.apply(&diffs);
+
+        // Overlapping instances: the later one wins.
+        let hit = index.hit_instance(Point::new(62.0, 10.0)).unwrap();
+        assert_eq!(
+            hit,
+            InstanceHit {
+                mark: MarkId(2),
+                index: 1,
+                key: 9,
+            }
+        );
+        assert_eq!(index.hit_instance(Point::new(56.0, 10.0)).unwrap().key, 7);
+        // Inside the square bounds but outside the circle.
+        assert_eq!(index.hit_instance(Point::new(55.5, 5.5)), None);
+        // Between instances, inside the mark's overall bounds.
+        assert_eq!(index.hit_instance(Point::new(100.0, 10.0)), None);
+        assert_eq!(index.hit(Point::new(150.0, 10.0)), Some(MarkId(2)));
+    }
+
+    #[test]
+    fn hit_instance_uses_the_instance_grid() {
+        // A dense lattice spanning many cells, plus one glyph larger than the grid limit.
+        let mut data = InstanceData::new();
+        for k in 0..10_000_u64 {
+            let (col, row) = ((k % 100) as f64, (k / 100) as f64);
+            data.push(k, Point::new(col * 10.0, row * 10.0), 4.0, Color::BLACK);
+        }
+        data.push(
+            u64::MAX,
+            Point::new(1_500.0, 1_500.0),
+            1_500.0,
+            Color::WHITE,
+        );
+        let mut scene = Scene::new();
+        let diffs = scene.tick([Mark::builder(MarkId(1))
+            .instances()
+            .instances_const(data)
+            .build()]);
+        let mut index = HitIndex::new();
+        index.apply(&diffs);
+
+        assert_eq!(
+            index.hit_instance(Point::new(371.0, 529.0)).unwrap().key,
+            5_337
+        );
+        assert_eq!(index.hit_instance(Point::new(375.0, 525.0)), None);
+        // The oversized glyph is drawn last, so it wins where it overlaps the lattice.
+        assert_eq!(
+            index.hit_instance(Point::new(990.0, 990.0)).unwrap().key,
+            u64::MAX
+        );
+        assert_eq!(index.hit(Point::new(2_000.0, 2_000.0)), Some(MarkId(1)));
+    }
+
+    fn triangle() -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((40.0, 0.0));
+        path.line_to((0.0, 40.0));
+        path.close_path();
+        path
+    }
+
+    #[test]
+    fn hit_respects_z_order_groups_and_clips() {
+        let table_id = TableId(3);
+        let mut scene = Scene::new();
+        let mut table = Table::new(table_id);
+        table.set_row_keys(vec![10, 11]);
+        let offset = SignalId(1);
+        scene.insert_signal(offset, 100.0_f64);
+
+        let group = MarkId(1);
+        let bar = MarkId::for_row(table_id, 10);
+        let shape = MarkId::for_row(table_id, 11);
+        let label = MarkId(2);
+        let marks = vec![
+            Mark::builder(group)
+                .group()
+                .transform_compute([InputRef::Signal { signal: offset }], move |ctx, _| {
+                    Affine::translate((ctx.signal::<f64>(offset).unwrap_or(0.0), 0.0))
+                })
+                .clip_const(Some(GroupClip::Rect(Rect::new(0.0, 0.0, 30.0, 100.0))))
+                .build(),
+            Mark::builder(bar)
+                .parent(group)
+                .x_const(0.0)
+                .y_const(0.0)
+                .w_const(20.0)
+                .h_const(80.0)
+                .build(),
+            Mark::builder(shape)
+                .parent(group)
+                .path()
+                .z_index(1)
+                .path_const(triangle())
+                .build(),
+            Mark::builder(label)
+                .x_const(0.0)
+                .y_const(0.0)
+                .w_const(10.0)
+                .h_const(10.0)
+                .z_index(5)
+                .build(),
+        ];
+
+        let mut index = HitIndex::with_cell_size(16.0);
+        index.apply(&scene.tick(marks));
+        index.register_rows(&table);
+        assert_eq!(index.len(), 4);
+
+        // The path is above the bar; outside the triangle the bar wins.
+        assert_eq!(index.hit(Point::new(105.0, 5.0)), Some(shape));
+        assert_eq!(index.hit(Point::new(115.0, 35.0)), Some(bar));
+        assert_eq!(index.hit_all(Point::new(105.0, 5.0)), vec![shape, bar]);
+        // The triangle extends past the group clip.
+        assert_eq!(index.hit(Point::new(135.0, 2.0)), None);
+        assert_eq!(
+            index.row(shape),
+            Some(RowRef {
+                table: table_id,
+                key: 11
+            })
+        );
+        assert_eq!(index.row(label), None);
+
+        // Moving the group re-indexes its children.
+        scene.set_signal(offset, 0.0_f64).unwrap();
+        index.apply(&scene.update());
+        assert_eq!(index.hit(Point::new(115.0, 35.0)), None);
+        assert_eq!(index.hit(Point::new(15.0, 35.0)), Some(bar));
+        // The ungrouped label is drawn above the group, but rows skip it.
+        assert_eq!(index.hit(Point::new(5.0, 5.0)), Some(label));
+        assert_eq!(
+            index
+                .hit_row(Point::new(5.0, 5.0))
+                .map(|(id, row)| (id, row.key)),
+            Some((shape, 11))
+        );
+
+        assert_eq!(
+            index.query_rect(Rect::new(18.0, 50.0, 60.0, 60.0)),
+            vec![bar]
+        );
+        assert_eq!(
+            index.query_rect(Rect::new(-1e9, -1e9, 1e9, 1e9)),
+            vec![bar, shape, label]
+        );
+
+        index.apply(&scene.tick(Vec::new()));
+        assert!(index.is_empty());
+        assert_eq!(index.hit(Point::new(5.0, 5.0)), None);
+    }
+
+    #[test]
+    fn stroked_lines_hit_on_the_stroke_only() {
+        let mut rule = BezPath::new();
+        rule.move_to((0.0, 10.0));
+        rule.line_to((100.0, 10.0));
+        let mut series = BezPath::new();
+        series.move_to((0.0, 100.0));
+        series.line_to((50.0, 50.0));
+        series.line_to((100.0, 100.0));
+        let line = |id, path| {
+            Mark::builder(id)
+                .path()
+                .path_const(path)
+                .fill_const(Color::TRANSPARENT)
+                .stroke_const(Color::BLACK)
+                .stroke_width_const(4.0)
+                .build()
+        };
+        let mut scene = Scene::new();
+        let mut index = HitIndex::with_cell_size(16.0);
+        index.apply(&scene.tick([line(MarkId(1), rule), line(MarkId(2), series)]));
+        assert_eq!(
+            index.bounds(MarkId(1)),
+            Some(Rect::new(-2.0, 8.0, 102.0, 12.0))
+        );
+
+        // A horizontal rule has zero-height geometry; its stroke is still hittable.
+        assert_eq!(index.hit(Point::new(50.0, 10.0)), Some(MarkId(1)));
+        assert_eq!(index.hit(Point::new(50.0, 11.0)), Some(MarkId(1)));
+        assert_eq!(index.hit(Point::new(50.0, 13.0)), None);
+
+        // A stroke-only series is not hit in the empty area under the polyline.
+        assert_eq!(index.hit(Point::new(50.0, 51.0)), Some(MarkId(2)));
+        assert_eq!(index.hit(Point::new(50.0, 90.0)), None);
+    }
+
+    #[test]
+    fn unfilled_rects_hit_on_the_outline_only() {
+        let rect = |id, fill: Color, fill_opacity| {
+            Mark::builder(id)
+                .rect()
+                .x_const(0.0)
+                .y_const(0.0)
+                .w_const(100.0)
+                .h_const(100.0)
+                .fill_const(fill)
+                .fill_opacity_const(fill_opacity)
+                .stroke_const(Color::BLACK)
+                .stroke_width_const(4.0)
+                .build()
+        };
+        let mut scene = Scene::new();
+        let mut index = HitIndex::new();
+        index.apply(&scene.tick([
+            rect(MarkId(1), Color::BLACK, 1.0),
+            rect(MarkId(2), Color::TRANSPARENT, 1.0),
+            rect(MarkId(3), Color::BLACK, 0.0),
+        ]));
+
+        // The filled rect is hit inside; the unfilled ones are drawn over it but see through.
+        assert_eq!(index.hit_all(Point::new(50.0, 50.0)), [MarkId(1)]);
+        // Everything is hit on the outline, including its outer half.
+        assert_eq!(
+            index.hit_all(Point::new(101.0, 50.0)),
+            [MarkId(3), MarkId(2), MarkId(1)]
+        );
+        assert_eq!(
+            index.hit_all(Point::new(50.0, 1.5)),
+            [MarkId(3), MarkId(2), MarkId(1)]
+        );
+        assert_eq!(index.hit(Point::new(50.0, 2.5)), Some(MarkId(1)));
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Packed per-instance data for [`crate::MarkKind::Instances`] marks.
+//!
+//! One instance mark stands in for many small glyphs (e.g. the points of a large scatter plot):
+//! a single encoding produces every position, size, and color, and updates report which
+//! instances entered, changed, or exited by key rather than re-emitting one diff per glyph.
+
+use alloc::vec::Vec;
+
+use hashbrown::{HashMap, HashSet};
+use kurbo::{Point, Rect};
+use peniko::Color;
+
+/// The glyph drawn for every instance of a [`crate::MarkKind::Instances`] mark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InstanceShape {
+    /// An axis-aligned square with side `size`.
+    #[default]
+    Square,
+    /// A circle with diameter `size`.
+    Circle,
+}
+
+/// Instances as parallel arrays: a stable key, center, size, and color per instance.
+///
+/// All four arrays have the same length; use [`InstanceData::push`] to keep them in step. Keys
+/// identify instances across updates (typically table row keys) and are what hit tests report.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstanceData {
+    /// Stable per-instance keys.
+    pub keys: Vec<u64>,
+    /// Glyph centers in scene coordinates.
+    pub positions: Vec<Point>,
+    /// Glyph sizes (square side or circle diameter) in scene coordinates.
+    pub sizes: Vec<f64>,
+    /// Glyph fill colors.
+    pub colors: Vec<Color>,
+}
+
+impl InstanceData {
+    /// Creates empty instance data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates empty instance data with room for `capacity` instances.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            sizes: Vec::with_capacity(capacity),
+            colors: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of instances.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if there are no instances.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Appends one instance.
+    pub fn push(&mut self, key: u64, position: Point, size: f64, color: Color) {
+        self.keys.push(key);
+        self.positions.push(position);
+        self.sizes.push(size);
+        self.colors.push(color);
+    }
+
+    /// Returns the glyph bounds of instance `index`, if it exists.
+    pub fn instance_bounds(&self, index: usize) -> Option<Rect> {
+        let center = *self.positions.get(index)?;
+        let half = self.sizes.get(index)?.abs() * 0.5;
+        Some(Rect::new(
+            center.x - half,
+            center.y - half,
+            center.x + half,
+            center.y + half,
+        ))
+    }
+
+    /// Returns the union of all glyph bounds, or `None` if there are no instances.
+    pub fn bounds(&self) -> Option<Rect> {
+        (0..self.len())
+            .filter_map(|i| self.instance_bounds(i))
+            .reduce(|a, b| a.union(b))
+    }
+
+    /// Returns `true` if the glyph of instance `index` contains `point`.
+    pub fn instance_contains(&self, shape: InstanceShape, index: usize, point: Point) -> bool {
+        let (Some(center), Some(size)) = (self.positions.get(index), self.sizes.get(index)) else {
+            return false;
+        };
+        let half = size.abs() * 0.5;
+        let d = point - *center;
+        match shape {
+            InstanceShape::Square => d.x.abs() <= half && d.y.abs() <= half,
+            InstanceShape::Circle => d.hypot2() <= half * half,
+        }
+    }
+
+    /// Returns the index of the topmost instance whose glyph contains `point`.
+    ///
+    /// Later instances draw above earlier ones. This scans every instance; [`crate::HitIndex`]
+    /// keeps a grid per instance mark instead.
+    pub fn hit(&self, shape: InstanceShape, point: Point) -> Option<usize> {
+        (0..self.len())
+            .rev()
+            .find(|&i| self.instance_contains(shape, i, point))
+    }
+
+    /// Compares two versions of the instances by key.
+    pub fn delta(&self, new: &Self) -> InstanceDelta {
+        let mut delta = InstanceDelta::default();
+        let same = |i: usize, j: usize| {
+            self.positions.get(i) == new.positions.get(j)
+                && self.sizes.get(i) == new.sizes.get(j)
+                && self.colors.get(i) == new.colors.get(j)
+        };
+        if self.keys == new.keys {
+            delta.updated = (0..new.len()).filter(|&i| !same(i, i)).collect();
+            return delta;
+        }
+        let old_index: HashMap<u64, usize> =
+            self.keys.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+        for (j, key) in new.keys.iter().enumerate() {
+            match old_index.get(key) {
+                Some(&i) if !same(i, j) => delta.updated.push(j),
+                Some(_) => {}
+                None => delta.entered.push(j),
+            }
+        }
+        let new_keys: HashSet<u64> = new.keys.iter().copied().collect();
+        delta.exited = self
+            .keys
+            .iter()
+            .copied()
+            .filter(|k| !new_keys.contains(k))
+            .collect();
+        delta
+    }
+}
+
+/// How the instances of a [`crate::MarkKind::Instances`] mark changed in an update.
+///
+/// Indices refer to the new [`InstanceData`]; exited instances are reported by key since they
+/// no longer have an index. Instances that only moved within the arrays are not reported.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstanceDelta {
+    /// New instances (indices into the new data).
+    pub entered: Vec<usize>,
+    /// Instances whose position, size, or color changed (indices into the new data).
+    pub updated: Vec<usize>,
+    /// Keys of instances that were removed.
+    pub exited: Vec<u64>,
+}
+
+impl InstanceDelta {
+    /// Returns `true` if no instance entered, changed, or exited.
+    pub fn is_empty(&self) -> bool {
+        self.entered.is_empty() && self.updated.is_empty() && self.exited.is_empty()
+    }
+}
@@ -9,11 +9,13 @@
 //! - explicit dependency tracking ([`InputRef`])
 //! - incremental evaluation + diff output ([`MarkDiff`])
 //! - per-kind mark payloads ([`MarkPayload`])
+//! - hit testing over the diff stream ([`HitIndex`])
+//! - animated transitions between diffed payloads ([`animate`])
 //!
 //! It intentionally does NOT provide a full visualization grammar.
 //!
 //! Conceptually, a chart frontend can:
-//! - store data in a [`Table`] (row keys + optional column access via [`TableData`])
+//! - store data in a [`Table`] (row keys + optional typed column access via [`TableData`])
 //! - store interaction state in [`Signal`]s (zoom, selection, etc.)
 //! - generate one [`Mark`] per row (bars/points/labels) with stable [`MarkId`]s
 //! - call [`Scene::tick_table_rows`] and apply the resulting [`MarkDiff`] stream to a renderer.
@@ -22,24 +24,44 @@
 
 extern crate alloc;
 
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::any::Any;
 use core::fmt;
-use hashbrown::HashMap;
+use core::time::Duration;
 use hashbrown::hash_map::Entry;
-use kurbo::{BezPath, Point, Rect, Shape};
-use peniko::{Brush, Color};
+use hashbrown::{HashMap, HashSet};
+use kurbo::{Affine, BezPath, Cap, Join, Point, Rect, RoundedRectRadii, Shape, Stroke};
+use peniko::{BlendMode, Brush, Color, ImageData, ImageQuality};
 use smallvec::SmallVec;
 
+pub mod animate;
+#[cfg(feature = "debug-deps")]
+mod debug_deps;
+mod hit;
+mod instances;
+mod record;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod threading;
+
+#[cfg(feature = "debug-deps")]
+pub use debug_deps::{DepSource, DepViolation, DepViolationKind};
+pub use hit::{HitIndex, InstanceHit, RowRef};
+pub use instances::{InstanceData, InstanceDelta, InstanceShape};
+pub use record::{DiffRecording, DiffSink};
+pub use threading::{ComputeFor, Local, Shared, SignalFor, Threading};
+
 /// Monotonic version counter for inputs.
 pub type Version = u64;
 
 /// Stable identifier for a [`Table`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableId(pub u32);
 
 /// Stable identifier for a [`Signal`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignalId(pub u32);
 
 /// Stable identifier for a [`Mark`].
@@ -47,6 +69,7 @@ pub struct SignalId(pub u32);
 /// `MarkId`s must remain stable across frames for the same conceptual visual item; this is what
 /// enables `Enter/Update/Exit` diffs and smooth transitions.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MarkId(pub u64);
 
 impl MarkId {
@@ -69,6 +92,7 @@ impl MarkId {
 
 /// Stable identifier for a table column (placeholder).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColId(pub u32);
 
 /// The geometric "kind" of a mark, which determines how channels are interpreted.
@@ -76,6 +100,7 @@ pub struct ColId(pub u32);
 /// `MarkKind` is derived from [`MarkEncodings`] (and is also echoed on [`MarkDiff`]) so downstream
 /// renderers can interpret [`MarkPayload`] correctly.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MarkKind {
     /// An axis-aligned rectangle using [`RectChannels`].
     Rect,
@@ -83,6 +108,12 @@ pub enum MarkKind {
     Text,
     /// A vector path.
     Path,
+    /// A container that transforms and clips its children (see [`Mark::parent`]).
+    Group,
+    /// A raster image drawn into a destination rectangle using [`ImageChannels`].
+    Image,
+    /// Many same-shaped glyphs drawn from packed arrays using [`InstancesChannels`].
+    Instances,
 }
 
 /// An input reference used for dependency tracking.
@@ -90,6 +121,7 @@ pub enum MarkKind {
 /// These references are declared on computed encodings (see [`Encoding::Compute`]) and determine
 /// what becomes dirty when tables/signals change.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputRef {
     /// Reference an entire table (coarse dependency).
     ///
@@ -108,6 +140,19 @@ pub enum InputRef {
         /// The referenced column.
         col: ColId,
     },
+    /// Reference a table column, read only at the mark's own row.
+    ///
+    /// This resolves to the same version as [`InputRef::TableCol`], but opts the dependency into
+    /// row-level invalidation: [`Scene::apply_table_patch`] refreshes only the marks of patched
+    /// rows instead of every dependent. Read the row through the by-key accessors (such as
+    /// [`EvalCtx::table_f64_by_key`]); use [`InputRef::TableCol`] for encodings that read other
+    /// rows of the column (aggregates, ranks, normalization).
+    TableRowCol {
+        /// The referenced table.
+        table: TableId,
+        /// The referenced column.
+        col: ColId,
+    },
     /// Reference a signal.
     Signal {
         /// The referenced signal.
@@ -117,50 +162,232 @@ pub enum InputRef {
 
 /// Minimal columnar table placeholder.
 /// Replace with Arrow, your own column store, or a trait object later.
+///
+/// Tables carry two levels of versioning:
+/// - [`Table::version`] changes on *any* change and resolves [`InputRef::Table`] dependencies.
+/// - [`Table::column_version`] changes only when that column (or the whole table) changes, and
+///   resolves [`InputRef::TableCol`] dependencies.
+///
+/// [`Table::bump`], [`Table::set_row_keys`], [`Table::set_data`], and patches that insert or delete
+/// rows invalidate every column. Use [`Table::bump_column`] or [`Table::set_column_data`] when only
+/// some columns changed.
 #[derive(Debug)]
-pub struct Table {
+pub struct Table<M: Threading = Local> {
     /// Stable identifier.
     pub id: TableId,
     /// Monotonic version counter.
     pub version: Version,
-    /// Stable keys for each row.
-    ///
-    /// v1 is intentionally minimal: this is enough to derive stable [`MarkId`]s for
-    /// row-driven mark sets without committing to a column representation yet.
-    pub row_keys: Vec<u64>,
+    /// Stable keys for each row (see [`Table::row_keys`]).
+    row_keys: Vec<u64>,
 
     /// Optional columnar access for encodings.
-    pub data: Option<Box<dyn TableData>>,
+    pub data: Option<Box<M::TableData>>,
+
+    /// Table version at the last change that affected every column.
+    columns_version: Version,
+    /// Table version at the last change to each individually bumped column.
+    column_versions: HashMap<ColId, Version>,
+    /// Row key → row index; empty until first needed (see [`Table::row_index`]).
+    row_index: HashMap<u64, usize>,
 }
 
 impl Table {
     /// Create a new table with version `1`.
+    ///
+    /// Use [`Table::new_shared`] for a table that can be inserted into a `Scene<Shared>`.
     pub fn new(id: TableId) -> Self {
+        Self::empty(id)
+    }
+
+    /// Create a new table with version `1` and the given row keys.
+    pub fn with_row_keys(id: TableId, row_keys: Vec<u64>) -> Self {
+        Self {
+            row_keys,
+            ..Self::new(id)
+        }
+    }
+}
+
+impl Table<Shared> {
+    /// Create a new table with version `1` whose data must be `Send + Sync` (see [`Shared`]).
+    pub fn new_shared(id: TableId) -> Self {
+        Self::empty(id)
+    }
+}
+
+impl<M: Threading> Table<M> {
+    fn empty(id: TableId) -> Self {
         Self {
             id,
             version: 1,
             row_keys: Vec::new(),
             data: None,
+            columns_version: 1,
+            column_versions: HashMap::new(),
+            row_index: HashMap::new(),
         }
     }
 
-    /// Increment the version counter.
+    /// Increment the version counter, invalidating every column.
     pub fn bump(&mut self) {
         self.version = self.version.wrapping_add(1);
+        self.columns_version = self.version;
+        self.column_versions.clear();
+    }
+
+    /// Increment the version counter for a single column.
+    ///
+    /// This bumps [`Table::version`] (so whole-table dependencies still observe the change) and
+    /// the column's own version, leaving other columns untouched.
+    pub fn bump_column(&mut self, col: ColId) {
+        self.version = self.version.wrapping_add(1);
+        self.column_versions.insert(col, self.version);
+    }
+
+    /// Return the current version of a column.
+    ///
+    /// Columns that were never bumped individually report the version of the last whole-table
+    /// change.
+    pub fn column_version(&self, col: ColId) -> Version {
+        match self.column_versions.get(&col) {
+            Some(&v) => v.max(self.columns_version),
+            None => self.columns_version,
+        }
+    }
+
+    /// Replace the table's data accessor, bumping only the listed columns.
+    ///
+    /// Use this when the new accessor differs from the old one only in `changed` columns (for
+    /// example, a recolored series). Row keys must be unchanged; otherwise use
+    /// [`Table::set_data`].
+    pub fn set_column_data(
+        &mut self,
+        data: Option<Box<M::TableData>>,
+        changed: impl IntoIterator<Item = ColId>,
+    ) {
+        self.data = data;
+        for col in changed {
+            self.bump_column(col);
+        }
+    }
+
+    /// Apply a row-level patch to the row keys (and data accessor, if provided).
+    ///
+    /// Inserts, deletes, and a replaced data accessor invalidate every column (the row set or
+    /// the backing store changed). A patch that only updates rows bumps the patch's
+    /// `changed_columns` (or every column, if none are listed). Per-row dependents are refreshed
+    /// individually by [`Scene::apply_table_patch`].
+    pub fn apply_patch(&mut self, patch: &mut TablePatch<M>) -> TablePatchRows {
+        let mut rows = TablePatchRows::default();
+        self.index_rows();
+
+        if !patch.deletes.is_empty() {
+            let deletes: HashSet<u64> = patch.deletes.iter().copied().collect();
+            let first = self.row_keys.iter().position(|k| deletes.contains(k));
+            if let Some(first) = first {
+                self.row_keys.retain(|k| {
+                    if deletes.contains(k) {
+                        rows.deleted.push(*k);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                for key in &rows.deleted {
+                    self.row_index.remove(key);
+                }
+                for (row, &key) in self.row_keys.iter().enumerate().skip(first) {
+                    self.row_index.insert(key, row);
+                }
+                rows.shifted = first..self.row_keys.len();
+            }
+        }
+
+        for &key in &patch.inserts {
+            match self.row_index.entry(key) {
+                Entry::Occupied(e) => rows.updated.push(*e.get()),
+                Entry::Vacant(e) => {
+                    let row = self.row_keys.len();
+                    e.insert(row);
+                    self.row_keys.push(key);
+                    rows.inserted.push(row);
+                }
+            }
+        }
+        for key in &patch.updates {
+            if let Some(&row) = self.row_index.get(key) {
+                rows.updated.push(row);
+            }
+        }
+        rows.updated.retain(|row| !rows.inserted.contains(row));
+        rows.updated.sort_unstable();
+        rows.updated.dedup();
+
+        let replaced_data = patch.data.is_some();
+        if let Some(data) = patch.data.take() {
+            self.data = Some(data);
+        }
+        if replaced_data || !rows.inserted.is_empty() || !rows.deleted.is_empty() {
+            self.bump();
+        } else if !patch.is_empty() {
+            if patch.changed_columns.is_empty() {
+                self.bump();
+            } else {
+                for &col in &patch.changed_columns {
+                    self.bump_column(col);
+                }
+            }
+        }
+        rows
+    }
+
+    /// Return the row index of a row key, if present.
+    ///
+    /// Tables used with [`Table::apply_patch`] or [`Scene::tick_table_rows`] keep a key index, so
+    /// this is a hash lookup for them and a linear scan otherwise.
+    pub fn row_index(&self, row_key: u64) -> Option<usize> {
+        if self.row_index.is_empty() {
+            self.row_keys.iter().position(|&k| k == row_key)
+        } else {
+            self.row_index.get(&row_key).copied()
+        }
+    }
+
+    /// Build the row key index, if it is not built yet.
+    ///
+    /// Row keys only change through [`Table::set_row_keys`] and [`Table::apply_patch`], which
+    /// keep a built index in sync.
+    fn index_rows(&mut self) {
+        if self.row_index.is_empty() {
+            self.row_index
+                .extend(self.row_keys.iter().enumerate().map(|(i, &k)| (k, i)));
+        }
     }
 
     /// Replace the table's row keys and bump its version.
     pub fn set_row_keys(&mut self, row_keys: Vec<u64>) {
         self.row_keys = row_keys;
+        if !self.row_index.is_empty() {
+            self.row_index.clear();
+            self.index_rows();
+        }
         self.bump();
     }
 
     /// Set the table's data accessor and bump its version.
-    pub fn set_data(&mut self, data: Option<Box<dyn TableData>>) {
+    pub fn set_data(&mut self, data: Option<Box<M::TableData>>) {
         self.data = data;
         self.bump();
     }
 
+    /// Return the stable key of each row.
+    ///
+    /// Keys derive stable [`MarkId`]s for row-driven mark sets (see [`MarkId::for_row`]). Replace
+    /// them with [`Table::set_row_keys`] or [`Table::apply_patch`].
+    pub fn row_keys(&self) -> &[u64] {
+        &self.row_keys
+    }
+
     /// Return the number of rows.
     pub fn row_count(&self) -> usize {
         self.row_keys.len()
@@ -172,18 +399,241 @@ impl Table {
     }
 }
 
+/// The logical type of a table column, as reported by [`TableData::column_type`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnType {
+    /// Numeric values, read via [`TableData::f64`].
+    F64,
+    /// UTF-8 strings, read via [`TableData::str`].
+    Str,
+    /// Dictionary-encoded categories, read via [`TableData::category`] and resolved to labels via
+    /// [`TableData::category_label`].
+    Category,
+    /// Signed 64-bit timestamps, read via [`TableData::timestamp`].
+    Timestamp,
+    /// Booleans, read via [`TableData::bool`].
+    Bool,
+}
+
 /// Optional columnar access for table-driven mark encodings.
 ///
-/// v0: only `f64` is supported because it's enough for basic charts (positions, sizes).
+/// Only [`TableData::f64`] is required. Typed accessors for strings, categories, timestamps and
+/// booleans have default implementations that return `None`, so numeric-only stores keep working
+/// unchanged; implement the ones your store can serve and report them via
+/// [`TableData::column_type`].
 ///
 /// To use this, implement `TableData` on your column store and set [`Table::data`]. Computed mark
-/// encodings can read values via [`EvalCtx::table_f64`].
+/// encodings can read values via [`EvalCtx::table_f64`] (and the typed variants such as
+/// [`EvalCtx::table_str`]).
 pub trait TableData: fmt::Debug {
     /// Number of rows available via this accessor.
     fn row_count(&self) -> usize;
 
     /// Return a numeric value for a given row/column.
     fn f64(&self, row: usize, col: ColId) -> Option<f64>;
+
+    /// Return a numeric column as a contiguous slice, if the backing store has one.
+    ///
+    /// When present, element `i` must equal `self.f64(i, col)` for every row in
+    /// `0..self.row_count()`. Bulk readers (domain inference, frame extraction, path generation)
+    /// use this to avoid a virtual call per cell; the default returns `None`, and readers then fall
+    /// back to [`TableData::f64`]. See [`F64Column`] for a view that handles both cases.
+    fn f64_slice(&self, col: ColId) -> Option<&[f64]> {
+        let _ = col;
+        None
+    }
+
+    /// Return the logical type of a column, if known.
+    ///
+    /// The default returns `None` (unknown); callers should then treat the column as numeric.
+    fn column_type(&self, col: ColId) -> Option<ColumnType> {
+        let _ = col;
+        None
+    }
+
+    /// Return a string value for a given row/column.
+    fn str(&self, row: usize, col: ColId) -> Option<&str> {
+        let _ = (row, col);
+        None
+    }
+
+    /// Return the dictionary code of a categorical value for a given row/column.
+    ///
+    /// Codes index into the column's dictionary; use [`TableData::category_label`] to resolve
+    /// them to labels.
+    fn category(&self, row: usize, col: ColId) -> Option<u32> {
+        let _ = (row, col);
+        None
+    }
+
+    /// Return the label for a dictionary code in a categorical column.
+    fn category_label(&self, col: ColId, code: u32) -> Option<&str> {
+        let _ = (col, code);
+        None
+    }
+
+    /// Return the number of entries in a categorical column's dictionary.
+    fn category_count(&self, col: ColId) -> Option<usize> {
+        let _ = col;
+        None
+    }
+
+    /// Return a timestamp value for a given row/column.
+    ///
+    /// The unit (seconds, milliseconds, etc) is defined by the caller, as with the domain of a
+    /// `vizir_charts` time scale; use the same unit for both.
+    fn timestamp(&self, row: usize, col: ColId) -> Option<i64> {
+        let _ = (row, col);
+        None
+    }
+
+    /// Return a boolean value for a given row/column.
+    fn bool(&self, row: usize, col: ColId) -> Option<bool> {
+        let _ = (row, col);
+        None
+    }
+}
+
+/// A row-level change set for a [`Table`], keyed by stable row keys.
+///
+/// Patches are applied with [`Scene::apply_table_patch`] (or [`Table::apply_patch`] for the row
+/// keys alone), in this order:
+/// 1. `deletes` are removed (the order of the remaining rows is preserved),
+/// 2. `inserts` are appended in order (inserting a key that is already present is treated as an
+///    update),
+/// 3. `updates` mark existing rows whose values changed in `changed_columns`.
+///
+/// `TableData` is an opaque accessor, so the patch does not carry cell values itself. Either
+/// update the backing store in place (for shared stores), or provide the post-patch accessor via
+/// [`TablePatch::data`].
+#[derive(Debug)]
+pub struct TablePatch<M: Threading = Local> {
+    /// Row keys to append.
+    pub inserts: Vec<u64>,
+    /// Row keys whose values changed.
+    pub updates: Vec<u64>,
+    /// Row keys to remove.
+    pub deletes: Vec<u64>,
+    /// Columns whose values changed for `updates`.
+    ///
+    /// If empty, updated rows are treated as changed in every column.
+    pub changed_columns: Vec<ColId>,
+    /// Optional replacement data accessor reflecting the post-patch table.
+    pub data: Option<Box<M::TableData>>,
+}
+
+impl<M: Threading> Default for TablePatch<M> {
+    fn default() -> Self {
+        Self {
+            inserts: Vec::new(),
+            updates: Vec::new(),
+            deletes: Vec::new(),
+            changed_columns: Vec::new(),
+            data: None,
+        }
+    }
+}
+
+impl TablePatch {
+    /// Create an empty patch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<M: Threading> TablePatch<M> {
+    /// Append row keys to insert.
+    pub fn with_inserts(mut self, row_keys: impl IntoIterator<Item = u64>) -> Self {
+        self.inserts.extend(row_keys);
+        self
+    }
+
+    /// Append row keys to update.
+    pub fn with_updates(mut self, row_keys: impl IntoIterator<Item = u64>) -> Self {
+        self.updates.extend(row_keys);
+        self
+    }
+
+    /// Append row keys to delete.
+    pub fn with_deletes(mut self, row_keys: impl IntoIterator<Item = u64>) -> Self {
+        self.deletes.extend(row_keys);
+        self
+    }
+
+    /// Append columns changed by `updates`.
+    pub fn with_changed_columns(mut self, cols: impl IntoIterator<Item = ColId>) -> Self {
+        self.changed_columns.extend(cols);
+        self
+    }
+
+    /// Set the replacement data accessor.
+    pub fn with_data(mut self, data: Box<M::TableData>) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Returns `true` if the patch touches no rows.
+    pub fn is_empty(&self) -> bool {
+        self.inserts.is_empty() && self.updates.is_empty() && self.deletes.is_empty()
+    }
+}
+
+/// The rows touched by [`Table::apply_patch`], as post-patch row indices.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TablePatchRows {
+    /// Row indices of newly inserted rows.
+    pub inserted: Vec<usize>,
+    /// Row indices of updated rows (that were not inserted and did not move).
+    pub updated: Vec<usize>,
+    /// Row indices of surviving rows whose index changed because earlier rows were deleted.
+    pub shifted: core::ops::Range<usize>,
+    /// Keys of rows that were actually removed.
+    pub deleted: Vec<u64>,
+}
+
+/// A read-only view of a numeric column that prefers contiguous slice access.
+///
+/// Created via [`F64Column::new`] or [`EvalCtx::table_f64_column`]. If the backing store exposes
+/// the column via [`TableData::f64_slice`], reads index the slice directly; otherwise they fall
+/// back to per-cell [`TableData::f64`] calls.
+#[derive(Clone, Copy, Debug)]
+pub enum F64Column<'a> {
+    /// The column is backed by a contiguous slice.
+    Slice(&'a [f64]),
+    /// The column is read one cell at a time.
+    Cells {
+        /// The table data accessor.
+        data: &'a dyn TableData,
+        /// The column to read.
+        col: ColId,
+    },
+}
+
+impl<'a> F64Column<'a> {
+    /// Create a column view, using the slice path when available.
+    pub fn new(data: &'a dyn TableData, col: ColId) -> Self {
+        match data.f64_slice(col) {
+            Some(slice) => Self::Slice(slice),
+            None => Self::Cells { data, col },
+        }
+    }
+
+    /// Return the value at `row`, if present.
+    #[inline]
+    pub fn get(&self, row: usize) -> Option<f64> {
+        match self {
+            Self::Slice(slice) => slice.get(row).copied(),
+            Self::Cells { data, col } => data.f64(row, *col),
+        }
+    }
+
+    /// Return the contiguous slice backing this column, if any.
+    pub fn as_slice(&self) -> Option<&'a [f64]> {
+        match self {
+            Self::Slice(slice) => Some(slice),
+            Self::Cells { .. } => None,
+        }
+    }
 }
 
 /// Type-erased access to a [`Signal`] for storage in a scene.
@@ -229,6 +679,52 @@ impl<T: Clone + 'static> AnySignal for Signal<T> {
     }
 }
 
+type DeriveFn<M> = dyn Fn(&EvalCtx<'_, M>) -> Box<dyn Any> + 'static;
+
+type StoreFn<M> = fn(&mut HashMap<SignalId, Box<<M as Threading>::Signal>>, SignalId, Box<dyn Any>);
+
+/// A signal computed from other inputs (see [`Scene::insert_derived_signal`]).
+struct DerivedSignal<M: Threading> {
+    deps: SmallVec<[InputRef; 4]>,
+    /// Input versions observed at the last evaluation (`None` before the first one).
+    seen: Option<SmallVec<[Option<Version>; 4]>>,
+    eval: Box<DeriveFn<M>>,
+    /// Writes a value produced by `eval`, bumping the signal version only if it changed.
+    store: StoreFn<M>,
+}
+
+fn store_derived<M: SignalFor<T>, T: Clone + PartialEq + 'static>(
+    signals: &mut HashMap<SignalId, Box<M::Signal>>,
+    id: SignalId,
+    value: Box<dyn Any>,
+) {
+    let Ok(value) = value.downcast::<T>() else {
+        return;
+    };
+    match signals
+        .get_mut(&id)
+        .and_then(|s| s.as_any_mut().downcast_mut::<Signal<T>>())
+    {
+        Some(signal) if signal.value == *value => {}
+        Some(signal) => {
+            signal.value = *value;
+            signal.bump();
+        }
+        None => {
+            // Missing, or present with another type: replace it.
+            let version = signals.get(&id).map_or(1, |s| s.version().wrapping_add(1));
+            signals.insert(
+                id,
+                M::box_signal(Signal {
+                    id,
+                    version,
+                    value: *value,
+                }),
+            );
+        }
+    }
+}
+
 /// Errors returned by typed signal accessors on [`Scene`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SignalAccessError {
@@ -236,14 +732,12 @@ pub enum SignalAccessError {
     TypeMismatch,
 }
 
-type ComputeFn<T> = dyn Fn(&EvalCtx<'_>, MarkId) -> T + 'static;
-
 /// A single encoding channel on a mark.
 /// v1 supports either a constant or a computed function.
 ///
 /// When using [`Encoding::Compute`], the `deps` list must include all inputs the closure reads.
 /// Dependency tracking is explicit; missing deps means missing updates.
-pub enum Encoding<T> {
+pub enum Encoding<T, M: Threading = Local> {
     /// A constant value.
     Const(T),
     /// A computed value, with explicit dependencies.
@@ -251,11 +745,11 @@ pub enum Encoding<T> {
         /// The inputs that may affect this encoding.
         deps: SmallVec<[InputRef; 4]>,
         /// Compute the value for a given mark.
-        f: Box<ComputeFn<T>>,
+        f: Box<M::ComputeFn<T>>,
     },
 }
 
-impl<T: fmt::Debug> fmt::Debug for Encoding<T> {
+impl<T: fmt::Debug, M: Threading> fmt::Debug for Encoding<T, M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Const(v) => f.debug_tuple("Const").field(v).finish(),
@@ -268,7 +762,7 @@ impl<T: fmt::Debug> fmt::Debug for Encoding<T> {
     }
 }
 
-impl<T: Clone> Encoding<T> {
+impl<T: Clone, M: Threading> Encoding<T, M> {
     fn deps(&self) -> SmallVec<[InputRef; 4]> {
         match self {
             Self::Const(_) => SmallVec::new(),
@@ -290,6 +784,11 @@ fn deps4(deps: impl IntoIterator<Item = InputRef>) -> SmallVec<[InputRef; 4]> {
 /// This is the “render-facing” data model: it is what downstream renderers consume, and it is what
 /// appears in [`MarkDiff`] diffs (boxed) and cached on [`Mark`].
 #[derive(Clone, Debug, PartialEq)]
+#[allow(
+    clippy::large_enum_variant,
+    reason = "payloads are boxed in diffs; boxing here would add an allocation per cached mark"
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MarkPayload {
     /// An axis-aligned rectangle.
     Rect(RectChannels),
@@ -297,6 +796,12 @@ pub enum MarkPayload {
     Text(TextChannels),
     /// A vector path.
     Path(PathChannels),
+    /// A group container.
+    Group(GroupChannels),
+    /// A raster image.
+    Image(ImageChannels),
+    /// Packed glyph instances.
+    Instances(InstancesChannels),
 }
 
 impl MarkPayload {
@@ -306,31 +811,196 @@ impl MarkPayload {
             Self::Rect(_) => MarkKind::Rect,
             Self::Text(_) => MarkKind::Text,
             Self::Path(_) => MarkKind::Path,
+            Self::Group(_) => MarkKind::Group,
+            Self::Image(_) => MarkKind::Image,
+            Self::Instances(_) => MarkKind::Instances,
+        }
+    }
+
+    /// Return the channel groups that differ between this payload and `new`.
+    ///
+    /// Payloads of different kinds differ in every payload channel (all but
+    /// [`ChannelMask::Z_ORDER`] and [`ChannelMask::PARENT`]).
+    pub fn changed_channels(&self, new: &Self) -> ChannelMask {
+        fn flag(changed: bool, mask: ChannelMask) -> ChannelMask {
+            if changed { mask } else { ChannelMask::NONE }
+        }
+        use ChannelMask as C;
+        match (self, new) {
+            (Self::Rect(a), Self::Rect(b)) => {
+                flag(
+                    a.rect != b.rect || a.corner_radii != b.corner_radii,
+                    C::GEOMETRY,
+                ) | flag(
+                    a.fill != b.fill || a.fill_opacity != b.fill_opacity,
+                    C::FILL,
+                ) | flag(
+                    a.stroke != b.stroke
+                        || a.stroke_width != b.stroke_width
+                        || a.stroke_opacity != b.stroke_opacity,
+                    C::STROKE,
+                ) | flag(
+                    a.opacity != b.opacity || a.blend_mode != b.blend_mode,
+                    C::COMPOSITING,
+                ) | flag(a.meta != b.meta, C::META)
+            }
+            (Self::Text(a), Self::Text(b)) => {
+                flag(a.pos != b.pos || a.angle != b.angle, C::GEOMETRY)
+                    | flag(
+                        a.text != b.text
+                            || a.font_size != b.font_size
+                            || a.font_family != b.font_family
+                            || a.font_weight != b.font_weight
+                            || a.font_style != b.font_style
+                            || a.line_height != b.line_height
+                            || a.anchor != b.anchor
+                            || a.baseline != b.baseline,
+                        C::TEXT,
+                    )
+                    | flag(
+                        a.fill != b.fill || a.fill_opacity != b.fill_opacity,
+                        C::FILL,
+                    )
+                    | flag(
+                        a.opacity != b.opacity || a.blend_mode != b.blend_mode,
+                        C::COMPOSITING,
+                    )
+                    | flag(a.meta != b.meta, C::META)
+            }
+            (Self::Path(a), Self::Path(b)) => {
+                flag(a.path != b.path, C::GEOMETRY)
+                    | flag(
+                        a.fill != b.fill || a.fill_opacity != b.fill_opacity,
+                        C::FILL,
+                    )
+                    | flag(
+                        a.stroke != b.stroke
+                            || a.stroke_width != b.stroke_width
+                            || a.stroke_style != b.stroke_style
+                            || a.stroke_opacity != b.stroke_opacity,
+                        C::STROKE,
+                    )
+                    | flag(
+                        a.opacity != b.opacity || a.blend_mode != b.blend_mode,
+                        C::COMPOSITING,
+                    )
+                    | flag(a.meta != b.meta, C::META)
+            }
+            (Self::Group(a), Self::Group(b)) => {
+                flag(a.transform != b.transform || a.clip != b.clip, C::GEOMETRY)
+                    | flag(a.meta != b.meta, C::META)
+            }
+            (Self::Instances(a), Self::Instances(b)) => {
+                flag(
+                    !Arc::ptr_eq(&a.instances, &b.instances) && a.instances != b.instances,
+                    C::INSTANCES,
+                ) | flag(a.shape != b.shape, C::GEOMETRY)
+                    | flag(a.opacity != b.opacity, C::COMPOSITING)
+                    | flag(a.meta != b.meta, C::META)
+            }
+            (Self::Image(a), Self::Image(b)) => {
+                flag(a.rect != b.rect, C::GEOMETRY)
+                    | flag(a.image != b.image || a.quality != b.quality, C::IMAGE)
+                    | flag(a.opacity != b.opacity, C::COMPOSITING)
+                    | flag(a.meta != b.meta, C::META)
+            }
+            _ => {
+                C::GEOMETRY
+                    | C::FILL
+                    | C::STROKE
+                    | C::TEXT
+                    | C::IMAGE
+                    | C::INSTANCES
+                    | C::COMPOSITING
+                    | C::META
+            }
         }
     }
 
+    /// Return the accessibility and interaction metadata of this payload.
+    ///
+    /// Payloads without metadata return [`MarkMeta::EMPTY`].
+    pub fn meta(&self) -> &MarkMeta {
+        let meta = match self {
+            Self::Rect(p) => &p.meta,
+            Self::Text(p) => &p.meta,
+            Self::Path(p) => &p.meta,
+            Self::Group(p) => &p.meta,
+            Self::Image(p) => &p.meta,
+            Self::Instances(p) => &p.meta,
+        };
+        meta.as_deref().unwrap_or(&EMPTY_META)
+    }
+
     /// Optional bounds hint for downstream damage calculation.
+    ///
+    /// Text bounds are unknown without a measurer; see [`MarkPayload::bounds_with`].
     pub fn bounds(&self) -> Option<Rect> {
+        self.bounds_with(None)
+    }
+
+    /// Like [`MarkPayload::bounds`], but measures text with `measurer` when provided.
+    pub fn bounds_with(&self, measurer: Option<&dyn TextMeasurer>) -> Option<Rect> {
         match self {
-            Self::Rect(r) => Some(r.rect),
-            // v1: text shaping/layout is downstream; bounds are not known here.
-            Self::Text(_) => None,
-            Self::Path(p) => Some(p.path.bounding_box()),
+            // Strokes are centered on the outline, so half the width lies outside the rect.
+            Self::Rect(r) => Some(if r.stroke_width > 0.0 {
+                r.rect.inflate(r.stroke_width * 0.5, r.stroke_width * 0.5)
+            } else {
+                r.rect
+            }),
+            // Text shaping/layout is downstream; bounds are only known with a measurer.
+            Self::Text(t) => measurer.map(|m| t.bounds(m)),
+            Self::Path(p) => Some(if p.stroke_width > 0.0 {
+                let half = p.stroke_width * 0.5;
+                p.path.bounding_box().inflate(half, half)
+            } else {
+                p.path.bounding_box()
+            }),
+            // Groups only have known bounds when clipped; children are otherwise unbounded.
+            Self::Group(g) => g
+                .clip
+                .as_ref()
+                .map(|clip| g.transform.transform_rect_bbox(clip.bounds())),
+            Self::Image(i) => Some(i.rect),
+            Self::Instances(i) => i.instances.bounds(),
         }
     }
 }
 
 /// Evaluated channels for [`MarkKind::Rect`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RectChannels {
     /// Rectangle geometry in scene coordinates.
     pub rect: Rect,
     /// Fill paint.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::brush"))]
     pub fill: Brush,
+    /// Outline paint.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::brush"))]
+    pub stroke: Brush,
+    /// Outline width in scene coordinates; `0` disables the outline.
+    pub stroke_width: f64,
+    /// Per-corner radii (top-left, top-right, bottom-right, bottom-left, in scene orientation).
+    ///
+    /// Renderers clamp radii to half the rect's width/height, as `kurbo::RoundedRect` does.
+    pub corner_radii: RoundedRectRadii,
+    /// Overall opacity in `0..=1`, applied to the whole mark (Vega-Lite `opacity`).
+    pub opacity: f64,
+    /// Fill opacity in `0..=1`, multiplied with the fill brush alpha (Vega-Lite `fillOpacity`).
+    pub fill_opacity: f64,
+    /// Stroke opacity in `0..=1`, multiplied with the stroke brush alpha (Vega-Lite
+    /// `strokeOpacity`).
+    pub stroke_opacity: f64,
+    /// Optional blend mode used when compositing the mark; `None` means normal source-over.
+    pub blend_mode: Option<BlendMode>,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
 }
 
 /// Evaluated channels for [`MarkKind::Text`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextChannels {
     /// Anchor position in scene coordinates.
     pub pos: Point,
@@ -338,6 +1008,17 @@ pub struct TextChannels {
     pub text: String,
     /// Font size in scene coordinates.
     pub font_size: f64,
+    /// Font family stack in CSS syntax (e.g. `"Inter, sans-serif"`); empty means the renderer
+    /// default.
+    pub font_family: String,
+    /// Font weight.
+    pub font_weight: FontWeight,
+    /// Font style (upright, italic, or oblique).
+    pub font_style: FontStyle,
+    /// Distance between baselines of consecutive lines, as a multiple of `font_size`.
+    ///
+    /// Only relevant for multi-line text (lines are separated by `\n`).
+    pub line_height: f64,
     /// Text rotation angle in degrees, with positive angles rotating clockwise.
     ///
     /// This is consumed by downstream renderers (for example, SVG `transform="rotate(...)"`).
@@ -348,7 +1029,16 @@ pub struct TextChannels {
     /// Vertical alignment for text relative to [`TextChannels::pos`].
     pub baseline: TextBaseline,
     /// Fill paint.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::brush"))]
     pub fill: Brush,
+    /// Overall opacity in `0..=1`, applied to the whole mark (Vega-Lite `opacity`).
+    pub opacity: f64,
+    /// Fill opacity in `0..=1`, multiplied with the fill brush alpha (Vega-Lite `fillOpacity`).
+    pub fill_opacity: f64,
+    /// Optional blend mode used when compositing the mark; `None` means normal source-over.
+    pub blend_mode: Option<BlendMode>,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
 }
 
 /// Horizontal anchoring for text.
@@ -360,6 +1050,7 @@ pub struct TextChannels {
 /// - y-axis tick labels use [`TextAnchor::End`] so the label’s right edge sits against the axis.
 /// - x-axis tick labels use [`TextAnchor::Middle`] to center labels under ticks.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextAnchor {
     /// Anchor at the start (left in LTR).
     Start,
@@ -375,6 +1066,7 @@ pub enum TextAnchor {
 ///
 /// In SVG terms, this maps to the `dominant-baseline` attribute.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextBaseline {
     /// Baseline is centered on the anchor point.
     Middle,
@@ -386,49 +1078,423 @@ pub enum TextBaseline {
     Ideographic,
 }
 
+impl TextChannels {
+    /// Estimate the scene-space bounds of this text using `measurer`.
+    ///
+    /// The measured box is placed according to [`TextChannels::anchor`] and
+    /// [`TextChannels::baseline`] (using typical ascent/descent proportions of the font size), then
+    /// rotated by [`TextChannels::angle`] around [`TextChannels::pos`]. Lines after the first flow
+    /// downwards.
+    pub fn bounds(&self, measurer: &dyn TextMeasurer) -> Rect {
+        let (w, h) = measurer.measure(&self.text, self.font_size, &self.font());
+        let Point { x, y } = self.pos;
+        let x0 = match self.anchor {
+            TextAnchor::Start => x,
+            TextAnchor::Middle => x - 0.5 * w,
+            TextAnchor::End => x - w,
+        };
+        // Distance from the anchor up to the top of the first line.
+        let above = self.font_size
+            * match self.baseline {
+                TextBaseline::Middle => 0.5,
+                TextBaseline::Alphabetic => 0.8,
+                TextBaseline::Hanging => 0.2,
+                TextBaseline::Ideographic => 1.0,
+            };
+        let local = Rect::new(x0, y - above, x0 + w, y - above + h);
+        if self.angle == 0.0 {
+            local
+        } else {
+            Affine::rotate_about(self.angle.to_radians(), self.pos).transform_rect_bbox(local)
+        }
+    }
+
+    /// Return the font properties of this text (everything but the size).
+    pub fn font(&self) -> TextFont {
+        TextFont {
+            family: self.font_family.clone(),
+            weight: self.font_weight,
+            style: self.font_style,
+            line_height: self.line_height,
+        }
+    }
+}
+
+/// A text measurement interface used to estimate text extents.
+///
+/// Text shaping stays downstream, so both the [`Scene`] (for text bounds and damage, see
+/// [`Scene::set_text_measurer`]) and chart-layer guide layout accept a measurer. Plug in a real
+/// shaping backend, or a heuristic for demos and tests.
+pub trait TextMeasurer {
+    /// Returns `(width, height)` in the same coordinate system as the marks.
+    ///
+    /// `font` carries the family, weight, style, and line height; `text` may contain `\n`
+    /// line breaks.
+    fn measure(&self, text: &str, font_size: f64, font: &TextFont) -> (f64, f64);
+}
+
+/// Font weight on the CSS/OpenType scale (`1..=1000`; 400 is normal, 700 is bold).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// Thin (100).
+    pub const THIN: Self = Self(100);
+    /// Light (300).
+    pub const LIGHT: Self = Self(300);
+    /// Normal (400).
+    pub const NORMAL: Self = Self(400);
+    /// Medium (500).
+    pub const MEDIUM: Self = Self(500);
+    /// Semi-bold (600).
+    pub const SEMI_BOLD: Self = Self(600);
+    /// Bold (700).
+    pub const BOLD: Self = Self(700);
+    /// Black (900).
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Font style (slant).
+///
+/// In SVG/CSS terms, this maps to the `font-style` attribute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStyle {
+    /// Upright glyphs.
+    #[default]
+    Normal,
+    /// Italic glyphs.
+    Italic,
+    /// Slanted upright glyphs.
+    Oblique,
+}
+
+/// Font properties of a text mark other than its size.
+///
+/// Chart-layer styles carry this as a unit, and text measurers receive it so layout can account for
+/// wider (bold) or taller (multi-line) text. Use [`MarkBuilder::font_const`] to apply it to a text
+/// mark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextFont {
+    /// Font family stack (see [`TextChannels::font_family`]).
+    pub family: String,
+    /// Font weight.
+    pub weight: FontWeight,
+    /// Font style.
+    pub style: FontStyle,
+    /// Line height as a multiple of the font size.
+    pub line_height: f64,
+}
+
+impl TextFont {
+    /// Sets the font family stack.
+    pub fn with_family(mut self, family: impl Into<String>) -> Self {
+        self.family = family.into();
+        self
+    }
+
+    /// Sets the font weight.
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the font style.
+    pub fn with_style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the line height (a multiple of the font size).
+    pub fn with_line_height(mut self, line_height: f64) -> Self {
+        self.line_height = line_height;
+        self
+    }
+}
+
+impl Default for TextFont {
+    fn default() -> Self {
+        Self {
+            family: String::new(),
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+            line_height: DEFAULT_LINE_HEIGHT,
+        }
+    }
+}
+
+/// Default [`TextChannels::line_height`], matching CSS `line-height: normal` for typical fonts.
+pub const DEFAULT_LINE_HEIGHT: f64 = 1.2;
+
 /// Evaluated channels for [`MarkKind::Path`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PathChannels {
     /// The vector path geometry.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::bez_path"))]
     pub path: BezPath,
     /// Fill paint.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::brush"))]
     pub fill: Brush,
     /// Stroke paint.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::brush"))]
     pub stroke: Brush,
     /// Stroke width in scene coordinates.
     pub stroke_width: f64,
+    /// Dash pattern, caps, joins, and miter limit for the stroke.
+    ///
+    /// Its `width` always equals [`PathChannels::stroke_width`], so it can be passed directly to
+    /// a stroker. Defaults to [`default_stroke_style`].
+    pub stroke_style: Stroke,
+    /// Overall opacity in `0..=1`, applied to the whole mark (Vega-Lite `opacity`).
+    pub opacity: f64,
+    /// Fill opacity in `0..=1`, multiplied with the fill brush alpha (Vega-Lite `fillOpacity`).
+    pub fill_opacity: f64,
+    /// Stroke opacity in `0..=1`, multiplied with the stroke brush alpha (Vega-Lite
+    /// `strokeOpacity`).
+    pub stroke_opacity: f64,
+    /// Optional blend mode used when compositing the mark; `None` means normal source-over.
+    pub blend_mode: Option<BlendMode>,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
 }
 
-impl Default for RectChannels {
-    fn default() -> Self {
-        Self {
-            rect: Rect::new(0.0, 0.0, 0.0, 0.0),
-            fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
-        }
-    }
+/// The default [`PathChannels::stroke_style`]: butt caps, miter joins (limit 4), and no dashes.
+///
+/// These match the SVG defaults, so renderers only need to emit non-default properties.
+pub fn default_stroke_style() -> Stroke {
+    Stroke::new(0.0)
+        .with_caps(Cap::Butt)
+        .with_join(Join::Miter)
+        .with_miter_limit(4.0)
 }
 
-impl Default for TextChannels {
-    fn default() -> Self {
-        Self {
-            pos: Point::new(0.0, 0.0),
-            text: String::new(),
-            font_size: 12.0,
-            angle: 0.0,
-            anchor: TextAnchor::Start,
-            baseline: TextBaseline::Middle,
-            fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
+/// Evaluated channels for [`MarkKind::Group`].
+///
+/// Children (marks whose [`Mark::parent`] is this group) are evaluated in the group's local
+/// coordinate space; renderers apply `transform` and then `clip` to all of them. Moving or clipping
+/// a whole group therefore produces a single `Update` diff for the group mark.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupChannels {
+    /// Transform from the group's local coordinates to its parent's coordinates.
+    pub transform: Affine,
+    /// Optional clip, in the group's local coordinates.
+    pub clip: Option<GroupClip>,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
+}
+
+/// A clip region for a [`MarkKind::Group`] mark.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroupClip {
+    /// Clip to an axis-aligned rectangle (e.g. a chart's data rectangle).
+    Rect(Rect),
+    /// Clip to an arbitrary path.
+    Path(#[cfg_attr(feature = "serde", serde(with = "serde_support::bez_path"))] BezPath),
+}
+
+impl GroupClip {
+    /// Return the bounding box of the clip region, in local coordinates.
+    pub fn bounds(&self) -> Rect {
+        match self {
+            Self::Rect(r) => *r,
+            Self::Path(p) => p.bounding_box(),
         }
     }
 }
 
-impl Default for PathChannels {
+/// Evaluated channels for [`MarkKind::Image`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageChannels {
+    /// Destination rectangle in scene coordinates; the image is stretched to fill it.
+    pub rect: Rect,
+    /// The image to draw.
+    pub image: ImageSource,
+    /// Sampling quality used when the image is scaled.
+    pub quality: ImageQuality,
+    /// Overall opacity in `0..=1`, applied to the whole mark (Vega-Lite `opacity`).
+    pub opacity: f64,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
+}
+
+/// Evaluated channels for [`MarkKind::Instances`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstancesChannels {
+    /// Per-instance keys, positions, sizes, and colors.
+    ///
+    /// Shared, so caching the payload and emitting it in diffs does not copy the arrays.
+    pub instances: Arc<InstanceData>,
+    /// Glyph drawn for every instance.
+    pub shape: InstanceShape,
+    /// Overall opacity in `0..=1`, applied to the whole mark (Vega-Lite `opacity`).
+    pub opacity: f64,
+    /// Accessibility and interaction metadata, or `None` if unset.
+    pub meta: Option<Box<MarkMeta>>,
+}
+
+/// The pixels drawn by a [`MarkKind::Image`] mark.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageSource {
+    /// An image resolved by the renderer (e.g. a URL, an asset key, or a texture name).
+    ///
+    /// An empty handle draws nothing.
+    Handle(String),
+    /// Decoded pixel data.
+    ///
+    /// The underlying [`peniko::Blob`] is reference counted, so cloning this is cheap.
+    Data(ImageData),
+}
+
+impl Default for ImageSource {
+    fn default() -> Self {
+        Self::Handle(String::new())
+    }
+}
+
+impl From<ImageData> for ImageSource {
+    fn from(data: ImageData) -> Self {
+        Self::Data(data)
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(handle: String) -> Self {
+        Self::Handle(handle)
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(handle: &str) -> Self {
+        Self::Handle(handle.into())
+    }
+}
+
+/// Evaluated accessibility and interaction metadata, shared by every mark kind.
+///
+/// These mirror Vega-Lite's `aria`/`description`, `tooltip`, and `href` channels. Renderers and
+/// hosts decide how to surface them (for example, SVG `role`/`aria-label`, `<title>`, and `<a>`).
+/// Empty strings mean "unset". Payloads only allocate metadata for marks that set some of it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkMeta {
+    /// ARIA role (e.g. `"graphics-symbol"`).
+    pub aria_role: String,
+    /// Accessible description, read by assistive technology.
+    pub description: String,
+    /// Tooltip content shown on hover.
+    pub tooltip: String,
+    /// Link target opened when the mark is activated.
+    pub href: String,
+}
+
+/// Borrowed by [`MarkPayload::meta`] for payloads without metadata.
+static EMPTY_META: MarkMeta = MarkMeta::EMPTY;
+
+impl MarkMeta {
+    /// Metadata with every field unset.
+    pub const EMPTY: Self = Self {
+        aria_role: String::new(),
+        description: String::new(),
+        tooltip: String::new(),
+        href: String::new(),
+    };
+
+    /// Returns `true` if no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self.aria_role.is_empty()
+            && self.description.is_empty()
+            && self.tooltip.is_empty()
+            && self.href.is_empty()
+    }
+}
+
+impl Default for RectChannels {
+    fn default() -> Self {
+        Self {
+            rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
+            stroke: Brush::default(),
+            stroke_width: 0.0,
+            corner_radii: RoundedRectRadii::from_single_radius(0.0),
+            opacity: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            blend_mode: None,
+            meta: None,
+        }
+    }
+}
+
+impl Default for TextChannels {
+    fn default() -> Self {
+        Self {
+            pos: Point::new(0.0, 0.0),
+            text: String::new(),
+            font_size: 12.0,
+            font_family: String::new(),
+            font_weight: FontWeight::NORMAL,
+            font_style: FontStyle::Normal,
+            line_height: DEFAULT_LINE_HEIGHT,
+            angle: 0.0,
+            anchor: TextAnchor::Start,
+            baseline: TextBaseline::Middle,
+            fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
+            opacity: 1.0,
+            fill_opacity: 1.0,
+            blend_mode: None,
+            meta: None,
+        }
+    }
+}
+
+impl Default for ImageChannels {
+    fn default() -> Self {
+        Self {
+            rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            image: ImageSource::default(),
+            quality: ImageQuality::Medium,
+            opacity: 1.0,
+            meta: None,
+        }
+    }
+}
+
+impl Default for GroupChannels {
+    fn default() -> Self {
+        Self {
+            transform: Affine::IDENTITY,
+            clip: None,
+            meta: None,
+        }
+    }
+}
+
+impl Default for PathChannels {
     fn default() -> Self {
         Self {
             path: BezPath::new(),
             fill: Brush::Solid(Color::from_rgba8(0, 0, 0, 255)),
             stroke: Brush::default(),
             stroke_width: 0.0,
+            stroke_style: default_stroke_style(),
+            opacity: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            blend_mode: None,
+            meta: None,
         }
     }
 }
@@ -438,26 +1504,60 @@ impl Default for PathChannels {
 /// These are the “bind-time” inputs: a higher layer (charts, DSL, Vega-like compiler) builds
 /// `MarkEncodings`, and the core evaluates them incrementally to produce [`MarkPayload`] diffs.
 #[derive(Debug)]
-pub enum MarkEncodings {
+pub enum MarkEncodings<M: Threading = Local> {
     /// Encodings for [`MarkKind::Rect`].
-    Rect(Box<RectEncodings>),
+    Rect(Box<RectEncodings<M>>),
     /// Encodings for [`MarkKind::Text`].
-    Text(Box<TextEncodings>),
+    Text(Box<TextEncodings<M>>),
     /// Encodings for [`MarkKind::Path`].
-    Path(Box<PathEncodings>),
+    Path(Box<PathEncodings<M>>),
+    /// Encodings for [`MarkKind::Group`].
+    Group(Box<GroupEncodings<M>>),
+    /// Encodings for [`MarkKind::Image`].
+    Image(Box<ImageEncodings<M>>),
+    /// Encodings for [`MarkKind::Instances`].
+    Instances(Box<InstancesEncodings<M>>),
 }
 
-impl MarkEncodings {
+impl<M: Threading> MarkEncodings<M> {
     fn kind(&self) -> MarkKind {
         match self {
             Self::Rect(_) => MarkKind::Rect,
             Self::Text(_) => MarkKind::Text,
             Self::Path(_) => MarkKind::Path,
+            Self::Group(_) => MarkKind::Group,
+            Self::Image(_) => MarkKind::Image,
+            Self::Instances(_) => MarkKind::Instances,
+        }
+    }
+
+    fn meta(&self) -> &MetaEncodings<M> {
+        match self {
+            Self::Rect(e) => &e.meta,
+            Self::Text(e) => &e.meta,
+            Self::Path(e) => &e.meta,
+            Self::Group(e) => &e.meta,
+            Self::Image(e) => &e.meta,
+            Self::Instances(e) => &e.meta,
+        }
+    }
+
+    fn meta_mut(&mut self) -> &mut MetaEncodings<M> {
+        match self {
+            Self::Rect(e) => &mut e.meta,
+            Self::Text(e) => &mut e.meta,
+            Self::Path(e) => &mut e.meta,
+            Self::Group(e) => &mut e.meta,
+            Self::Image(e) => &mut e.meta,
+            Self::Instances(e) => &mut e.meta,
         }
     }
 
     fn deps(&self) -> SmallVec<[InputRef; 8]> {
         let mut out = SmallVec::new();
+        for enc in self.meta().encodings() {
+            out.extend(enc.deps());
+        }
         match self {
             Self::Rect(e) => {
                 let e = e.as_ref();
@@ -466,6 +1566,13 @@ impl MarkEncodings {
                 out.extend(e.w.deps());
                 out.extend(e.h.deps());
                 out.extend(e.fill.deps());
+                out.extend(e.stroke.deps());
+                out.extend(e.stroke_width.deps());
+                out.extend(e.corner_radii.deps());
+                out.extend(e.opacity.deps());
+                out.extend(e.fill_opacity.deps());
+                out.extend(e.stroke_opacity.deps());
+                out.extend(e.blend_mode.deps());
             }
             Self::Text(e) => {
                 let e = e.as_ref();
@@ -473,10 +1580,17 @@ impl MarkEncodings {
                 out.extend(e.y.deps());
                 out.extend(e.text.deps());
                 out.extend(e.font_size.deps());
+                out.extend(e.font_family.deps());
+                out.extend(e.font_weight.deps());
+                out.extend(e.font_style.deps());
+                out.extend(e.line_height.deps());
                 out.extend(e.angle.deps());
                 out.extend(e.anchor.deps());
                 out.extend(e.baseline.deps());
                 out.extend(e.fill.deps());
+                out.extend(e.opacity.deps());
+                out.extend(e.fill_opacity.deps());
+                out.extend(e.blend_mode.deps());
             }
             Self::Path(e) => {
                 let e = e.as_ref();
@@ -484,64 +1598,337 @@ impl MarkEncodings {
                 out.extend(e.fill.deps());
                 out.extend(e.stroke.deps());
                 out.extend(e.stroke_width.deps());
+                out.extend(e.stroke_style.deps());
+                out.extend(e.opacity.deps());
+                out.extend(e.fill_opacity.deps());
+                out.extend(e.stroke_opacity.deps());
+                out.extend(e.blend_mode.deps());
+            }
+            Self::Group(e) => {
+                let e = e.as_ref();
+                out.extend(e.transform.deps());
+                out.extend(e.clip.deps());
+            }
+            Self::Image(e) => {
+                let e = e.as_ref();
+                out.extend(e.x.deps());
+                out.extend(e.y.deps());
+                out.extend(e.w.deps());
+                out.extend(e.h.deps());
+                out.extend(e.image.deps());
+                out.extend(e.quality.deps());
+                out.extend(e.opacity.deps());
+            }
+            Self::Instances(e) => {
+                let e = e.as_ref();
+                out.extend(e.instances.deps());
+                out.extend(e.shape.deps());
+                out.extend(e.opacity.deps());
             }
         }
         out.sort();
         out.dedup();
         out
     }
+
+    /// Count computed encodings that would be evaluated, either all of them (`None`) or those
+    /// depending on `changed_inputs`.
+    fn computed_count(&self, changed_inputs: Option<&SmallVec<[InputRef; 8]>>) -> usize {
+        fn hit<T, M: Threading>(
+            enc: &Encoding<T, M>,
+            changed: Option<&SmallVec<[InputRef; 8]>>,
+        ) -> usize {
+            match changed {
+                None => usize::from(matches!(enc, Encoding::Compute { .. })),
+                Some(changed) => usize::from(encoding_needs_update(enc, changed)),
+            }
+        }
+        let c = changed_inputs;
+        let meta: usize = self.meta().encodings().into_iter().map(|e| hit(e, c)).sum();
+        meta + match self {
+            Self::Rect(e) => {
+                let e = e.as_ref();
+                hit(&e.x, c)
+                    + hit(&e.y, c)
+                    + hit(&e.w, c)
+                    + hit(&e.h, c)
+                    + hit(&e.fill, c)
+                    + hit(&e.stroke, c)
+                    + hit(&e.stroke_width, c)
+                    + hit(&e.corner_radii, c)
+                    + hit(&e.opacity, c)
+                    + hit(&e.fill_opacity, c)
+                    + hit(&e.stroke_opacity, c)
+                    + hit(&e.blend_mode, c)
+            }
+            Self::Text(e) => {
+                let e = e.as_ref();
+                hit(&e.x, c)
+                    + hit(&e.y, c)
+                    + hit(&e.text, c)
+                    + hit(&e.font_size, c)
+                    + hit(&e.font_family, c)
+                    + hit(&e.font_weight, c)
+                    + hit(&e.font_style, c)
+                    + hit(&e.line_height, c)
+                    + hit(&e.angle, c)
+                    + hit(&e.anchor, c)
+                    + hit(&e.baseline, c)
+                    + hit(&e.fill, c)
+                    + hit(&e.opacity, c)
+                    + hit(&e.fill_opacity, c)
+                    + hit(&e.blend_mode, c)
+            }
+            Self::Path(e) => {
+                let e = e.as_ref();
+                hit(&e.path, c)
+                    + hit(&e.fill, c)
+                    + hit(&e.stroke, c)
+                    + hit(&e.stroke_width, c)
+                    + hit(&e.stroke_style, c)
+                    + hit(&e.opacity, c)
+                    + hit(&e.fill_opacity, c)
+                    + hit(&e.stroke_opacity, c)
+                    + hit(&e.blend_mode, c)
+            }
+            Self::Group(e) => {
+                let e = e.as_ref();
+                hit(&e.transform, c) + hit(&e.clip, c)
+            }
+            Self::Image(e) => {
+                let e = e.as_ref();
+                hit(&e.x, c)
+                    + hit(&e.y, c)
+                    + hit(&e.w, c)
+                    + hit(&e.h, c)
+                    + hit(&e.image, c)
+                    + hit(&e.quality, c)
+                    + hit(&e.opacity, c)
+            }
+            Self::Instances(e) => {
+                let e = e.as_ref();
+                hit(&e.instances, c) + hit(&e.shape, c) + hit(&e.opacity, c)
+            }
+        }
+    }
 }
 
 /// Encodings for [`MarkKind::Rect`].
 #[derive(Debug)]
-pub struct RectEncodings {
+pub struct RectEncodings<M: Threading = Local> {
     /// X position.
-    pub x: Encoding<f64>,
+    pub x: Encoding<f64, M>,
     /// Y position.
-    pub y: Encoding<f64>,
+    pub y: Encoding<f64, M>,
     /// Width.
-    pub w: Encoding<f64>,
+    pub w: Encoding<f64, M>,
     /// Height.
-    pub h: Encoding<f64>,
+    pub h: Encoding<f64, M>,
     /// Fill paint.
-    pub fill: Encoding<Brush>,
+    pub fill: Encoding<Brush, M>,
+    /// Outline paint.
+    pub stroke: Encoding<Brush, M>,
+    /// Outline width.
+    pub stroke_width: Encoding<f64, M>,
+    /// Per-corner radii (see [`RectChannels::corner_radii`]).
+    pub corner_radii: Encoding<RoundedRectRadii, M>,
+    /// Overall opacity (see [`RectChannels::opacity`]).
+    pub opacity: Encoding<f64, M>,
+    /// Fill opacity.
+    pub fill_opacity: Encoding<f64, M>,
+    /// Stroke opacity.
+    pub stroke_opacity: Encoding<f64, M>,
+    /// Optional blend mode.
+    pub blend_mode: Encoding<Option<BlendMode>, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
 }
 
 /// Encodings for [`MarkKind::Text`].
 #[derive(Debug)]
-pub struct TextEncodings {
+pub struct TextEncodings<M: Threading = Local> {
     /// X position.
-    pub x: Encoding<f64>,
+    pub x: Encoding<f64, M>,
     /// Y position.
-    pub y: Encoding<f64>,
+    pub y: Encoding<f64, M>,
     /// Text content.
-    pub text: Encoding<String>,
+    pub text: Encoding<String, M>,
     /// Font size.
-    pub font_size: Encoding<f64>,
+    pub font_size: Encoding<f64, M>,
+    /// Font family stack (see [`TextChannels::font_family`]).
+    pub font_family: Encoding<String, M>,
+    /// Font weight.
+    pub font_weight: Encoding<FontWeight, M>,
+    /// Font style.
+    pub font_style: Encoding<FontStyle, M>,
+    /// Line height as a multiple of the font size.
+    pub line_height: Encoding<f64, M>,
     /// Text rotation angle in degrees (see [`TextChannels::angle`]).
-    pub angle: Encoding<f64>,
+    pub angle: Encoding<f64, M>,
     /// Horizontal text anchoring (see [`TextAnchor`]).
-    pub anchor: Encoding<TextAnchor>,
+    pub anchor: Encoding<TextAnchor, M>,
     /// Vertical alignment for text (see [`TextBaseline`]).
-    pub baseline: Encoding<TextBaseline>,
+    pub baseline: Encoding<TextBaseline, M>,
     /// Fill paint.
-    pub fill: Encoding<Brush>,
+    pub fill: Encoding<Brush, M>,
+    /// Overall opacity (see [`RectChannels::opacity`]).
+    pub opacity: Encoding<f64, M>,
+    /// Fill opacity.
+    pub fill_opacity: Encoding<f64, M>,
+    /// Optional blend mode.
+    pub blend_mode: Encoding<Option<BlendMode>, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
 }
 
 /// Encodings for [`MarkKind::Path`].
 #[derive(Debug)]
-pub struct PathEncodings {
+pub struct PathEncodings<M: Threading = Local> {
     /// Path geometry.
-    pub path: Encoding<BezPath>,
+    pub path: Encoding<BezPath, M>,
     /// Fill paint.
-    pub fill: Encoding<Brush>,
+    pub fill: Encoding<Brush, M>,
     /// Stroke paint.
-    pub stroke: Encoding<Brush>,
+    pub stroke: Encoding<Brush, M>,
     /// Stroke width.
-    pub stroke_width: Encoding<f64>,
+    pub stroke_width: Encoding<f64, M>,
+    /// Stroke dashes, caps, joins, and miter limit (the width is taken from `stroke_width`).
+    pub stroke_style: Encoding<Stroke, M>,
+    /// Overall opacity (see [`RectChannels::opacity`]).
+    pub opacity: Encoding<f64, M>,
+    /// Fill opacity.
+    pub fill_opacity: Encoding<f64, M>,
+    /// Stroke opacity.
+    pub stroke_opacity: Encoding<f64, M>,
+    /// Optional blend mode.
+    pub blend_mode: Encoding<Option<BlendMode>, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
+}
+
+/// Encodings for [`MarkKind::Group`].
+#[derive(Debug)]
+pub struct GroupEncodings<M: Threading = Local> {
+    /// Local-to-parent transform.
+    pub transform: Encoding<Affine, M>,
+    /// Optional clip region, in local coordinates.
+    pub clip: Encoding<Option<GroupClip>, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
+}
+
+/// Encodings for [`MarkKind::Image`].
+#[derive(Debug)]
+pub struct ImageEncodings<M: Threading = Local> {
+    /// X position.
+    pub x: Encoding<f64, M>,
+    /// Y position.
+    pub y: Encoding<f64, M>,
+    /// Width.
+    pub w: Encoding<f64, M>,
+    /// Height.
+    pub h: Encoding<f64, M>,
+    /// Image source.
+    pub image: Encoding<ImageSource, M>,
+    /// Sampling quality.
+    pub quality: Encoding<ImageQuality, M>,
+    /// Overall opacity (see [`RectChannels::opacity`]).
+    pub opacity: Encoding<f64, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
+}
+
+/// Encodings for [`MarkKind::Instances`].
+#[derive(Debug)]
+pub struct InstancesEncodings<M: Threading = Local> {
+    /// Per-instance data.
+    pub instances: Encoding<Arc<InstanceData>, M>,
+    /// Glyph shape.
+    pub shape: Encoding<InstanceShape, M>,
+    /// Overall opacity (see [`RectChannels::opacity`]).
+    pub opacity: Encoding<f64, M>,
+    /// Accessibility and interaction metadata.
+    pub meta: MetaEncodings<M>,
+}
+
+/// Encodings for [`MarkMeta`], shared by every mark kind.
+#[derive(Debug)]
+pub struct MetaEncodings<M: Threading = Local> {
+    /// ARIA role.
+    pub aria_role: Encoding<String, M>,
+    /// Accessible description.
+    pub description: Encoding<String, M>,
+    /// Tooltip content.
+    pub tooltip: Encoding<String, M>,
+    /// Link target.
+    pub href: Encoding<String, M>,
 }
 
-impl Default for RectEncodings {
+impl<M: Threading> MetaEncodings<M> {
+    fn encodings(&self) -> [&Encoding<String, M>; 4] {
+        [
+            &self.aria_role,
+            &self.description,
+            &self.tooltip,
+            &self.href,
+        ]
+    }
+
+    fn eval(&self, ctx: &EvalCtx<'_, M>, id: MarkId) -> Option<Box<MarkMeta>> {
+        let meta = MarkMeta {
+            aria_role: eval_value(&self.aria_role, ctx, id),
+            description: eval_value(&self.description, ctx, id),
+            tooltip: eval_value(&self.tooltip, ctx, id),
+            href: eval_value(&self.href, ctx, id),
+        };
+        (!meta.is_empty()).then(|| Box::new(meta))
+    }
+
+    fn update(
+        &self,
+        ctx: &EvalCtx<'_, M>,
+        id: MarkId,
+        changed_inputs: &SmallVec<[InputRef; 8]>,
+        meta: &mut Option<Box<MarkMeta>>,
+    ) {
+        let needs_update = self
+            .encodings()
+            .into_iter()
+            .any(|e| encoding_needs_update(e, changed_inputs));
+        if !needs_update {
+            return;
+        }
+        let m = meta.get_or_insert_default();
+        if encoding_needs_update(&self.aria_role, changed_inputs) {
+            m.aria_role = eval_value(&self.aria_role, ctx, id);
+        }
+        if encoding_needs_update(&self.description, changed_inputs) {
+            m.description = eval_value(&self.description, ctx, id);
+        }
+        if encoding_needs_update(&self.tooltip, changed_inputs) {
+            m.tooltip = eval_value(&self.tooltip, ctx, id);
+        }
+        if encoding_needs_update(&self.href, changed_inputs) {
+            m.href = eval_value(&self.href, ctx, id);
+        }
+        if m.is_empty() {
+            *meta = None;
+        }
+    }
+}
+
+impl<M: Threading> Default for MetaEncodings<M> {
+    fn default() -> Self {
+        Self {
+            aria_role: Encoding::Const(String::new()),
+            description: Encoding::Const(String::new()),
+            tooltip: Encoding::Const(String::new()),
+            href: Encoding::Const(String::new()),
+        }
+    }
+}
+
+impl<M: Threading> Default for RectEncodings<M> {
     fn default() -> Self {
         Self {
             x: Encoding::Const(0.0),
@@ -549,32 +1936,90 @@ impl Default for RectEncodings {
             w: Encoding::Const(0.0),
             h: Encoding::Const(0.0),
             fill: Encoding::Const(Brush::Solid(Color::from_rgba8(0, 0, 0, 255))),
+            stroke: Encoding::Const(Brush::default()),
+            stroke_width: Encoding::Const(0.0),
+            corner_radii: Encoding::Const(RoundedRectRadii::from_single_radius(0.0)),
+            opacity: Encoding::Const(1.0),
+            fill_opacity: Encoding::Const(1.0),
+            stroke_opacity: Encoding::Const(1.0),
+            blend_mode: Encoding::Const(None),
+            meta: MetaEncodings::default(),
         }
     }
 }
 
-impl Default for TextEncodings {
+impl<M: Threading> Default for TextEncodings<M> {
     fn default() -> Self {
         Self {
             x: Encoding::Const(0.0),
             y: Encoding::Const(0.0),
             text: Encoding::Const(String::new()),
             font_size: Encoding::Const(12.0),
+            font_family: Encoding::Const(String::new()),
+            font_weight: Encoding::Const(FontWeight::NORMAL),
+            font_style: Encoding::Const(FontStyle::Normal),
+            line_height: Encoding::Const(DEFAULT_LINE_HEIGHT),
             angle: Encoding::Const(0.0),
             anchor: Encoding::Const(TextAnchor::Start),
             baseline: Encoding::Const(TextBaseline::Middle),
             fill: Encoding::Const(Brush::Solid(Color::from_rgba8(0, 0, 0, 255))),
+            opacity: Encoding::Const(1.0),
+            fill_opacity: Encoding::Const(1.0),
+            blend_mode: Encoding::Const(None),
+            meta: MetaEncodings::default(),
         }
     }
 }
 
-impl Default for PathEncodings {
+impl<M: Threading> Default for PathEncodings<M> {
     fn default() -> Self {
         Self {
             path: Encoding::Const(BezPath::new()),
             fill: Encoding::Const(Brush::Solid(Color::from_rgba8(0, 0, 0, 255))),
             stroke: Encoding::Const(Brush::default()),
             stroke_width: Encoding::Const(0.0),
+            stroke_style: Encoding::Const(default_stroke_style()),
+            opacity: Encoding::Const(1.0),
+            fill_opacity: Encoding::Const(1.0),
+            stroke_opacity: Encoding::Const(1.0),
+            blend_mode: Encoding::Const(None),
+            meta: MetaEncodings::default(),
+        }
+    }
+}
+
+impl<M: Threading> Default for ImageEncodings<M> {
+    fn default() -> Self {
+        Self {
+            x: Encoding::Const(0.0),
+            y: Encoding::Const(0.0),
+            w: Encoding::Const(0.0),
+            h: Encoding::Const(0.0),
+            image: Encoding::Const(ImageSource::default()),
+            quality: Encoding::Const(ImageQuality::Medium),
+            opacity: Encoding::Const(1.0),
+            meta: MetaEncodings::default(),
+        }
+    }
+}
+
+impl<M: Threading> Default for InstancesEncodings<M> {
+    fn default() -> Self {
+        Self {
+            instances: Encoding::Const(Arc::default()),
+            shape: Encoding::Const(InstanceShape::default()),
+            opacity: Encoding::Const(1.0),
+            meta: MetaEncodings::default(),
+        }
+    }
+}
+
+impl<M: Threading> Default for GroupEncodings<M> {
+    fn default() -> Self {
+        Self {
+            transform: Encoding::Const(Affine::IDENTITY),
+            clip: Encoding::Const(None),
+            meta: MetaEncodings::default(),
         }
     }
 }
@@ -584,18 +2029,29 @@ impl Default for PathEncodings {
 /// After mutating any encoding, call [`Mark::rebuild_deps`] so incremental updates
 /// can cheaply detect dirtiness.
 #[derive(Debug)]
-pub struct Mark {
+pub struct Mark<M: Threading = Local> {
     /// Stable identifier.
     pub id: MarkId,
 
     /// Z-ordering for rendering; higher values are drawn above lower values.
+    ///
+    /// For marks with a [`Mark::parent`], this orders the mark among its siblings.
     pub z_index: i32,
 
+    /// The [`MarkKind::Group`] mark containing this mark, if any.
+    ///
+    /// A child's channels are in its group's local coordinates. The scene does not validate that
+    /// the parent exists or is a group, and removing a group does not remove its children.
+    pub parent: Option<MarkId>,
+
+    /// Scheduling priority for [`Scene::update_budgeted`]; higher values are evaluated first.
+    pub priority: i32,
+
     /// The geometric kind of this mark.
     pub kind: MarkKind,
 
     /// Encodings for this mark's kind.
-    pub encodings: MarkEncodings,
+    pub encodings: MarkEncodings<M>,
 
     /// Flattened dependency summary for quick dirtiness checks.
     pub deps: SmallVec<[InputRef; 8]>,
@@ -606,25 +2062,67 @@ pub struct Mark {
     /// Z-index used at the time of the last evaluation (for diffing/reordering).
     cached_z_index: i32,
 
-    /// Last versions observed for inputs (simple per-mark tracking).
-    pub last_seen: HashMap<InputRef, Version>,
+    /// Parent used at the time of the last evaluation.
+    cached_parent: Option<MarkId>,
+
+    /// Input versions observed by the last evaluation, parallel to [`Mark::deps`] (`0` = unseen).
+    ///
+    /// Most marks have a handful of deps, so this stays inline and is matched by position rather
+    /// than hashed.
+    last_seen: SmallVec<[Version; 4]>,
 
     force_eval: bool,
+
+    /// Inputs reported as changed for this mark only (e.g. by a row-level table patch).
+    pending_inputs: SmallVec<[InputRef; 4]>,
 }
 
 impl Mark {
     /// Create a mark with constant/default encodings.
+    ///
+    /// Use [`Mark::new_shared`] for a mark that can be inserted into a `Scene<Shared>`.
     pub fn new(id: MarkId) -> Self {
+        Self::empty(id)
+    }
+
+    /// Start building a mark with chainable encoding setters.
+    pub fn builder(id: MarkId) -> MarkBuilder {
+        MarkBuilder {
+            mark: Self::new(id),
+        }
+    }
+}
+
+impl Mark<Shared> {
+    /// Create a mark whose encoding closures must be `Send + Sync` (see [`Shared`]).
+    pub fn new_shared(id: MarkId) -> Self {
+        Self::empty(id)
+    }
+
+    /// Start building a mark whose encoding closures must be `Send + Sync`.
+    pub fn shared_builder(id: MarkId) -> MarkBuilder<Shared> {
+        MarkBuilder {
+            mark: Self::new_shared(id),
+        }
+    }
+}
+
+impl<M: Threading> Mark<M> {
+    fn empty(id: MarkId) -> Self {
         let mut m = Self {
             id,
             z_index: 0,
+            parent: None,
+            priority: 0,
             kind: MarkKind::Rect,
             encodings: MarkEncodings::Rect(Box::default()),
             deps: SmallVec::new(),
             cache: None,
             cached_z_index: 0,
-            last_seen: HashMap::new(),
+            cached_parent: None,
+            last_seen: SmallVec::new(),
             force_eval: false,
+            pending_inputs: SmallVec::new(),
         };
         m.rebuild_deps();
         m
@@ -633,30 +2131,73 @@ impl Mark {
     /// Rebuild [`Mark::deps`] from per-encoding deps.
     pub fn rebuild_deps(&mut self) {
         self.kind = self.encodings.kind();
-        self.deps = self.encodings.deps();
+        let deps = self.encodings.deps();
+        if deps != self.deps {
+            // Positions no longer line up; unseen versions re-evaluate the affected encodings.
+            self.last_seen.clear();
+        }
+        self.deps = deps;
     }
 
-    /// Start building a mark with chainable encoding setters.
-    pub fn builder(id: MarkId) -> MarkBuilder {
-        MarkBuilder {
-            mark: Self::new(id),
+    /// Returns `true` if this mark must be visited by the next update regardless of input versions.
+    fn needs_visit(&self) -> bool {
+        self.cache.is_none()
+            || self.force_eval
+            || !self.pending_inputs.is_empty()
+            || self.cached_z_index != self.z_index
+            || self.cached_parent != self.parent
+    }
+
+    /// Carry evaluation state over from the previous mark with the same id.
+    ///
+    /// If the kind changed, the previous mark exits instead and its `Exit` diff is returned.
+    fn adopt_state(&mut self, old: Self, measurer: Option<&dyn TextMeasurer>) -> Option<MarkDiff> {
+        if self.kind != old.kind {
+            let bounds = old.cache.as_ref().and_then(|p| p.bounds_with(measurer));
+            return Some(MarkDiff::Exit {
+                id: self.id,
+                z_index: old.z_index,
+                parent: old.parent,
+                kind: old.kind,
+                old: old.cache.map(Box::new),
+                bounds,
+            });
         }
+        if self.deps == old.deps {
+            self.last_seen = old.last_seen;
+        } else {
+            self.force_eval = true;
+        }
+        self.cache = old.cache;
+        self.cached_z_index = old.cached_z_index;
+        self.cached_parent = old.cached_parent;
+        self.pending_inputs = old.pending_inputs;
+        None
     }
 }
 
 /// A builder for [`Mark`] that rebuilds dependencies on `build()`.
 #[derive(Debug)]
-pub struct MarkBuilder {
-    mark: Mark,
+pub struct MarkBuilder<M: Threading = Local> {
+    mark: Mark<M>,
 }
 
-impl MarkBuilder {
+impl<M: Threading> MarkBuilder<M> {
     /// Set the mark z-index (rendering order).
     pub fn z_index(mut self, z_index: i32) -> Self {
         self.mark.z_index = z_index;
         self
     }
 
+    /// Set the scheduling priority used by [`Scene::update_budgeted`].
+    ///
+    /// Use higher values for UI-critical marks (e.g. interaction feedback) so they are evaluated
+    /// before background series when a frame budget is exhausted.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.mark.priority = priority;
+        self
+    }
+
     /// Set the mark kind.
     pub fn kind(mut self, kind: MarkKind) -> Self {
         self.mark.kind = kind;
@@ -664,6 +2205,9 @@ impl MarkBuilder {
             MarkKind::Rect => MarkEncodings::Rect(Box::default()),
             MarkKind::Text => MarkEncodings::Text(Box::default()),
             MarkKind::Path => MarkEncodings::Path(Box::default()),
+            MarkKind::Group => MarkEncodings::Group(Box::default()),
+            MarkKind::Image => MarkEncodings::Image(Box::default()),
+            MarkKind::Instances => MarkEncodings::Instances(Box::default()),
         };
         self
     }
@@ -683,36 +2227,64 @@ impl MarkBuilder {
         self.kind(MarkKind::Path)
     }
 
+    /// Convenience for `MarkKind::Group`.
+    pub fn group(self) -> Self {
+        self.kind(MarkKind::Group)
+    }
+
+    /// Convenience for `MarkKind::Image`.
+    pub fn image(self) -> Self {
+        self.kind(MarkKind::Image)
+    }
+
+    /// Convenience for `MarkKind::Instances`.
+    pub fn instances(self) -> Self {
+        self.kind(MarkKind::Instances)
+    }
+
+    /// Place this mark inside a [`MarkKind::Group`] mark (see [`Mark::parent`]).
+    pub fn parent(mut self, group: MarkId) -> Self {
+        self.mark.parent = Some(group);
+        self
+    }
+
     /// Set the `x` encoding to a constant value.
     pub fn x_const(mut self, v: f64) -> Self {
         match &mut self.mark.encodings {
             MarkEncodings::Rect(e) => e.as_mut().x = Encoding::Const(v),
             MarkEncodings::Text(e) => e.as_mut().x = Encoding::Const(v),
-            MarkEncodings::Path(_) => {}
+            MarkEncodings::Image(e) => e.as_mut().x = Encoding::Const(v),
+            MarkEncodings::Path(_) | MarkEncodings::Group(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `x` encoding to a computed value.
-    pub fn x_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
+    pub fn x_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
         match &mut self.mark.encodings {
             MarkEncodings::Rect(e) => {
                 e.as_mut().x = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
                 };
             }
             MarkEncodings::Text(e) => {
                 e.as_mut().x = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Image(e) => {
+                e.as_mut().x = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
                 };
             }
-            MarkEncodings::Path(_) => {}
+            MarkEncodings::Path(_) | MarkEncodings::Group(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
@@ -722,77 +2294,118 @@ impl MarkBuilder {
         match &mut self.mark.encodings {
             MarkEncodings::Rect(e) => e.as_mut().y = Encoding::Const(v),
             MarkEncodings::Text(e) => e.as_mut().y = Encoding::Const(v),
-            MarkEncodings::Path(_) => {}
+            MarkEncodings::Image(e) => e.as_mut().y = Encoding::Const(v),
+            MarkEncodings::Path(_) | MarkEncodings::Group(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `y` encoding to a computed value.
-    pub fn y_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
+    pub fn y_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
         match &mut self.mark.encodings {
             MarkEncodings::Rect(e) => {
                 e.as_mut().y = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
                 };
             }
             MarkEncodings::Text(e) => {
                 e.as_mut().y = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
                 };
             }
-            MarkEncodings::Path(_) => {}
+            MarkEncodings::Image(e) => {
+                e.as_mut().y = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(_) | MarkEncodings::Group(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `w` encoding to a constant value.
     pub fn w_const(mut self, v: f64) -> Self {
-        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
-            e.as_mut().w = Encoding::Const(v);
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().w = Encoding::Const(v),
+            MarkEncodings::Image(e) => e.as_mut().w = Encoding::Const(v),
+            MarkEncodings::Text(_)
+            | MarkEncodings::Path(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `w` encoding to a computed value (rect marks only).
-    pub fn w_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
-        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
-            e.as_mut().w = Encoding::Compute {
-                deps: deps4(deps),
-                f: Box::new(f),
-            };
-        }
-        self
-    }
-
+    pub fn w_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().w = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Image(e) => {
+                e.as_mut().w = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(_)
+            | MarkEncodings::Path(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
     /// Set the `h` encoding to a constant value.
     pub fn h_const(mut self, v: f64) -> Self {
-        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
-            e.as_mut().h = Encoding::Const(v);
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().h = Encoding::Const(v),
+            MarkEncodings::Image(e) => e.as_mut().h = Encoding::Const(v),
+            MarkEncodings::Text(_)
+            | MarkEncodings::Path(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `h` encoding to a computed value (rect marks only).
-    pub fn h_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
-        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
-            e.as_mut().h = Encoding::Compute {
-                deps: deps4(deps),
-                f: Box::new(f),
-            };
+    pub fn h_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().h = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Image(e) => {
+                e.as_mut().h = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(_)
+            | MarkEncodings::Path(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Instances(_) => {}
         }
         self
     }
@@ -804,6 +2417,7 @@ impl MarkBuilder {
             MarkEncodings::Rect(e) => e.as_mut().fill = Encoding::Const(brush),
             MarkEncodings::Text(e) => e.as_mut().fill = Encoding::Const(brush),
             MarkEncodings::Path(e) => e.as_mut().fill = Encoding::Const(brush),
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
@@ -815,35 +2429,218 @@ impl MarkBuilder {
             MarkEncodings::Rect(e) => e.as_mut().fill = Encoding::Const(v),
             MarkEncodings::Text(e) => e.as_mut().fill = Encoding::Const(v),
             MarkEncodings::Path(e) => e.as_mut().fill = Encoding::Const(v),
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
 
     /// Set the `fill` encoding to a computed value.
-    pub fn fill_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> Brush + 'static,
-    ) -> Self {
+    pub fn fill_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Brush + 'static,
+        M: ComputeFor<Brush, F>,
+    {
         match &mut self.mark.encodings {
             MarkEncodings::Rect(e) => {
                 e.as_mut().fill = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
                 };
             }
             MarkEncodings::Text(e) => {
                 e.as_mut().fill = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
                 };
             }
             MarkEncodings::Path(e) => {
                 e.as_mut().fill = Encoding::Compute {
                     deps: deps4(deps),
-                    f: Box::new(f),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `opacity` encoding to a constant value (all marks except groups).
+    pub fn opacity_const(mut self, v: f64) -> Self {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().opacity = Encoding::Const(v),
+            MarkEncodings::Text(e) => e.as_mut().opacity = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().opacity = Encoding::Const(v),
+            MarkEncodings::Image(e) => e.as_mut().opacity = Encoding::Const(v),
+            MarkEncodings::Instances(e) => e.as_mut().opacity = Encoding::Const(v),
+            MarkEncodings::Group(_) => {}
+        }
+        self
+    }
+
+    /// Set the `opacity` encoding to a computed value (all marks except groups).
+    pub fn opacity_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(e) => {
+                e.as_mut().opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Image(e) => {
+                e.as_mut().opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Instances(e) => {
+                e.as_mut().opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Group(_) => {}
+        }
+        self
+    }
+
+    /// Set the `fill_opacity` encoding to a constant value (rect, text, and path marks).
+    pub fn fill_opacity_const(mut self, v: f64) -> Self {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().fill_opacity = Encoding::Const(v),
+            MarkEncodings::Text(e) => e.as_mut().fill_opacity = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().fill_opacity = Encoding::Const(v),
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `fill_opacity` encoding to a computed value (rect, text, and path marks).
+    pub fn fill_opacity_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().fill_opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(e) => {
+                e.as_mut().fill_opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().fill_opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `stroke_opacity` encoding to a constant value (rect and path marks).
+    pub fn stroke_opacity_const(mut self, v: f64) -> Self {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().stroke_opacity = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().stroke_opacity = Encoding::Const(v),
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `stroke_opacity` encoding to a computed value (rect and path marks).
+    pub fn stroke_opacity_compute<F>(
+        mut self,
+        deps: impl IntoIterator<Item = InputRef>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().stroke_opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().stroke_opacity = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `blend_mode` encoding to a constant value (rect, text, and path marks).
+    pub fn blend_mode_const(mut self, v: Option<BlendMode>) -> Self {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().blend_mode = Encoding::Const(v),
+            MarkEncodings::Text(e) => e.as_mut().blend_mode = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().blend_mode = Encoding::Const(v),
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
+
+    /// Set the `blend_mode` encoding to a computed value (rect, text, and path marks).
+    pub fn blend_mode_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Option<BlendMode> + 'static,
+        M: ComputeFor<Option<BlendMode>, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().blend_mode = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(e) => {
+                e.as_mut().blend_mode = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().blend_mode = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
                 };
             }
+            MarkEncodings::Group(_) | MarkEncodings::Image(_) | MarkEncodings::Instances(_) => {}
         }
         self
     }
@@ -857,15 +2654,15 @@ impl MarkBuilder {
     }
 
     /// Set the `text` encoding to a computed value (text marks only).
-    pub fn text_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> String + 'static,
-    ) -> Self {
+    pub fn text_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
+    {
         if let MarkEncodings::Text(e) = &mut self.mark.encodings {
             e.as_mut().text = Encoding::Compute {
                 deps: deps4(deps),
-                f: Box::new(f),
+                f: M::box_compute(f),
             };
         }
         self
@@ -888,15 +2685,15 @@ impl MarkBuilder {
     }
 
     /// Set the `angle` encoding to a computed value in degrees (text marks only).
-    pub fn angle_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
+    pub fn angle_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
         if let MarkEncodings::Text(e) = &mut self.mark.encodings {
             e.as_mut().angle = Encoding::Compute {
                 deps: deps4(deps),
-                f: Box::new(f),
+                f: M::box_compute(f),
             };
         }
         self
@@ -943,923 +2740,4038 @@ impl MarkBuilder {
     }
 
     /// Set the `font_size` encoding to a computed value (text marks only).
-    pub fn font_size_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> f64 + 'static,
-    ) -> Self {
+    pub fn font_size_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
         if let MarkEncodings::Text(e) = &mut self.mark.encodings {
             e.as_mut().font_size = Encoding::Compute {
                 deps: deps4(deps),
-                f: Box::new(f),
+                f: M::box_compute(f),
             };
         }
         self
     }
 
-    /// Set the `path` encoding to a constant value (path marks only).
-    pub fn path_const(mut self, v: BezPath) -> Self {
-        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
-            e.as_mut().path = Encoding::Const(v);
+    /// Set the `font_family` encoding to a constant value (text marks only).
+    ///
+    /// The value is a font family stack in CSS syntax.
+    pub fn font_family_const(mut self, v: impl Into<String>) -> Self {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_family = Encoding::Const(v.into());
         }
         self
     }
 
-    /// Set the `path` encoding to a computed value (path marks only).
-    pub fn path_compute(
-        mut self,
-        deps: impl IntoIterator<Item = InputRef>,
-        f: impl Fn(&EvalCtx<'_>, MarkId) -> BezPath + 'static,
-    ) -> Self {
-        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
-            e.as_mut().path = Encoding::Compute {
+    /// Set the `font_family` encoding to a computed value (text marks only).
+    ///
+    /// The value is a font family stack in CSS syntax.
+    pub fn font_family_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
+    {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_family = Encoding::Compute {
                 deps: deps4(deps),
-                f: Box::new(f),
+                f: M::box_compute(f),
             };
         }
         self
     }
 
-    /// Set the `stroke` encoding to a constant value (path marks only).
-    pub fn stroke_const(mut self, v: Color) -> Self {
-        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
-            e.as_mut().stroke = Encoding::Const(Brush::Solid(v));
+    /// Set the `font_weight` encoding to a constant value (text marks only).
+    pub fn font_weight_const(mut self, v: FontWeight) -> Self {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_weight = Encoding::Const(v);
         }
         self
     }
 
-    /// Set the `stroke` encoding to a constant brush (path marks only).
-    pub fn stroke_brush_const(mut self, v: impl Into<Brush>) -> Self {
-        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
-            e.as_mut().stroke = Encoding::Const(v.into());
+    /// Set the `font_weight` encoding to a computed value (text marks only).
+    pub fn font_weight_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> FontWeight + 'static,
+        M: ComputeFor<FontWeight, F>,
+    {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_weight = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
         }
         self
     }
 
-    /// Set the `stroke_width` encoding to a constant value (path marks only).
-    pub fn stroke_width_const(mut self, v: f64) -> Self {
-        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
-            e.as_mut().stroke_width = Encoding::Const(v);
+    /// Set the `font_style` encoding to a constant value (text marks only).
+    pub fn font_style_const(mut self, v: FontStyle) -> Self {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_style = Encoding::Const(v);
         }
         self
     }
 
-    /// Finish building and rebuild dependencies.
-    pub fn build(mut self) -> Mark {
-        self.mark.rebuild_deps();
-        self.mark
+    /// Set the `font_style` encoding to a computed value (text marks only).
+    pub fn font_style_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> FontStyle + 'static,
+        M: ComputeFor<FontStyle, F>,
+    {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().font_style = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
-}
 
-/// Read-only access to scene inputs during encoding evaluation.
-pub struct EvalCtx<'a> {
-    /// Tables available during evaluation.
-    pub tables: &'a HashMap<TableId, Table>,
-    /// Signals available during evaluation.
-    pub signals: &'a HashMap<SignalId, Box<dyn AnySignal>>,
-}
+    /// Set the `line_height` encoding to a constant value (text marks only).
+    ///
+    /// The value is a multiple of the font size.
+    pub fn line_height_const(mut self, v: f64) -> Self {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().line_height = Encoding::Const(v);
+        }
+        self
+    }
 
-impl fmt::Debug for EvalCtx<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("EvalCtx")
-            .field("tables_len", &self.tables.len())
-            .field("signals_len", &self.signals.len())
-            .finish()
+    /// Set the `line_height` encoding to a computed value (text marks only).
+    ///
+    /// The value is a multiple of the font size.
+    pub fn line_height_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        if let MarkEncodings::Text(e) = &mut self.mark.encodings {
+            e.as_mut().line_height = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
-}
 
-impl<'a> EvalCtx<'a> {
-    /// Return the current version of a table, if present.
-    pub fn table_version(&self, id: TableId) -> Option<Version> {
-        self.tables.get(&id).map(|t| t.version)
+    /// Set the font family, weight, style, and line height to constant values (text marks only).
+    pub fn font_const(self, font: &TextFont) -> Self {
+        self.font_family_const(font.family.clone())
+            .font_weight_const(font.weight)
+            .font_style_const(font.style)
+            .line_height_const(font.line_height)
     }
-    /// Return the current version of a signal, if present.
-    pub fn signal_version(&self, id: SignalId) -> Option<Version> {
-        self.signals.get(&id).map(|s| s.version())
+
+    /// Set the `path` encoding to a constant value (path marks only).
+    pub fn path_const(mut self, v: BezPath) -> Self {
+        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
+            e.as_mut().path = Encoding::Const(v);
+        }
+        self
     }
 
-    /// Downcast and clone a signal value, if present and of type `T`.
-    pub fn signal<T: Clone + 'static>(&self, id: SignalId) -> Option<T> {
-        let s = self.signals.get(&id)?;
-        let typed = s.as_any().downcast_ref::<Signal<T>>()?;
-        Some(typed.value.clone())
+    /// Set the `path` encoding to a computed value (path marks only).
+    pub fn path_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> BezPath + 'static,
+        M: ComputeFor<BezPath, F>,
+    {
+        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
+            e.as_mut().path = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
 
-    /// Read a numeric table value, if a table data accessor is present.
-    pub fn table_f64(&self, table: TableId, row: usize, col: ColId) -> Option<f64> {
-        let t = self.tables.get(&table)?;
-        let data = t.data.as_deref()?;
-        data.f64(row, col)
+    /// Set the `stroke` encoding to a constant value (rect and path marks).
+    pub fn stroke_const(self, v: Color) -> Self {
+        self.stroke_brush_const(Brush::Solid(v))
     }
 
-    /// Return the current table row count.
-    pub fn table_row_count(&self, table: TableId) -> Option<usize> {
-        self.tables.get(&table).map(|t| t.row_keys.len())
+    /// Set the `stroke` encoding to a constant brush (rect and path marks).
+    pub fn stroke_brush_const(mut self, v: impl Into<Brush>) -> Self {
+        let v = v.into();
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().stroke = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().stroke = Encoding::Const(v),
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
     }
-}
 
-/// Mark-level diffs keyed by stable identity.
-/// These are what Understory display/imaging layers consume.
-///
-/// Payloads are boxed to keep `MarkDiff` itself reasonably sized.
-#[derive(Debug)]
-pub enum MarkDiff {
-    /// A mark is newly present.
-    Enter {
-        /// Stable identifier.
-        id: MarkId,
-        /// Z-ordering for rendering; higher values are drawn above lower values.
-        z_index: i32,
-        /// The mark kind.
-        kind: MarkKind,
-        /// Newly evaluated channels.
-        new: Box<MarkPayload>,
-        /// Optional bounds hint for downstream damage calculation.
-        bounds: Option<Rect>,
-    },
-    /// A mark exists and some channels changed.
-    Update {
-        /// Stable identifier.
-        id: MarkId,
-        /// Previous z-index.
-        old_z_index: i32,
-        /// New z-index.
-        new_z_index: i32,
-        /// The mark kind.
-        kind: MarkKind,
-        /// Previously cached channels.
-        old: Box<MarkPayload>,
-        /// Newly evaluated channels.
-        new: Box<MarkPayload>,
-        /// Optional old bounds hint for downstream damage calculation.
-        old_bounds: Option<Rect>,
-        /// Optional new bounds hint for downstream damage calculation.
-        new_bounds: Option<Rect>,
-        /// Optional damage hint for downstream incremental rendering.
-        ///
-        /// When both old and new bounds are known, this is the union of the two. If either bound
-        /// is unknown, this is `None` and downstream systems may choose a fallback strategy.
-        damage: Option<Rect>,
-    },
-    /// A mark was removed.
-    Exit {
-        /// Stable identifier.
-        id: MarkId,
-        /// Z-ordering for rendering.
-        z_index: i32,
-        /// The mark kind.
-        kind: MarkKind,
-        /// Cached channels, if this mark was previously evaluated.
-        old: Option<Box<MarkPayload>>,
-        /// Optional bounds hint for downstream damage calculation.
-        bounds: Option<Rect>,
-    },
-}
-
-impl MarkDiff {
-    /// Returns the best available damage rectangle for this diff.
-    ///
-    /// - `Enter`/`Exit`: returns the mark bounds hint, if present.
-    /// - `Update`: returns the `damage` hint when present, otherwise `None`.
-    pub fn damage(&self) -> Option<Rect> {
-        match self {
-            Self::Enter { bounds, .. } => *bounds,
-            Self::Update { damage, .. } => *damage,
-            Self::Exit { bounds, .. } => *bounds,
+    /// Set the `stroke` encoding to a computed value (rect and path marks).
+    pub fn stroke_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Brush + 'static,
+        M: ComputeFor<Brush, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().stroke = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().stroke = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
         }
+        self
     }
-}
 
-/// A mutable collection of tables, signals, and marks, with incremental evaluation.
-pub struct Scene {
-    /// Tables keyed by [`TableId`].
-    pub tables: HashMap<TableId, Table>,
-    /// Signals keyed by [`SignalId`].
-    pub signals: HashMap<SignalId, Box<dyn AnySignal>>,
-    /// Marks keyed by [`MarkId`].
-    pub marks: HashMap<MarkId, Mark>,
-    pending_removals: Vec<MarkId>,
-}
+    /// Set the `stroke_width` encoding to a constant value (rect and path marks).
+    pub fn stroke_width_const(mut self, v: f64) -> Self {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => e.as_mut().stroke_width = Encoding::Const(v),
+            MarkEncodings::Path(e) => e.as_mut().stroke_width = Encoding::Const(v),
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
+    }
 
-impl fmt::Debug for Scene {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Scene")
-            .field("tables_len", &self.tables.len())
-            .field("signals_len", &self.signals.len())
-            .field("marks_len", &self.marks.len())
-            .field("pending_removals_len", &self.pending_removals.len())
-            .finish()
+    /// Set the `stroke_width` encoding to a computed value (rect and path marks).
+    pub fn stroke_width_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> f64 + 'static,
+        M: ComputeFor<f64, F>,
+    {
+        match &mut self.mark.encodings {
+            MarkEncodings::Rect(e) => {
+                e.as_mut().stroke_width = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Path(e) => {
+                e.as_mut().stroke_width = Encoding::Compute {
+                    deps: deps4(deps),
+                    f: M::box_compute(f),
+                };
+            }
+            MarkEncodings::Text(_)
+            | MarkEncodings::Group(_)
+            | MarkEncodings::Image(_)
+            | MarkEncodings::Instances(_) => {}
+        }
+        self
     }
-}
 
-impl Scene {
-    /// Create an empty scene.
-    pub fn new() -> Self {
-        Self {
-            tables: HashMap::new(),
-            signals: HashMap::new(),
-            marks: HashMap::new(),
-            pending_removals: Vec::new(),
+    /// Set the `corner_radii` encoding to a constant value (rect marks only).
+    ///
+    /// Accepts a single radius (`f64`) or per-corner radii (`(tl, tr, br, bl)`).
+    pub fn corner_radius_const(mut self, v: impl Into<RoundedRectRadii>) -> Self {
+        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
+            e.as_mut().corner_radii = Encoding::Const(v.into());
         }
+        self
     }
 
-    /// Queue a mark removal so it yields an `Exit` on the next `update()`.
-    pub fn remove_mark(&mut self, id: MarkId) {
-        self.pending_removals.push(id);
+    /// Set the `corner_radii` encoding to a computed value (rect marks only).
+    pub fn corner_radius_compute<F>(
+        mut self,
+        deps: impl IntoIterator<Item = InputRef>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> RoundedRectRadii + 'static,
+        M: ComputeFor<RoundedRectRadii, F>,
+    {
+        if let MarkEncodings::Rect(e) = &mut self.mark.encodings {
+            e.as_mut().corner_radii = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
 
-    /// Insert or replace a signal with an initial version of `1`.
-    pub fn insert_signal<T: Clone + 'static>(&mut self, id: SignalId, value: T) {
-        self.signals.insert(
-            id,
-            Box::new(Signal {
-                id,
-                version: 1,
-                value,
-            }),
-        );
+    /// Set the `stroke_style` encoding to a constant value (path marks only).
+    ///
+    /// The style's `width` is ignored; use [`MarkBuilder::stroke_width_const`] for the width.
+    pub fn stroke_style_const(mut self, v: Stroke) -> Self {
+        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
+            e.as_mut().stroke_style = Encoding::Const(v);
+        }
+        self
     }
 
-    /// Insert or replace a table with version `1`.
-    pub fn insert_table(&mut self, table: Table) {
-        self.tables.insert(table.id, table);
+    /// Set the `stroke_style` encoding to a computed value (path marks only).
+    pub fn stroke_style_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Stroke + 'static,
+        M: ComputeFor<Stroke, F>,
+    {
+        if let MarkEncodings::Path(e) = &mut self.mark.encodings {
+            e.as_mut().stroke_style = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
 
-    /// Replace a table's row keys and bump its version (inserting if missing).
-    pub fn set_table_row_keys(&mut self, id: TableId, row_keys: Vec<u64>) {
-        match self.tables.entry(id) {
-            Entry::Occupied(mut e) => {
-                e.get_mut().set_row_keys(row_keys);
-            }
-            Entry::Vacant(e) => {
-                let mut table = Table::new(id);
-                table.row_keys = row_keys;
-                e.insert(table);
-            }
+    /// Set the `transform` encoding to a constant value (group marks only).
+    pub fn transform_const(mut self, v: Affine) -> Self {
+        if let MarkEncodings::Group(e) = &mut self.mark.encodings {
+            e.as_mut().transform = Encoding::Const(v);
         }
+        self
     }
 
-    /// Set a table's data accessor and bump its version (inserting it if missing).
-    pub fn set_table_data(&mut self, id: TableId, data: Option<Box<dyn TableData>>) {
-        match self.tables.entry(id) {
-            Entry::Occupied(mut e) => {
-                e.get_mut().set_data(data);
-            }
-            Entry::Vacant(e) => {
-                let mut table = Table::new(id);
-                table.data = data;
-                e.insert(table);
-            }
+    /// Set the `transform` encoding to a computed value (group marks only).
+    pub fn transform_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Affine + 'static,
+        M: ComputeFor<Affine, F>,
+    {
+        if let MarkEncodings::Group(e) = &mut self.mark.encodings {
+            e.as_mut().transform = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
         }
+        self
     }
 
-    /// Set a signal value and bump its version (inserting it if missing).
+    /// Bind the `transform` encoding to an [`Affine`] signal (group marks only).
     ///
-    /// Returns `Err(TypeMismatch)` if a signal exists at `id` with a different type.
-    pub fn set_signal<T: Clone + 'static>(
-        &mut self,
-        id: SignalId,
-        value: T,
-    ) -> Result<(), SignalAccessError> {
-        let Some(signal) = self.signals.get_mut(&id) else {
-            self.insert_signal(id, value);
-            return Ok(());
-        };
-
-        let typed = signal
-            .as_any_mut()
-            .downcast_mut::<Signal<T>>()
-            .ok_or(SignalAccessError::TypeMismatch)?;
-
-        typed.bump();
-        typed.value = value;
-        Ok(())
+    /// This is the cheap path for pan/zoom: children keep their own coordinates, and changing the
+    /// signal produces a single [`MarkDiff::Update`] for the group rather than re-evaluating every
+    /// child. A missing or mistyped signal reads as the identity.
+    pub fn transform_signal(mut self, signal: SignalId) -> Self {
+        if let MarkEncodings::Group(e) = &mut self.mark.encodings {
+            e.as_mut().transform = Encoding::Compute {
+                deps: deps4([InputRef::Signal { signal }]),
+                f: M::box_sync_compute(move |ctx, _| {
+                    ctx.signal::<Affine>(signal).unwrap_or(Affine::IDENTITY)
+                }),
+            };
+        }
+        self
     }
 
-    /// Get an immutable reference to a signal value, if present and of type `T`.
-    pub fn signal_ref<T: Clone + 'static>(
-        &self,
-        id: SignalId,
-    ) -> Result<Option<&T>, SignalAccessError> {
-        let Some(signal) = self.signals.get(&id) else {
-            return Ok(None);
-        };
-        let typed = signal
-            .as_any()
-            .downcast_ref::<Signal<T>>()
-            .ok_or(SignalAccessError::TypeMismatch)?;
-        Ok(Some(&typed.value))
+    /// Set the `clip` encoding to a constant value (group marks only).
+    pub fn clip_const(mut self, v: Option<GroupClip>) -> Self {
+        if let MarkEncodings::Group(e) = &mut self.mark.encodings {
+            e.as_mut().clip = Encoding::Const(v);
+        }
+        self
     }
 
-    /// Get a mutable reference to a signal value, if present and of type `T`.
-    pub fn signal_mut<T: Clone + 'static>(
-        &mut self,
-        id: SignalId,
-    ) -> Result<Option<&mut T>, SignalAccessError> {
-        let Some(signal) = self.signals.get_mut(&id) else {
-            return Ok(None);
-        };
-        let typed = signal
-            .as_any_mut()
-            .downcast_mut::<Signal<T>>()
-            .ok_or(SignalAccessError::TypeMismatch)?;
-        Ok(Some(&mut typed.value))
+    /// Set the `clip` encoding to a computed value (group marks only).
+    pub fn clip_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> Option<GroupClip> + 'static,
+        M: ComputeFor<Option<GroupClip>, F>,
+    {
+        if let MarkEncodings::Group(e) = &mut self.mark.encodings {
+            e.as_mut().clip = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
     }
 
-    /// Insert or replace a mark.
-    pub fn upsert_mark(&mut self, mark: Mark) {
-        self.marks.insert(mark.id, mark);
+    /// Set the `image` encoding to a constant value (image marks only).
+    pub fn image_const(mut self, v: impl Into<ImageSource>) -> Self {
+        if let MarkEncodings::Image(e) = &mut self.mark.encodings {
+            e.as_mut().image = Encoding::Const(v.into());
+        }
+        self
     }
 
-    /// Replace the scene's mark set (structural reconciliation).
-    ///
-    /// The provided marks are treated as the complete "current frame" mark set:
-    /// - Any existing mark ids not present in `marks` are removed and returned as `Exit` diffs.
-    /// - Any mark whose [`MarkKind`] changes will also emit an `Exit` diff, and will re-enter on
-    ///   the next evaluation.
-    /// - Marks with ids that already exist will retain their cached evaluation state.
-    ///
-    /// This method does not evaluate marks; call [`Scene::update`] (or [`Scene::tick`]) to
-    /// compute `Enter`/`Update` diffs.
-    pub fn set_marks<I>(&mut self, marks: I) -> Vec<MarkDiff>
+    /// Set the `image` encoding to a computed value (image marks only).
+    pub fn image_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
     where
-        I: IntoIterator<Item = Mark>,
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> ImageSource + 'static,
+        M: ComputeFor<ImageSource, F>,
     {
-        self.pending_removals.clear();
+        if let MarkEncodings::Image(e) = &mut self.mark.encodings {
+            e.as_mut().image = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
+    }
 
-        let mut old_marks = core::mem::take(&mut self.marks);
-        let mut exits = Vec::new();
+    /// Set the image sampling quality to a constant value (image marks only).
+    pub fn image_quality_const(mut self, v: ImageQuality) -> Self {
+        if let MarkEncodings::Image(e) = &mut self.mark.encodings {
+            e.as_mut().quality = Encoding::Const(v);
+        }
+        self
+    }
 
-        for mut mark in marks {
-            mark.rebuild_deps();
+    /// Set the image sampling quality to a computed value (image marks only).
+    pub fn image_quality_compute<F>(
+        mut self,
+        deps: impl IntoIterator<Item = InputRef>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> ImageQuality + 'static,
+        M: ComputeFor<ImageQuality, F>,
+    {
+        if let MarkEncodings::Image(e) = &mut self.mark.encodings {
+            e.as_mut().quality = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute(f),
+            };
+        }
+        self
+    }
 
-            if let Some(old) = old_marks.remove(&mark.id) {
-                if mark.kind != old.kind {
-                    let bounds = old.cache.as_ref().and_then(MarkPayload::bounds);
-                    exits.push(MarkDiff::Exit {
-                        id: mark.id,
-                        z_index: old.z_index,
-                        kind: old.kind,
-                        old: old.cache.map(Box::new),
-                        bounds,
-                    });
-                } else {
-                    mark.force_eval |= mark.deps != old.deps;
-                    mark.cache = old.cache;
-                    mark.cached_z_index = old.cached_z_index;
-                    mark.last_seen = old.last_seen;
-                }
-            }
+    /// Set the instance data to a constant value (instance marks only).
+    pub fn instances_const(mut self, v: InstanceData) -> Self {
+        if let MarkEncodings::Instances(e) = &mut self.mark.encodings {
+            e.as_mut().instances = Encoding::Const(Arc::new(v));
+        }
+        self
+    }
 
-            self.marks.insert(mark.id, mark);
+    /// Set the instance data to a computed value (instance marks only).
+    ///
+    /// The closure builds every instance at once, typically by looping over a table's rows, so
+    /// deps are usually whole columns or [`InputRef::Table`].
+    pub fn instances_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> InstanceData + 'static,
+        M: ComputeFor<InstanceData, F>,
+    {
+        if let MarkEncodings::Instances(e) = &mut self.mark.encodings {
+            e.as_mut().instances = Encoding::Compute {
+                deps: deps4(deps),
+                f: M::box_compute_map(f, Arc::new),
+            };
         }
+        self
+    }
 
-        for (id, old) in old_marks {
-            let bounds = old.cache.as_ref().and_then(MarkPayload::bounds);
-            exits.push(MarkDiff::Exit {
-                id,
-                z_index: old.z_index,
-                kind: old.kind,
-                old: old.cache.map(Box::new),
-                bounds,
-            });
+    /// Set the instance glyph shape to a constant value (instance marks only).
+    pub fn instance_shape_const(mut self, v: InstanceShape) -> Self {
+        if let MarkEncodings::Instances(e) = &mut self.mark.encodings {
+            e.as_mut().shape = Encoding::Const(v);
         }
+        self
+    }
 
-        exits
+    /// Set the ARIA role to a constant value (see [`MarkMeta`]).
+    pub fn aria_role_const(mut self, v: impl Into<String>) -> Self {
+        self.mark.encodings.meta_mut().aria_role = Encoding::Const(v.into());
+        self
     }
 
-    /// Invalidate a mark so the next [`Scene::update`] recomputes all channels.
-    pub fn invalidate_mark(&mut self, id: MarkId) -> bool {
-        let Some(mark) = self.marks.get_mut(&id) else {
-            return false;
+    /// Set the ARIA role to a computed value (see [`MarkMeta`]).
+    pub fn aria_role_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
+    {
+        self.mark.encodings.meta_mut().aria_role = Encoding::Compute {
+            deps: deps4(deps),
+            f: M::box_compute(f),
         };
-        mark.force_eval = true;
-        true
+        self
     }
 
-    /// Perform a full tick: reconcile marks and evaluate diffs.
-    ///
-    /// This is the most convenient API for “frame-based” callers: provide the full mark set for
-    /// the current frame, and get back `Enter/Update/Exit` diffs.
-    pub fn tick<I>(&mut self, marks: I) -> Vec<MarkDiff>
+    /// Set the accessible description to a constant value (see [`MarkMeta`]).
+    pub fn description_const(mut self, v: impl Into<String>) -> Self {
+        self.mark.encodings.meta_mut().description = Encoding::Const(v.into());
+        self
+    }
+
+    /// Set the accessible description to a computed value (see [`MarkMeta`]).
+    pub fn description_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
     where
-        I: IntoIterator<Item = Mark>,
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
     {
-        let mut diffs = self.set_marks(marks);
-        diffs.extend(self.update());
-        diffs
+        self.mark.encodings.meta_mut().description = Encoding::Compute {
+            deps: deps4(deps),
+            f: M::box_compute(f),
+        };
+        self
+    }
+
+    /// Set the tooltip to a constant value (see [`MarkMeta`]).
+    pub fn tooltip_const(mut self, v: impl Into<String>) -> Self {
+        self.mark.encodings.meta_mut().tooltip = Encoding::Const(v.into());
+        self
+    }
+
+    /// Set the tooltip to a computed value (see [`MarkMeta`]).
+    pub fn tooltip_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
+    {
+        self.mark.encodings.meta_mut().tooltip = Encoding::Compute {
+            deps: deps4(deps),
+            f: M::box_compute(f),
+        };
+        self
+    }
+
+    /// Set the link target (`href`) to a constant value (see [`MarkMeta`]).
+    pub fn href_const(mut self, v: impl Into<String>) -> Self {
+        self.mark.encodings.meta_mut().href = Encoding::Const(v.into());
+        self
+    }
+
+    /// Set the link target (`href`) to a computed value (see [`MarkMeta`]).
+    pub fn href_compute<F>(mut self, deps: impl IntoIterator<Item = InputRef>, f: F) -> Self
+    where
+        F: Fn(&EvalCtx<'_, M>, MarkId) -> String + 'static,
+        M: ComputeFor<String, F>,
+    {
+        self.mark.encodings.meta_mut().href = Encoding::Compute {
+            deps: deps4(deps),
+            f: M::box_compute(f),
+        };
+        self
+    }
+
+    /// Finish building and rebuild dependencies.
+    pub fn build(mut self) -> Mark<M> {
+        self.mark.rebuild_deps();
+        self.mark
+    }
+}
+
+/// Read-only access to scene inputs during encoding evaluation.
+///
+/// With the `debug-deps` feature, reads through the accessor methods are recorded and checked
+/// against the encoding's declared deps (see `Scene::take_dep_violations`); reads through the
+/// raw `tables`/`signals` fields are not.
+pub struct EvalCtx<'a, M: Threading = Local> {
+    /// Tables available during evaluation.
+    pub tables: &'a HashMap<TableId, Table<M>>,
+    /// Signals available during evaluation.
+    pub signals: &'a HashMap<SignalId, Box<M::Signal>>,
+    /// Where reads are recorded while checking deps.
+    #[cfg(feature = "debug-deps")]
+    reads: Option<&'a debug_deps::ReadLog>,
+    /// Where compute encodings log their reads, if this update is audited.
+    #[cfg(feature = "debug-deps")]
+    audit: Option<&'a debug_deps::DepAudit>,
+}
+
+impl<M: Threading> fmt::Debug for EvalCtx<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvalCtx")
+            .field("tables_len", &self.tables.len())
+            .field("signals_len", &self.signals.len())
+            .finish()
+    }
+}
+
+impl<'a, M: Threading> EvalCtx<'a, M> {
+    fn new(
+        tables: &'a HashMap<TableId, Table<M>>,
+        signals: &'a HashMap<SignalId, Box<M::Signal>>,
+    ) -> Self {
+        Self {
+            tables,
+            signals,
+            #[cfg(feature = "debug-deps")]
+            reads: None,
+            #[cfg(feature = "debug-deps")]
+            audit: None,
+        }
+    }
+
+    /// Note that the encoding being evaluated read `input`.
+    #[inline]
+    fn record_read(&self, input: InputRef) {
+        #[cfg(feature = "debug-deps")]
+        if let Some(log) = self.reads {
+            log.record(input);
+        }
+        #[cfg(not(feature = "debug-deps"))]
+        let _ = input;
+    }
+
+    /// Return the current version of a table, if present.
+    pub fn table_version(&self, id: TableId) -> Option<Version> {
+        self.tables.get(&id).map(|t| t.version)
+    }
+    /// Return the current version of any input, if present.
+    ///
+    /// [`InputRef::TableCol`] and [`InputRef::TableRowCol`] resolve to the column version (see
+    /// [`Table::column_version`]).
+    pub fn input_version(&self, input: InputRef) -> Option<Version> {
+        match input {
+            InputRef::Table { table } => self.table_version(table),
+            InputRef::TableCol { table, col } | InputRef::TableRowCol { table, col } => {
+                self.table_column_version(table, col)
+            }
+            InputRef::Signal { signal } => self.signal_version(signal),
+        }
+    }
+    /// Return the current version of a table column, if the table is present.
+    pub fn table_column_version(&self, id: TableId, col: ColId) -> Option<Version> {
+        self.tables.get(&id).map(|t| t.column_version(col))
+    }
+    /// Return the current version of a signal, if present.
+    pub fn signal_version(&self, id: SignalId) -> Option<Version> {
+        self.signals.get(&id).map(|s| s.version())
+    }
+
+    /// Downcast and clone a signal value, if present and of type `T`.
+    pub fn signal<T: Clone + 'static>(&self, id: SignalId) -> Option<T> {
+        self.record_read(InputRef::Signal { signal: id });
+        let s = self.signals.get(&id)?;
+        let typed = s.as_any().downcast_ref::<Signal<T>>()?;
+        Some(typed.value.clone())
+    }
+
+    /// Read a numeric table value, if a table data accessor is present.
+    pub fn table_f64(&self, table: TableId, row: usize, col: ColId) -> Option<f64> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.f64(row, col)
+    }
+
+    /// Return a numeric table column as a contiguous slice, if the backing store has one.
+    pub fn table_f64_slice(&self, table: TableId, col: ColId) -> Option<&'a [f64]> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.f64_slice(col)
+    }
+
+    /// Return a numeric column view for bulk reads, if a table data accessor is present.
+    ///
+    /// The view reads from a contiguous slice when available and falls back to per-cell access.
+    pub fn table_f64_column(&self, table: TableId, col: ColId) -> Option<F64Column<'a>> {
+        self.record_read(InputRef::TableCol { table, col });
+        Some(F64Column::new(self.table_data(table)?, col))
+    }
+
+    /// Return the logical type of a table column, if known.
+    pub fn table_column_type(&self, table: TableId, col: ColId) -> Option<ColumnType> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.column_type(col)
+    }
+
+    /// Read a string table value, if a table data accessor is present.
+    ///
+    /// For [`ColumnType::Category`] columns this falls back to the category label, so label
+    /// encodings can read either representation.
+    pub fn table_str(&self, table: TableId, row: usize, col: ColId) -> Option<&'a str> {
+        self.record_read(InputRef::TableCol { table, col });
+        cell_str(self.table_data(table)?, row, col)
+    }
+
+    /// Read a numeric table value at the row with key `row_key`.
+    ///
+    /// This is the row-local counterpart of [`EvalCtx::table_f64`]: the row is looked up by key at
+    /// evaluation time, and the read is recorded as [`InputRef::TableRowCol`].
+    pub fn table_f64_by_key(&self, table: TableId, row_key: u64, col: ColId) -> Option<f64> {
+        self.record_read(InputRef::TableRowCol { table, col });
+        let t = self.tables.get(&table)?;
+        t.data.as_deref()?.f64(t.row_index(row_key)?, col)
+    }
+
+    /// Read a string table value at the row with key `row_key`.
+    ///
+    /// This is the row-local counterpart of [`EvalCtx::table_str`] (see
+    /// [`EvalCtx::table_f64_by_key`]).
+    pub fn table_str_by_key(&self, table: TableId, row_key: u64, col: ColId) -> Option<&'a str> {
+        self.record_read(InputRef::TableRowCol { table, col });
+        let t = self.tables.get(&table)?;
+        cell_str(
+            M::table_data(t.data.as_deref()?),
+            t.row_index(row_key)?,
+            col,
+        )
+    }
+
+    /// Read the dictionary code of a categorical table value.
+    pub fn table_category(&self, table: TableId, row: usize, col: ColId) -> Option<u32> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.category(row, col)
+    }
+
+    /// Resolve a dictionary code to its label in a categorical table column.
+    pub fn table_category_label(&self, table: TableId, col: ColId, code: u32) -> Option<&'a str> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.category_label(col, code)
+    }
+
+    /// Read a timestamp table value, if a table data accessor is present.
+    pub fn table_timestamp(&self, table: TableId, row: usize, col: ColId) -> Option<i64> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.timestamp(row, col)
+    }
+
+    /// Read a boolean table value, if a table data accessor is present.
+    pub fn table_bool(&self, table: TableId, row: usize, col: ColId) -> Option<bool> {
+        self.record_read(InputRef::TableCol { table, col });
+        self.table_data(table)?.bool(row, col)
+    }
+
+    fn table_data(&self, table: TableId) -> Option<&'a dyn TableData> {
+        Some(M::table_data(self.tables.get(&table)?.data.as_deref()?))
+    }
+
+    /// Return the current table row count.
+    pub fn table_row_count(&self, table: TableId) -> Option<usize> {
+        self.record_read(InputRef::Table { table });
+        self.tables.get(&table).map(|t| t.row_keys.len())
+    }
+
+    /// Return the current row index of a row key (see [`Table::row_index`]).
+    ///
+    /// Use this for encodings that depend on the row's position (such as a band slot); the lookup
+    /// is recorded as an [`InputRef::Table`] read, since inserts and deletes move rows. Encodings
+    /// that only read values at their row should use the by-key accessors (such as
+    /// [`EvalCtx::table_f64_by_key`]) instead.
+    pub fn table_row_index(&self, table: TableId, row_key: u64) -> Option<usize> {
+        self.record_read(InputRef::Table { table });
+        self.tables.get(&table)?.row_index(row_key)
+    }
+
+    /// Return the current table row keys.
+    pub fn table_row_keys(&self, table: TableId) -> Option<&'a [u64]> {
+        self.record_read(InputRef::Table { table });
+        self.tables.get(&table).map(|t| t.row_keys.as_slice())
+    }
+}
+
+/// Reads a string cell, falling back to the category label for categorical columns.
+fn cell_str(data: &dyn TableData, row: usize, col: ColId) -> Option<&str> {
+    if let Some(s) = data.str(row, col) {
+        return Some(s);
+    }
+    let code = data.category(row, col)?;
+    data.category_label(col, code)
+}
+
+/// Mark-level diffs keyed by stable identity.
+/// These are what Understory display/imaging layers consume.
+///
+/// Payloads are boxed to keep `MarkDiff` itself reasonably sized.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarkDiff {
+    /// A mark is newly present.
+    Enter {
+        /// Stable identifier.
+        id: MarkId,
+        /// Z-ordering for rendering; higher values are drawn above lower values.
+        z_index: i32,
+        /// Containing group, if any (see [`Mark::parent`]).
+        parent: Option<MarkId>,
+        /// The mark kind.
+        kind: MarkKind,
+        /// Newly evaluated channels.
+        new: Box<MarkPayload>,
+        /// Optional bounds hint for downstream damage calculation.
+        bounds: Option<Rect>,
+    },
+    /// A mark exists and some channels changed.
+    Update {
+        /// Stable identifier.
+        id: MarkId,
+        /// Previous z-index.
+        old_z_index: i32,
+        /// New z-index.
+        new_z_index: i32,
+        /// Previous containing group.
+        old_parent: Option<MarkId>,
+        /// New containing group.
+        new_parent: Option<MarkId>,
+        /// The mark kind.
+        kind: MarkKind,
+        /// Which channel groups differ between `old` and `new` (never empty).
+        ///
+        /// Renderers can use this to skip work, e.g. keep a tessellated path when only
+        /// [`ChannelMask::FILL`] changed.
+        changed: ChannelMask,
+        /// Previously cached channels, unless disabled via [`Scene::set_include_old_payloads`].
+        old: Option<Box<MarkPayload>>,
+        /// For [`MarkKind::Instances`] marks whose [`ChannelMask::INSTANCES`] changed: which
+        /// instances entered, changed, or exited, so consumers can patch per-instance buffers.
+        ///
+        /// This is index metadata only: `new` (and `old`, if included) still carry every
+        /// instance. The arrays are shared rather than copied, but a recorded or serialized
+        /// diff contains all of them.
+        instances: Option<Box<InstanceDelta>>,
+        /// Newly evaluated channels.
+        new: Box<MarkPayload>,
+        /// Optional old bounds hint for downstream damage calculation.
+        old_bounds: Option<Rect>,
+        /// Optional new bounds hint for downstream damage calculation.
+        new_bounds: Option<Rect>,
+        /// Optional damage hint for downstream incremental rendering.
+        ///
+        /// When both old and new bounds are known, this is the union of the two. If either bound
+        /// is unknown, this is `None` and downstream systems may choose a fallback strategy.
+        damage: Option<Rect>,
+    },
+    /// A mark was removed.
+    Exit {
+        /// Stable identifier.
+        id: MarkId,
+        /// Z-ordering for rendering.
+        z_index: i32,
+        /// Containing group, if any.
+        parent: Option<MarkId>,
+        /// The mark kind.
+        kind: MarkKind,
+        /// Cached channels, if this mark was previously evaluated.
+        old: Option<Box<MarkPayload>>,
+        /// Optional bounds hint for downstream damage calculation.
+        bounds: Option<Rect>,
+    },
+}
+
+/// A set of channel groups, as reported by [`MarkDiff::Update::changed`].
+///
+/// Each flag covers related payload fields across mark kinds:
+/// - [`GEOMETRY`](Self::GEOMETRY): rect/image rect, path, text position and angle, corner radii,
+///   group transform and clip, instance shape.
+/// - [`FILL`](Self::FILL): fill paint and fill opacity.
+/// - [`STROKE`](Self::STROKE): stroke paint, width, style, and opacity.
+/// - [`TEXT`](Self::TEXT): text content, font, line height, anchor, and baseline.
+/// - [`IMAGE`](Self::IMAGE): image source and sampling quality.
+/// - [`INSTANCES`](Self::INSTANCES): the [`InstanceData`] of an instance mark (see
+///   [`MarkDiff::Update::instances`] for which instances changed).
+/// - [`COMPOSITING`](Self::COMPOSITING): overall opacity and blend mode.
+/// - [`META`](Self::META): [`MarkMeta`].
+/// - [`Z_ORDER`](Self::Z_ORDER) and [`PARENT`](Self::PARENT): the mark's z-index and group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelMask(u16);
+
+impl ChannelMask {
+    /// No channels.
+    pub const NONE: Self = Self(0);
+    /// Geometry and placement.
+    pub const GEOMETRY: Self = Self(1 << 0);
+    /// Fill paint and opacity.
+    pub const FILL: Self = Self(1 << 1);
+    /// Stroke paint, width, style, and opacity.
+    pub const STROKE: Self = Self(1 << 2);
+    /// Text content and font properties.
+    pub const TEXT: Self = Self(1 << 3);
+    /// Image source and quality.
+    pub const IMAGE: Self = Self(1 << 4);
+    /// Overall opacity and blend mode.
+    pub const COMPOSITING: Self = Self(1 << 5);
+    /// Accessibility and interaction metadata.
+    pub const META: Self = Self(1 << 6);
+    /// Z-index.
+    pub const Z_ORDER: Self = Self(1 << 7);
+    /// Containing group.
+    pub const PARENT: Self = Self(1 << 8);
+    /// Packed instance data.
+    pub const INSTANCES: Self = Self(1 << 9);
+    /// Every channel.
+    pub const ALL: Self = Self((1 << 10) - 1);
+
+    /// Returns the raw bits.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if no channels are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if every channel in `other` is set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any channel in `other` is set.
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl core::ops::BitOr for ChannelMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for ChannelMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
     }
+}
+
+impl core::ops::BitAnd for ChannelMask {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl MarkDiff {
+    /// Returns the id of the mark this diff applies to.
+    pub fn id(&self) -> MarkId {
+        match self {
+            Self::Enter { id, .. } | Self::Update { id, .. } | Self::Exit { id, .. } => *id,
+        }
+    }
+
+    /// Returns the best available damage rectangle for this diff.
+    ///
+    /// - `Enter`/`Exit`: returns the mark bounds hint, if present.
+    /// - `Update`: returns the `damage` hint when present, otherwise `None`.
+    pub fn damage(&self) -> Option<Rect> {
+        match self {
+            Self::Enter { bounds, .. } => *bounds,
+            Self::Update { damage, .. } => *damage,
+            Self::Exit { bounds, .. } => *bounds,
+        }
+    }
+}
+
+/// A monotonic clock used to time [`Scene::update_with_stats`].
+///
+/// The core crate is `no_std` and does not depend on any timing crate; callers inject a clock
+/// from their environment (e.g. wrapping `std::time::Instant`). Any `Fn() -> Duration` closure
+/// implements this trait.
+pub trait FrameClock {
+    /// Return the current time, relative to an arbitrary fixed origin.
+    fn now(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> FrameClock for F {
+    fn now(&self) -> Duration {
+        (self)()
+    }
+}
+
+/// Per-frame counters (and optional timing) returned by [`Scene::update_with_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Marks visited by the scheduler (evaluated or checked for changes).
+    pub marks_visited: usize,
+    /// `Enter` diffs emitted.
+    pub marks_entered: usize,
+    /// `Update` diffs emitted.
+    pub marks_updated: usize,
+    /// `Exit` diffs emitted, including those returned by [`Scene::set_marks`] and
+    /// [`Scene::apply_table_patch`] since the previous update.
+    pub marks_exited: usize,
+    /// Computed encodings that were re-evaluated (constant encodings are not counted).
+    pub encodings_recomputed: usize,
+    /// Distinct tables whose (table or column) version was observed to change.
+    pub tables_changed: usize,
+    /// Distinct signals whose version was observed to change.
+    pub signals_changed: usize,
+    /// Wall-clock time spent in the update, if a [`FrameClock`] was provided.
+    pub elapsed: Option<Duration>,
+}
+
+/// A mutable collection of tables, signals, and marks, with incremental evaluation.
+///
+/// The scene maintains a reverse dependency index (`InputRef -> marks`) and a dirty set, so
+/// [`Scene::update`] only visits marks whose inputs changed (plus new, invalidated, or reordered
+/// marks), rather than every mark. The index is maintained by the mark APIs ([`Scene::set_marks`],
+/// [`Scene::upsert_mark`], [`Scene::remove_mark`], [`Scene::apply_table_patch`]); if you mutate
+/// [`Scene::marks`] directly, call [`Scene::invalidate_mark`] afterwards.
+///
+/// Scenes are single-threaded ([`Local`]) by default; a `Scene<Shared>` requires `Send + Sync`
+/// encodings and data so it can be evaluated across threads (see [`Threading`]).
+pub struct Scene<M: Threading = Local> {
+    /// Tables keyed by [`TableId`].
+    pub tables: HashMap<TableId, Table<M>>,
+    /// Signals keyed by [`SignalId`].
+    pub signals: HashMap<SignalId, Box<M::Signal>>,
+    /// Marks keyed by [`MarkId`].
+    pub marks: HashMap<MarkId, Mark<M>>,
+    pending_removals: Vec<MarkId>,
+    /// `Exit` diffs returned by reconciliation since the last update, for [`FrameStats`].
+    reconciled_exits: usize,
+    /// Reverse dependency index: which marks depend on each input.
+    dependents: HashMap<InputRef, HashSet<MarkId>>,
+    /// Input versions observed by the last update, for every indexed input.
+    observed: HashMap<InputRef, Version>,
+    /// Marks that must be visited by the next update.
+    dirty: HashSet<MarkId>,
+    /// Optional text metrics provider used for text bounds in diffs.
+    text_measurer: Option<Box<M::TextMeasurer>>,
+    /// Whether `Update` diffs carry the previous payload (see [`Scene::set_include_old_payloads`]).
+    include_old_payloads: bool,
+    /// Signals computed from other inputs, refreshed at the start of each update.
+    derived: HashMap<SignalId, DerivedSignal<M>>,
+    /// Dependency mismatches found by updates since the last [`Scene::take_dep_violations`].
+    #[cfg(feature = "debug-deps")]
+    dep_violations: Vec<DepViolation>,
+    /// Whether an undeclared read panics instead of only being recorded.
+    #[cfg(feature = "debug-deps")]
+    panic_on_undeclared_deps: bool,
+}
+
+impl<M: Threading> fmt::Debug for Scene<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Scene");
+        s.field("tables_len", &self.tables.len())
+            .field("signals_len", &self.signals.len())
+            .field("marks_len", &self.marks.len())
+            .field("pending_removals_len", &self.pending_removals.len())
+            .field("reconciled_exits", &self.reconciled_exits)
+            .field("dependents_len", &self.dependents.len())
+            .field("observed_len", &self.observed.len())
+            .field("dirty_len", &self.dirty.len())
+            .field("has_text_measurer", &self.text_measurer.is_some())
+            .field("include_old_payloads", &self.include_old_payloads)
+            .field("derived_signals_len", &self.derived.len());
+        #[cfg(feature = "debug-deps")]
+        s.field("dep_violations_len", &self.dep_violations.len())
+            .field("panic_on_undeclared_deps", &self.panic_on_undeclared_deps);
+        s.finish()
+    }
+}
+
+impl Scene {
+    /// Create an empty scene.
+    ///
+    /// Use [`Scene::new_shared`] for a scene that can be evaluated across threads.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Scene<Shared> {
+    /// Create an empty scene whose encodings and data must be `Send + Sync` (see [`Shared`]).
+    pub fn new_shared() -> Self {
+        Self::default()
+    }
+}
+
+impl<M: Threading> Default for Scene<M> {
+    fn default() -> Self {
+        Self {
+            tables: HashMap::new(),
+            signals: HashMap::new(),
+            marks: HashMap::new(),
+            pending_removals: Vec::new(),
+            reconciled_exits: 0,
+            dependents: HashMap::new(),
+            observed: HashMap::new(),
+            dirty: HashSet::new(),
+            text_measurer: None,
+            include_old_payloads: true,
+            derived: HashMap::new(),
+            #[cfg(feature = "debug-deps")]
+            dep_violations: Vec::new(),
+            #[cfg(feature = "debug-deps")]
+            panic_on_undeclared_deps: true,
+        }
+    }
+}
+
+impl<M: Threading> Scene<M> {
+    /// Set (or clear) the text metrics provider used to compute text bounds.
+    ///
+    /// With a measurer, text diffs carry bounds and `Update` damage like other marks (see
+    /// [`TextChannels::bounds`]); without one, text bounds are `None`. The measurer only affects
+    /// diffs produced after this call.
+    pub fn set_text_measurer(&mut self, measurer: Option<Box<M::TextMeasurer>>) {
+        self.text_measurer = measurer;
+    }
+
+    /// Return the text metrics provider, if any.
+    pub fn text_measurer(&self) -> Option<&dyn TextMeasurer> {
+        self.text_measurer.as_deref().map(M::text_measurer)
+    }
+
+    /// Set whether [`MarkDiff::Update`] diffs carry the previous payload (`old`); on by default.
+    ///
+    /// Consumers that only apply new state (most renderers) can turn this off to avoid boxing a
+    /// second payload per update; [`MarkDiff::Update::changed`] still reports which channels
+    /// changed. Transitions ([`animate::Animator`]) fall back to the displayed state.
+    pub fn set_include_old_payloads(&mut self, include: bool) {
+        self.include_old_payloads = include;
+    }
+
+    /// Return `true` if [`MarkDiff::Update`] diffs carry the previous payload.
+    pub fn includes_old_payloads(&self) -> bool {
+        self.include_old_payloads
+    }
+
+    /// Set whether an encoding reading an input missing from its deps panics; on by default.
+    ///
+    /// When off, such reads are only recorded for [`Scene::take_dep_violations`].
+    #[cfg(feature = "debug-deps")]
+    pub fn set_panic_on_undeclared_deps(&mut self, panic: bool) {
+        self.panic_on_undeclared_deps = panic;
+    }
+
+    /// Return and clear the dependency mismatches found since the last call.
+    ///
+    /// Updates evaluate compute encodings (and derived signals) under a recording [`EvalCtx`] and
+    /// compare what each one read to its declared deps, naming the mark and channel or the
+    /// derived signal. Unused deps are only ever recorded; undeclared reads panic unless disabled
+    /// with [`Scene::set_panic_on_undeclared_deps`].
+    #[cfg(feature = "debug-deps")]
+    pub fn take_dep_violations(&mut self) -> Vec<DepViolation> {
+        core::mem::take(&mut self.dep_violations)
+    }
+
+    /// Queue a mark removal so it yields an `Exit` on the next `update()`.
+    pub fn remove_mark(&mut self, id: MarkId) {
+        self.pending_removals.push(id);
+    }
+
+    /// Return the number of marks known to need evaluation.
+    ///
+    /// This includes marks left over by [`Scene::update_budgeted`], but not marks whose inputs
+    /// changed since the last update (those are discovered when the update starts).
+    pub fn pending_update_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Return the marks that declare a dependency on `input`.
+    pub fn dependents(&self, input: InputRef) -> impl Iterator<Item = MarkId> + '_ {
+        self.dependents.get(&input).into_iter().flatten().copied()
+    }
+
+    fn index_mark(&mut self, id: MarkId, deps: &[InputRef]) {
+        for dep in deps {
+            self.dependents.entry(*dep).or_default().insert(id);
+        }
+    }
+
+    fn unindex_mark(&mut self, id: MarkId, deps: &[InputRef]) {
+        for dep in deps {
+            if let Entry::Occupied(mut e) = self.dependents.entry(*dep) {
+                e.get_mut().remove(&id);
+                if e.get().is_empty() {
+                    e.remove();
+                    self.observed.remove(dep);
+                }
+            }
+        }
+    }
+
+    /// Insert a mark into the map and the dependency index, replacing any previous mark.
+    fn insert_indexed(&mut self, mark: Mark<M>) {
+        let id = mark.id;
+        let deps = mark.deps.clone();
+        if mark.needs_visit() {
+            self.dirty.insert(id);
+        }
+        if let Some(old) = self.marks.insert(id, mark) {
+            self.unindex_mark(id, &old.deps);
+        }
+        self.index_mark(id, &deps);
+    }
+
+    /// Remove a mark from the map and the dependency index.
+    fn remove_indexed(&mut self, id: MarkId) -> Option<Mark<M>> {
+        let old = self.marks.remove(&id)?;
+        self.unindex_mark(id, &old.deps);
+        self.dirty.remove(&id);
+        Some(old)
+    }
+
+    /// Insert or replace a signal with an initial version of `1`.
+    pub fn insert_signal<T: Clone + 'static>(&mut self, id: SignalId, value: T)
+    where
+        M: SignalFor<T>,
+    {
+        self.signals.insert(
+            id,
+            M::box_signal(Signal {
+                id,
+                version: 1,
+                value,
+            }),
+        );
+    }
+
+    /// Insert or replace a table with version `1`.
+    pub fn insert_table(&mut self, table: Table<M>) {
+        self.tables.insert(table.id, table);
+    }
+
+    /// Replace a table's row keys and bump its version (inserting if missing).
+    pub fn set_table_row_keys(&mut self, id: TableId, row_keys: Vec<u64>) {
+        match self.tables.entry(id) {
+            Entry::Occupied(mut e) => {
+                e.get_mut().set_row_keys(row_keys);
+            }
+            Entry::Vacant(e) => {
+                e.insert(Table {
+                    row_keys,
+                    ..Table::empty(id)
+                });
+            }
+        }
+    }
+
+    /// Set a table's data accessor and bump its version (inserting it if missing).
+    pub fn set_table_data(&mut self, id: TableId, data: Option<Box<M::TableData>>) {
+        match self.tables.entry(id) {
+            Entry::Occupied(mut e) => {
+                e.get_mut().set_data(data);
+            }
+            Entry::Vacant(e) => {
+                let mut table = Table::empty(id);
+                table.data = data;
+                e.insert(table);
+            }
+        }
+    }
+
+    /// Mark a single table column as changed.
+    ///
+    /// Only encodings that depend on this column (via [`InputRef::TableCol`]) or on the whole
+    /// table (via [`InputRef::Table`]) are re-evaluated by the next [`Scene::update`].
+    ///
+    /// Returns `false` if the table does not exist.
+    pub fn set_table_column_changed(&mut self, id: TableId, col: ColId) -> bool {
+        let Some(table) = self.tables.get_mut(&id) else {
+            return false;
+        };
+        table.bump_column(col);
+        true
+    }
+
+    /// Replace a table's data accessor, bumping only the listed columns (inserting it if missing).
+    ///
+    /// See [`Table::set_column_data`].
+    pub fn set_table_column_data(
+        &mut self,
+        id: TableId,
+        data: Option<Box<M::TableData>>,
+        changed: impl IntoIterator<Item = ColId>,
+    ) {
+        match self.tables.entry(id) {
+            Entry::Occupied(mut e) => {
+                e.get_mut().set_column_data(data, changed);
+            }
+            Entry::Vacant(e) => {
+                let mut table = Table::empty(id);
+                table.data = data;
+                e.insert(table);
+            }
+        }
+    }
+
+    /// Set a signal value and bump its version (inserting it if missing).
+    ///
+    /// Returns `Err(TypeMismatch)` if a signal exists at `id` with a different type.
+    pub fn set_signal<T: Clone + 'static>(
+        &mut self,
+        id: SignalId,
+        value: T,
+    ) -> Result<(), SignalAccessError>
+    where
+        M: SignalFor<T>,
+    {
+        let Some(signal) = self.signals.get_mut(&id) else {
+            self.insert_signal(id, value);
+            return Ok(());
+        };
+
+        let typed = signal
+            .as_any_mut()
+            .downcast_mut::<Signal<T>>()
+            .ok_or(SignalAccessError::TypeMismatch)?;
+
+        typed.bump();
+        typed.value = value;
+        Ok(())
+    }
+
+    /// Insert or replace a derived signal, computed by `f` from the inputs listed in `deps`.
+    ///
+    /// The signal is evaluated immediately, and again at the start of each [`Scene::update`] in
+    /// which one of its `deps` changed version. Derived signals may depend on other derived
+    /// signals; they are refreshed in dependency order (a dependency cycle is broken arbitrarily,
+    /// and each signal is evaluated at most once per update). The signal's version only bumps
+    /// when the computed value differs from the previous one, so marks depending on it are
+    /// re-evaluated only for actual changes.
+    ///
+    /// As with [`Encoding::Compute`], `deps` must list every input `f` reads. Values written with
+    /// [`Scene::set_signal`] are overwritten the next time the signal is refreshed.
+    pub fn insert_derived_signal<T, F>(
+        &mut self,
+        id: SignalId,
+        deps: impl IntoIterator<Item = InputRef>,
+        f: F,
+    ) where
+        T: Clone + PartialEq + 'static,
+        F: Fn(&EvalCtx<'_, M>) -> T + 'static,
+        M: SignalFor<T>,
+    {
+        self.derived.insert(
+            id,
+            DerivedSignal {
+                deps: deps4(deps),
+                seen: None,
+                eval: Box::new(move |ctx| Box::new(f(ctx))),
+                store: store_derived::<M, T>,
+            },
+        );
+        self.refresh_derived(id);
+    }
+
+    /// Stop deriving a signal; its last value stays in place as a plain signal.
+    ///
+    /// Returns `false` if `id` was not a derived signal.
+    pub fn remove_derived_signal(&mut self, id: SignalId) -> bool {
+        self.derived.remove(&id).is_some()
+    }
+
+    /// Return `true` if `id` is a derived signal.
+    pub fn is_derived_signal(&self, id: SignalId) -> bool {
+        self.derived.contains_key(&id)
+    }
+
+    /// Re-evaluate a derived signal if any of its inputs changed since its last evaluation.
+    fn refresh_derived(&mut self, id: SignalId) {
+        let Some(derived) = self.derived.get_mut(&id) else {
+            return;
+        };
+        let ctx = EvalCtx::new(&self.tables, &self.signals);
+        let seen: SmallVec<[Option<Version>; 4]> = derived
+            .deps
+            .iter()
+            .map(|dep| ctx.input_version(*dep))
+            .collect();
+        if derived.seen.as_ref() == Some(&seen) {
+            return;
+        }
+        #[cfg(feature = "debug-deps")]
+        let log = debug_deps::ReadLog::default();
+        #[cfg(feature = "debug-deps")]
+        let ctx = EvalCtx {
+            reads: Some(&log),
+            ..ctx
+        };
+        let value = (derived.eval)(&ctx);
+        derived.seen = Some(seen);
+        #[cfg(feature = "debug-deps")]
+        let start = self.dep_violations.len();
+        #[cfg(feature = "debug-deps")]
+        debug_deps::check_derived(id, &derived.deps, &log.take(), &mut self.dep_violations);
+        (derived.store)(&mut self.signals, id, value);
+        #[cfg(feature = "debug-deps")]
+        self.raise_undeclared_deps(start);
+    }
+
+    /// Refresh all derived signals, dependencies first.
+    fn refresh_derived_signals(&mut self) {
+        if self.derived.is_empty() {
+            return;
+        }
+        let mut roots: Vec<SignalId> = self.derived.keys().copied().collect();
+        roots.sort_unstable();
+        // Iterative post-order DFS over derived-on-derived edges; `false` marks "in progress".
+        let mut state: HashMap<SignalId, bool> = HashMap::new();
+        let mut order = Vec::with_capacity(roots.len());
+        for root in roots {
+            let mut stack = alloc::vec![(root, 0_usize)];
+            while let Some((id, next)) = stack.pop() {
+                if next == 0 {
+                    if state.contains_key(&id) {
+                        continue;
+                    }
+                    state.insert(id, false);
+                }
+                let deps = &self.derived[&id].deps;
+                let upstream = deps[next..].iter().position(|dep| {
+                    matches!(dep, InputRef::Signal { signal }
+                        if self.derived.contains_key(signal) && !state.contains_key(signal))
+                });
+                match upstream {
+                    Some(offset) => {
+                        let InputRef::Signal { signal } = deps[next + offset] else {
+                            unreachable!("matched a signal dependency above");
+                        };
+                        stack.push((id, next + offset + 1));
+                        stack.push((signal, 0));
+                    }
+                    None => {
+                        state.insert(id, true);
+                        order.push(id);
+                    }
+                }
+            }
+        }
+        for id in order {
+            self.refresh_derived(id);
+        }
+    }
+
+    /// Get an immutable reference to a signal value, if present and of type `T`.
+    pub fn signal_ref<T: Clone + 'static>(
+        &self,
+        id: SignalId,
+    ) -> Result<Option<&T>, SignalAccessError> {
+        let Some(signal) = self.signals.get(&id) else {
+            return Ok(None);
+        };
+        let typed = signal
+            .as_any()
+            .downcast_ref::<Signal<T>>()
+            .ok_or(SignalAccessError::TypeMismatch)?;
+        Ok(Some(&typed.value))
+    }
+
+    /// Get a mutable reference to a signal value, if present and of type `T`.
+    pub fn signal_mut<T: Clone + 'static>(
+        &mut self,
+        id: SignalId,
+    ) -> Result<Option<&mut T>, SignalAccessError> {
+        let Some(signal) = self.signals.get_mut(&id) else {
+            return Ok(None);
+        };
+        let typed = signal
+            .as_any_mut()
+            .downcast_mut::<Signal<T>>()
+            .ok_or(SignalAccessError::TypeMismatch)?;
+        Ok(Some(&mut typed.value))
+    }
+
+    /// Insert or replace a mark.
+    pub fn upsert_mark(&mut self, mark: Mark<M>) {
+        self.insert_indexed(mark);
+    }
+
+    /// Replace the scene's mark set (structural reconciliation).
+    ///
+    /// The provided marks are treated as the complete "current frame" mark set:
+    /// - Any existing mark ids not present in `marks` are removed and returned as `Exit` diffs.
+    /// - Any mark whose [`MarkKind`] changes will also emit an `Exit` diff, and will re-enter on
+    ///   the next evaluation.
+    /// - Marks with ids that already exist will retain their cached evaluation state.
+    ///
+    /// This method does not evaluate marks; call [`Scene::update`] (or [`Scene::tick`]) to
+    /// compute `Enter`/`Update` diffs.
+    pub fn set_marks<I>(&mut self, marks: I) -> Vec<MarkDiff>
+    where
+        I: IntoIterator<Item = Mark<M>>,
+    {
+        self.pending_removals.clear();
+
+        let mut old_marks = core::mem::take(&mut self.marks);
+        let mut exits = Vec::new();
+
+        for mut mark in marks {
+            mark.rebuild_deps();
+
+            if let Some(old) = old_marks.remove(&mark.id) {
+                let old_deps = old.deps.clone();
+                exits.extend(mark.adopt_state(old, self.text_measurer()));
+                self.unindex_mark(mark.id, &old_deps);
+            }
+
+            self.insert_indexed(mark);
+        }
+
+        for (id, old) in old_marks {
+            self.unindex_mark(id, &old.deps);
+            self.dirty.remove(&id);
+            let bounds = old
+                .cache
+                .as_ref()
+                .and_then(|p| p.bounds_with(self.text_measurer()));
+            exits.push(MarkDiff::Exit {
+                id,
+                z_index: old.z_index,
+                parent: old.parent,
+                kind: old.kind,
+                old: old.cache.map(Box::new),
+                bounds,
+            });
+        }
+
+        self.reconciled_exits += exits.len();
+        exits
+    }
+
+    /// Invalidate a mark so the next [`Scene::update`] recomputes all channels.
+    ///
+    /// This also re-indexes the mark's dependencies, so it must be called after inserting or
+    /// mutating a mark through [`Scene::marks`] directly. Only the mark's current deps are
+    /// unindexed; to change a mark's deps, replace it with [`Scene::upsert_mark`] instead.
+    pub fn invalidate_mark(&mut self, id: MarkId) -> bool {
+        let Some(mark) = self.marks.get_mut(&id) else {
+            return false;
+        };
+        mark.force_eval = true;
+        let deps = mark.deps.clone();
+        self.unindex_mark(id, &deps);
+        self.index_mark(id, &deps);
+        self.dirty.insert(id);
+        true
+    }
+
+    /// Perform a full tick: reconcile marks and evaluate diffs.
+    ///
+    /// This is the most convenient API for “frame-based” callers: provide the full mark set for
+    /// the current frame, and get back `Enter/Update/Exit` diffs.
+    pub fn tick<I>(&mut self, marks: I) -> Vec<MarkDiff>
+    where
+        I: IntoIterator<Item = Mark<M>>,
+    {
+        let mut diffs = self.set_marks(marks);
+        diffs.extend(self.update());
+        diffs
+    }
+
+    /// Build and tick a complete mark set derived from a table's row keys.
+    ///
+    /// This is a v1 convenience for "one mark per row" patterns. It derives stable
+    /// `MarkId`s via [`MarkId::for_row`], reconciles the full set, and evaluates.
+    ///
+    /// If you need multiple marks per row or nested mark hierarchies, build marks
+    /// explicitly and use [`Scene::tick`].
+    pub fn tick_table_rows<F>(&mut self, table: TableId, mut build: F) -> Vec<MarkDiff>
+    where
+        F: FnMut(MarkId, u64, usize) -> Mark<M>,
+    {
+        let Some(t) = self.tables.get_mut(&table) else {
+            return self.tick(core::iter::empty());
+        };
+        t.index_rows();
+        let keys = t.row_keys.clone();
+
+        self.tick(
+            keys.into_iter()
+                .enumerate()
+                .map(|(i, k)| build(MarkId::for_row(table, k), k, i)),
+        )
+    }
+
+    /// Apply a row-level [`TablePatch`] to a table and its per-row marks.
+    ///
+    /// This is the incremental counterpart of [`Scene::tick_table_rows`] for tables whose marks are
+    /// keyed by [`MarkId::for_row`]. Only marks for touched rows are affected:
+    /// - marks for deleted rows are removed and returned as `Exit` diffs,
+    /// - marks for inserted rows are built via `build(id, row_key)` and enter on the next update,
+    /// - marks for updated rows re-evaluate only encodings that depend on the patch's changed
+    ///   columns (or every encoding, if no columns are listed).
+    ///
+    /// Rows that shift because earlier rows were deleted are left alone, so encodings must read
+    /// their row by key at evaluation time (via [`EvalCtx::table_f64_by_key`] and friends, under
+    /// [`InputRef::TableRowCol`] deps) rather than capture a row index; a sliding window that
+    /// appends and drops rows only touches those rows.
+    ///
+    /// The patch still bumps the table's column versions (see [`Table::apply_patch`]), so
+    /// whole-column dependents such as aggregates, derived signals, and line paths re-evaluate as
+    /// usual. Only [`InputRef::TableRowCol`] deps skip those bumps, since the row-level changes
+    /// above cover them; [`InputRef::TableCol`] deps of per-row marks re-evaluate like any other.
+    ///
+    /// The table is inserted if missing. Like [`Scene::set_marks`], this does not evaluate marks;
+    /// call [`Scene::update`] (or use [`Scene::tick_table_patch`]).
+    pub fn apply_table_patch<F>(
+        &mut self,
+        table: TableId,
+        mut patch: TablePatch<M>,
+        mut build: F,
+    ) -> Vec<MarkDiff>
+    where
+        F: FnMut(MarkId, u64) -> Mark<M>,
+    {
+        let t = self
+            .tables
+            .entry(table)
+            .or_insert_with(|| Table::empty(table));
+        // Row-local dependents that were up to date before the patch stay up to date after it.
+        let settled: Vec<InputRef> = self
+            .observed
+            .iter()
+            .filter(|(key, v)| match key {
+                InputRef::TableRowCol { table: t_id, col } if *t_id == table => {
+                    **v == t.column_version(*col)
+                }
+                _ => false,
+            })
+            .map(|(key, _)| *key)
+            .collect();
+        let rows = t.apply_patch(&mut patch);
+        for key in settled {
+            if let InputRef::TableRowCol { col, .. } = key {
+                self.observed.insert(key, t.column_version(col));
+            }
+        }
+        let inserted: Vec<u64> = rows.inserted.iter().map(|&row| t.row_keys[row]).collect();
+        let updated: Vec<u64> = rows.updated.iter().map(|&row| t.row_keys[row]).collect();
+
+        let mut exits = Vec::new();
+        for key in rows.deleted {
+            let id = MarkId::for_row(table, key);
+            self.pending_removals.retain(|r| *r != id);
+            if let Some(old) = self.remove_indexed(id) {
+                let bounds = old
+                    .cache
+                    .as_ref()
+                    .and_then(|p| p.bounds_with(self.text_measurer()));
+                exits.push(MarkDiff::Exit {
+                    id,
+                    z_index: old.z_index,
+                    parent: old.parent,
+                    kind: old.kind,
+                    old: old.cache.map(Box::new),
+                    bounds,
+                });
+            }
+        }
+
+        for key in inserted {
+            let id = MarkId::for_row(table, key);
+            let mut mark = build(id, key);
+            mark.rebuild_deps();
+            if let Some(old) = self.remove_indexed(id) {
+                exits.extend(mark.adopt_state(old, self.text_measurer()));
+                mark.force_eval = true;
+            }
+            self.insert_indexed(mark);
+        }
+
+        for key in updated {
+            let id = MarkId::for_row(table, key);
+            let Some(mark) = self.marks.get_mut(&id) else {
+                continue;
+            };
+            self.dirty.insert(id);
+            if patch.changed_columns.is_empty() {
+                mark.force_eval = true;
+            } else {
+                for &col in &patch.changed_columns {
+                    let input = InputRef::TableRowCol { table, col };
+                    if !mark.pending_inputs.contains(&input) {
+                        mark.pending_inputs.push(input);
+                    }
+                }
+            }
+        }
+
+        self.reconciled_exits += exits.len();
+        exits
+    }
+
+    /// Apply a row-level [`TablePatch`] and evaluate diffs.
+    ///
+    /// This is [`Scene::apply_table_patch`] followed by [`Scene::update`].
+    pub fn tick_table_patch<F>(
+        &mut self,
+        table: TableId,
+        patch: TablePatch<M>,
+        build: F,
+    ) -> Vec<MarkDiff>
+    where
+        F: FnMut(MarkId, u64) -> Mark<M>,
+    {
+        let mut diffs = self.apply_table_patch(table, patch, build);
+        diffs.extend(self.update());
+        diffs
+    }
+
+    /// Evaluate incremental updates and produce mark diffs.
+    ///
+    /// Use this when the mark set is stable and only input versions changed (tables/signals).
+    ///
+    /// Only dirty marks are visited: marks depending on an input whose version changed since the
+    /// last update (found via the reverse dependency index), plus new, invalidated, patched, or
+    /// reordered marks.
+    ///
+    /// Diffs for removed marks come first, followed by the visited marks' diffs in [`MarkId`]
+    /// order.
+    pub fn update(&mut self) -> Vec<MarkDiff> {
+        self.update_inner(None, None, update_marks)
+    }
+
+    /// Like [`Scene::update`], but also returns per-frame counters.
+    ///
+    /// If `clock` is provided, [`FrameStats::elapsed`] is the time spent in the update. Counting
+    /// adds a small amount of work per visited mark, so prefer [`Scene::update`] when the stats
+    /// are not consumed.
+    pub fn update_with_stats(
+        &mut self,
+        clock: Option<&dyn FrameClock>,
+    ) -> (Vec<MarkDiff>, FrameStats) {
+        self.update_counted(clock, update_marks)
+    }
+
+    fn update_counted(
+        &mut self,
+        clock: Option<&dyn FrameClock>,
+        eval: EvalMarksFn<M>,
+    ) -> (Vec<MarkDiff>, FrameStats) {
+        let start = clock.map(FrameClock::now);
+        let mut stats = FrameStats::default();
+        let diffs = self.update_inner(None, Some(&mut stats), eval);
+        for diff in &diffs {
+            match diff {
+                MarkDiff::Enter { .. } => stats.marks_entered += 1,
+                MarkDiff::Update { .. } => stats.marks_updated += 1,
+                MarkDiff::Exit { .. } => stats.marks_exited += 1,
+            }
+        }
+        if let (Some(clock), Some(start)) = (clock, start) {
+            stats.elapsed = Some(clock.now().saturating_sub(start));
+        }
+        (diffs, stats)
+    }
+
+    /// Like [`Scene::update`], but evaluates at most `max_marks` dirty marks.
+    ///
+    /// Dirty marks are evaluated in [`Mark::priority`] order (highest first, ties broken by
+    /// [`MarkId`]); the rest stay dirty and are evaluated by subsequent calls. Queued removals are
+    /// always processed. Use [`Scene::pending_update_count`] to check whether work remains.
+    pub fn update_budgeted(&mut self, max_marks: usize) -> Vec<MarkDiff> {
+        self.update_inner(Some(max_marks), None, update_marks)
+    }
+
+    fn update_inner(
+        &mut self,
+        max_marks: Option<usize>,
+        mut stats: Option<&mut FrameStats>,
+        eval: EvalMarksFn<M>,
+    ) -> Vec<MarkDiff> {
+        let mut diffs = Vec::new();
+
+        let reconciled_exits = core::mem::take(&mut self.reconciled_exits);
+        if let Some(stats) = stats.as_deref_mut() {
+            stats.marks_exited += reconciled_exits;
+        }
+        self.refresh_derived_signals();
+
+        for id in core::mem::take(&mut self.pending_removals) {
+            let removed = self.remove_indexed(id);
+            let old = removed.as_ref().and_then(|m| m.cache.clone());
+            let bounds = old
+                .as_ref()
+                .and_then(|p| p.bounds_with(self.text_measurer()));
+            diffs.push(MarkDiff::Exit {
+                id,
+                z_index: removed.as_ref().map_or(0, |m| m.z_index),
+                parent: removed.as_ref().and_then(|m| m.parent),
+                kind: removed.as_ref().map_or(MarkKind::Rect, |m| m.kind),
+                old: old.map(Box::new),
+                bounds,
+            });
+        }
+
+        #[cfg(feature = "debug-deps")]
+        let audit = debug_deps::DepAudit::default();
+        let ctx = EvalCtx::new(&self.tables, &self.signals);
+        #[cfg(feature = "debug-deps")]
+        let ctx = EvalCtx {
+            audit: Some(&audit),
+            ..ctx
+        };
+
+        let mut changed_tables = HashSet::new();
+        let mut changed_signals = HashSet::new();
+        for (key, ids) in &self.dependents {
+            let Some(v) = ctx.input_version(*key) else {
+                continue;
+            };
+            if self.observed.insert(*key, v) != Some(v) {
+                self.dirty.extend(ids.iter().copied());
+                if stats.is_some() {
+                    match *key {
+                        InputRef::Table { table }
+                        | InputRef::TableCol { table, .. }
+                        | InputRef::TableRowCol { table, .. } => {
+                            changed_tables.insert(table);
+                        }
+                        InputRef::Signal { signal } => {
+                            changed_signals.insert(signal);
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(stats) = stats.as_deref_mut() {
+            stats.tables_changed = changed_tables.len();
+            stats.signals_changed = changed_signals.len();
+        }
+
+        let ids: Vec<MarkId> = match max_marks {
+            Some(max) if max < self.dirty.len() => {
+                let mut ranked: Vec<(i32, MarkId)> = self
+                    .dirty
+                    .iter()
+                    .map(|id| (self.marks.get(id).map_or(0, |m| m.priority), *id))
+                    .collect();
+                ranked.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+                ranked.truncate(max);
+                for (_, id) in &ranked {
+                    self.dirty.remove(id);
+                }
+                ranked.into_iter().map(|(_, id)| id).collect()
+            }
+            _ => {
+                let mut ids: Vec<MarkId> = self.dirty.drain().collect();
+                ids.sort_unstable();
+                ids
+            }
+        };
+
+        diffs.extend(eval(
+            &mut self.marks,
+            &ids,
+            &ctx,
+            self.text_measurer.as_deref(),
+            self.include_old_payloads,
+            stats,
+        ));
+
+        #[cfg(feature = "debug-deps")]
+        self.check_deps(&ids, &audit);
+
+        diffs
+    }
+
+    /// Audits the compute encodings evaluated for the marks in `ids` (see
+    /// [`Scene::take_dep_violations`]).
+    #[cfg(feature = "debug-deps")]
+    fn check_deps(&mut self, ids: &[MarkId], audit: &debug_deps::DepAudit) {
+        let reads = audit.take();
+        let start = self.dep_violations.len();
+        for id in ids {
+            if let Some(mark) = self.marks.get(id) {
+                debug_deps::check_mark(&mark.encodings, *id, &reads, &mut self.dep_violations);
+            }
+        }
+        self.raise_undeclared_deps(start);
+    }
+
+    /// Panics on the first undeclared read recorded since `start`, unless disabled.
+    #[cfg(feature = "debug-deps")]
+    fn raise_undeclared_deps(&self, start: usize) {
+        if self.panic_on_undeclared_deps
+            && let Some(v) = self.dep_violations[start..]
+                .iter()
+                .find(|v| matches!(v.kind, DepViolationKind::Undeclared(_)))
+        {
+            panic!("{v}");
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl Scene<Shared> {
+    /// Like [`Scene::update`], but evaluates large dirty sets across threads.
+    ///
+    /// The diffs are the same as [`Scene::update`] would return, in the same order.
+    pub fn update_parallel(&mut self) -> Vec<MarkDiff> {
+        self.update_inner(None, None, update_marks_parallel)
+    }
+
+    /// Like [`Scene::update_with_stats`], but evaluates large dirty sets across threads.
+    pub fn update_parallel_with_stats(
+        &mut self,
+        clock: Option<&dyn FrameClock>,
+    ) -> (Vec<MarkDiff>, FrameStats) {
+        self.update_counted(clock, update_marks_parallel)
+    }
+}
+
+/// Evaluates the dirty marks listed in `ids`, returning their diffs in `ids` order.
+type EvalMarksFn<M> = fn(
+    &mut HashMap<MarkId, Mark<M>>,
+    &[MarkId],
+    &EvalCtx<'_, M>,
+    Option<&<M as Threading>::TextMeasurer>,
+    bool,
+    Option<&mut FrameStats>,
+) -> Vec<MarkDiff>;
+
+/// Evaluates the marks listed in `ids` on the calling thread, returning their diffs in `ids`
+/// order.
+fn update_marks<M: Threading>(
+    marks: &mut HashMap<MarkId, Mark<M>>,
+    ids: &[MarkId],
+    ctx: &EvalCtx<'_, M>,
+    measurer: Option<&M::TextMeasurer>,
+    include_old: bool,
+    mut stats: Option<&mut FrameStats>,
+) -> Vec<MarkDiff> {
+    let measurer = measurer.map(M::text_measurer);
+    let mut diffs = Vec::new();
+    for id in ids {
+        if let Some(mark) = marks.get_mut(id) {
+            diffs.extend(update_mark(
+                mark,
+                ctx,
+                measurer,
+                include_old,
+                stats.as_deref_mut(),
+            ));
+        }
+    }
+    diffs
+}
+
+/// Below this many dirty marks, [`Scene::update_parallel`] evaluates on the calling thread, since
+/// splitting the work would cost more than it saves.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_MARKS: usize = 256;
+
+/// Evaluates the marks listed in `ids` across threads, returning their diffs in `ids` order.
+#[cfg(feature = "parallel")]
+fn update_marks_parallel(
+    marks: &mut HashMap<MarkId, Mark<Shared>>,
+    ids: &[MarkId],
+    ctx: &EvalCtx<'_, Shared>,
+    measurer: Option<&<Shared as Threading>::TextMeasurer>,
+    include_old: bool,
+    stats: Option<&mut FrameStats>,
+) -> Vec<MarkDiff> {
+    use rayon::prelude::*;
+
+    if ids.len() < PARALLEL_MIN_MARKS {
+        return update_marks(marks, ids, ctx, measurer, include_old, stats);
+    }
+
+    // Move the dirty marks out so the work is proportional to `ids`, not to the scene.
+    let mut selected: Vec<(MarkId, Mark<Shared>)> =
+        ids.iter().filter_map(|id| marks.remove_entry(id)).collect();
+
+    let counting = stats.is_some();
+    // Indexed parallel iterators collect in input order, so diffs stay sorted like `ids`.
+    let results: Vec<(Option<MarkDiff>, FrameStats)> = selected
+        .par_iter_mut()
+        .map(|(_, mark)| {
+            let mut local = FrameStats::default();
+            let diff = update_mark(
+                mark,
+                ctx,
+                measurer.map(Shared::text_measurer),
+                include_old,
+                counting.then_some(&mut local),
+            );
+            (diff, local)
+        })
+        .collect();
+    marks.extend(selected);
+
+    if let Some(stats) = stats {
+        for (_, local) in &results {
+            stats.marks_visited += local.marks_visited;
+            stats.encodings_recomputed += local.encodings_recomputed;
+        }
+    }
+    results.into_iter().filter_map(|(diff, _)| diff).collect()
+}
+
+/// Evaluates one dirty mark, returning its diff (if anything visible changed).
+fn update_mark<M: Threading>(
+    mark: &mut Mark<M>,
+    ctx: &EvalCtx<'_, M>,
+    measurer: Option<&dyn TextMeasurer>,
+    include_old: bool,
+    stats: Option<&mut FrameStats>,
+) -> Option<MarkDiff> {
+    let mut changed_inputs = SmallVec::<[InputRef; 8]>::new();
+
+    mark.last_seen.resize(mark.deps.len(), 0);
+    for (dep, seen) in mark.deps.iter().zip(mark.last_seen.iter_mut()) {
+        let Some(v) = ctx.input_version(*dep) else {
+            continue;
+        };
+        let prev = core::mem::replace(seen, v);
+        if mark.cache.is_some() && v != prev {
+            changed_inputs.push(*dep);
+        }
+    }
+    for dep in mark.pending_inputs.drain(..) {
+        if mark.cache.is_some() && !changed_inputs.contains(&dep) {
+            changed_inputs.push(dep);
+        }
+    }
+
+    if let Some(stats) = stats {
+        stats.marks_visited += 1;
+        stats.encodings_recomputed += if mark.cache.is_none() || mark.force_eval {
+            mark.encodings.computed_count(None)
+        } else {
+            mark.encodings.computed_count(Some(&changed_inputs))
+        };
+    }
+
+    if mark.cache.is_none() {
+        let new = eval_payload(&mark.encodings, ctx, mark.id);
+        let diff = MarkDiff::Enter {
+            id: mark.id,
+            z_index: mark.z_index,
+            parent: mark.parent,
+            kind: mark.kind,
+            new: Box::new(new.clone()),
+            bounds: new.bounds_with(measurer),
+        };
+        mark.cache = Some(new);
+        mark.cached_z_index = mark.z_index;
+        mark.cached_parent = mark.parent;
+        mark.force_eval = false;
+        return Some(diff);
+    }
+
+    if !mark.force_eval
+        && changed_inputs.is_empty()
+        && mark.cached_z_index == mark.z_index
+        && mark.cached_parent == mark.parent
+    {
+        return None;
+    }
+
+    let old = mark.cache.take().expect("checked above");
+    let new = if mark.force_eval {
+        mark.force_eval = false;
+        eval_payload(&mark.encodings, ctx, mark.id)
+    } else {
+        let mut new = old.clone();
+        if !changed_inputs.is_empty() {
+            update_payload_incremental(&mark.encodings, ctx, mark.id, &changed_inputs, &mut new);
+        }
+        new
+    };
+
+    let mut changed = old.changed_channels(&new);
+    if mark.cached_z_index != mark.z_index {
+        changed |= ChannelMask::Z_ORDER;
+    }
+    if mark.cached_parent != mark.parent {
+        changed |= ChannelMask::PARENT;
+    }
+    let diff = (!changed.is_empty()).then(|| {
+        let old_bounds = old.bounds_with(measurer);
+        // Bounds derive from geometry, rect stroke width, (measured) text, and instances only.
+        let bounds_channels = ChannelMask::GEOMETRY
+            | ChannelMask::STROKE
+            | ChannelMask::TEXT
+            | ChannelMask::INSTANCES;
+        let new_bounds = if changed.intersects(bounds_channels) {
+            new.bounds_with(measurer)
+        } else {
+            old_bounds
+        };
+        let instances = match (&old, &new) {
+            (MarkPayload::Instances(a), MarkPayload::Instances(b))
+                if changed.contains(ChannelMask::INSTANCES) =>
+            {
+                Some(Box::new(a.instances.delta(&b.instances)))
+            }
+            _ => None,
+        };
+        MarkDiff::Update {
+            id: mark.id,
+            old_z_index: mark.cached_z_index,
+            new_z_index: mark.z_index,
+            old_parent: mark.cached_parent,
+            new_parent: mark.parent,
+            kind: mark.kind,
+            changed,
+            old: include_old.then(|| Box::new(old)),
+            instances,
+            new: Box::new(new.clone()),
+            old_bounds,
+            new_bounds,
+            damage: union_bounds(old_bounds, new_bounds),
+        }
+    });
+    mark.cache = Some(new);
+    mark.cached_z_index = mark.z_index;
+    mark.cached_parent = mark.parent;
+    diff
+}
+
+fn union_bounds(a: Option<Rect>, b: Option<Rect>) -> Option<Rect> {
+    let a = a?;
+    let b = b?;
+    Some(Rect::new(
+        a.x0.min(b.x0),
+        a.y0.min(b.y0),
+        a.x1.max(b.x1),
+        a.y1.max(b.y1),
+    ))
+}
+
+fn encoding_needs_update<T, M: Threading>(
+    enc: &Encoding<T, M>,
+    changed_inputs: &SmallVec<[InputRef; 8]>,
+) -> bool {
+    match enc {
+        Encoding::Const(_) => false,
+        Encoding::Compute { deps, .. } => deps.iter().any(|d| changed_inputs.contains(d)),
+    }
+}
+
+fn eval_value<T: Clone, M: Threading>(enc: &Encoding<T, M>, ctx: &EvalCtx<'_, M>, id: MarkId) -> T {
+    match enc {
+        Encoding::Const(v) => v.clone(),
+        #[cfg(feature = "debug-deps")]
+        Encoding::Compute { f, .. } if ctx.audit.is_some() => {
+            debug_deps::eval_recorded(ctx.audit.expect("checked above"), enc, f, ctx, id)
+        }
+        Encoding::Compute { f, .. } => (f)(ctx, id),
+    }
+}
+
+fn eval_payload<M: Threading>(
+    encodings: &MarkEncodings<M>,
+    ctx: &EvalCtx<'_, M>,
+    id: MarkId,
+) -> MarkPayload {
+    match encodings {
+        MarkEncodings::Rect(e) => {
+            let e = e.as_ref();
+            let x = eval_value(&e.x, ctx, id);
+            let y = eval_value(&e.y, ctx, id);
+            let w = eval_value(&e.w, ctx, id);
+            let h = eval_value(&e.h, ctx, id);
+            let fill = eval_value(&e.fill, ctx, id);
+
+            let x0 = x.min(x + w);
+            let x1 = x.max(x + w);
+            let y0 = y.min(y + h);
+            let y1 = y.max(y + h);
+
+            MarkPayload::Rect(RectChannels {
+                rect: Rect::new(x0, y0, x1, y1),
+                fill,
+                stroke: eval_value(&e.stroke, ctx, id),
+                stroke_width: eval_value(&e.stroke_width, ctx, id),
+                corner_radii: eval_value(&e.corner_radii, ctx, id),
+                opacity: eval_value(&e.opacity, ctx, id),
+                fill_opacity: eval_value(&e.fill_opacity, ctx, id),
+                stroke_opacity: eval_value(&e.stroke_opacity, ctx, id),
+                blend_mode: eval_value(&e.blend_mode, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+        MarkEncodings::Text(e) => {
+            let e = e.as_ref();
+            MarkPayload::Text(TextChannels {
+                pos: Point::new(eval_value(&e.x, ctx, id), eval_value(&e.y, ctx, id)),
+                text: eval_value(&e.text, ctx, id),
+                font_size: eval_value(&e.font_size, ctx, id),
+                font_family: eval_value(&e.font_family, ctx, id),
+                font_weight: eval_value(&e.font_weight, ctx, id),
+                font_style: eval_value(&e.font_style, ctx, id),
+                line_height: eval_value(&e.line_height, ctx, id),
+                angle: eval_value(&e.angle, ctx, id),
+                anchor: eval_value(&e.anchor, ctx, id),
+                baseline: eval_value(&e.baseline, ctx, id),
+                fill: eval_value(&e.fill, ctx, id),
+                opacity: eval_value(&e.opacity, ctx, id),
+                fill_opacity: eval_value(&e.fill_opacity, ctx, id),
+                blend_mode: eval_value(&e.blend_mode, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+        MarkEncodings::Path(e) => {
+            let e = e.as_ref();
+            let stroke_width = eval_value(&e.stroke_width, ctx, id);
+            let mut stroke_style = eval_value(&e.stroke_style, ctx, id);
+            stroke_style.width = stroke_width;
+            MarkPayload::Path(PathChannels {
+                path: eval_value(&e.path, ctx, id),
+                fill: eval_value(&e.fill, ctx, id),
+                stroke: eval_value(&e.stroke, ctx, id),
+                stroke_width,
+                stroke_style,
+                opacity: eval_value(&e.opacity, ctx, id),
+                fill_opacity: eval_value(&e.fill_opacity, ctx, id),
+                stroke_opacity: eval_value(&e.stroke_opacity, ctx, id),
+                blend_mode: eval_value(&e.blend_mode, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+        MarkEncodings::Group(e) => {
+            let e = e.as_ref();
+            MarkPayload::Group(GroupChannels {
+                transform: eval_value(&e.transform, ctx, id),
+                clip: eval_value(&e.clip, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+        MarkEncodings::Image(e) => {
+            let e = e.as_ref();
+            let x = eval_value(&e.x, ctx, id);
+            let y = eval_value(&e.y, ctx, id);
+            let w = eval_value(&e.w, ctx, id);
+            let h = eval_value(&e.h, ctx, id);
+            MarkPayload::Image(ImageChannels {
+                rect: Rect::new(x, y, x + w, y + h).abs(),
+                image: eval_value(&e.image, ctx, id),
+                quality: eval_value(&e.quality, ctx, id),
+                opacity: eval_value(&e.opacity, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+        MarkEncodings::Instances(e) => {
+            let e = e.as_ref();
+            MarkPayload::Instances(InstancesChannels {
+                instances: eval_value(&e.instances, ctx, id),
+                shape: eval_value(&e.shape, ctx, id),
+                opacity: eval_value(&e.opacity, ctx, id),
+                meta: e.meta.eval(ctx, id),
+            })
+        }
+    }
+}
+
+fn update_payload_incremental<M: Threading>(
+    encodings: &MarkEncodings<M>,
+    ctx: &EvalCtx<'_, M>,
+    id: MarkId,
+    changed_inputs: &SmallVec<[InputRef; 8]>,
+    payload: &mut MarkPayload,
+) {
+    match encodings {
+        MarkEncodings::Rect(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Rect(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            let mut x = p.rect.x0;
+            let mut y = p.rect.y0;
+            let mut w = p.rect.width();
+            let mut h = p.rect.height();
+
+            let mut recompute_rect = false;
+            if encoding_needs_update(&e.x, changed_inputs) {
+                x = eval_value(&e.x, ctx, id);
+                recompute_rect = true;
+            }
+            if encoding_needs_update(&e.y, changed_inputs) {
+                y = eval_value(&e.y, ctx, id);
+                recompute_rect = true;
+            }
+            if encoding_needs_update(&e.w, changed_inputs) {
+                w = eval_value(&e.w, ctx, id);
+                recompute_rect = true;
+            }
+            if encoding_needs_update(&e.h, changed_inputs) {
+                h = eval_value(&e.h, ctx, id);
+                recompute_rect = true;
+            }
+            if encoding_needs_update(&e.fill, changed_inputs) {
+                p.fill = eval_value(&e.fill, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke, changed_inputs) {
+                p.stroke = eval_value(&e.stroke, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke_width, changed_inputs) {
+                p.stroke_width = eval_value(&e.stroke_width, ctx, id);
+            }
+            if encoding_needs_update(&e.corner_radii, changed_inputs) {
+                p.corner_radii = eval_value(&e.corner_radii, ctx, id);
+            }
+            if encoding_needs_update(&e.opacity, changed_inputs) {
+                p.opacity = eval_value(&e.opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.fill_opacity, changed_inputs) {
+                p.fill_opacity = eval_value(&e.fill_opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke_opacity, changed_inputs) {
+                p.stroke_opacity = eval_value(&e.stroke_opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.blend_mode, changed_inputs) {
+                p.blend_mode = eval_value(&e.blend_mode, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+            if recompute_rect {
+                let x0 = x.min(x + w);
+                let x1 = x.max(x + w);
+                let y0 = y.min(y + h);
+                let y1 = y.max(y + h);
+                p.rect = Rect::new(x0, y0, x1, y1);
+            }
+        }
+        MarkEncodings::Text(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Text(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            if encoding_needs_update(&e.x, changed_inputs) {
+                p.pos.x = eval_value(&e.x, ctx, id);
+            }
+            if encoding_needs_update(&e.y, changed_inputs) {
+                p.pos.y = eval_value(&e.y, ctx, id);
+            }
+            if encoding_needs_update(&e.text, changed_inputs) {
+                p.text = eval_value(&e.text, ctx, id);
+            }
+            if encoding_needs_update(&e.font_size, changed_inputs) {
+                p.font_size = eval_value(&e.font_size, ctx, id);
+            }
+            if encoding_needs_update(&e.font_family, changed_inputs) {
+                p.font_family = eval_value(&e.font_family, ctx, id);
+            }
+            if encoding_needs_update(&e.font_weight, changed_inputs) {
+                p.font_weight = eval_value(&e.font_weight, ctx, id);
+            }
+            if encoding_needs_update(&e.font_style, changed_inputs) {
+                p.font_style = eval_value(&e.font_style, ctx, id);
+            }
+            if encoding_needs_update(&e.line_height, changed_inputs) {
+                p.line_height = eval_value(&e.line_height, ctx, id);
+            }
+            if encoding_needs_update(&e.angle, changed_inputs) {
+                p.angle = eval_value(&e.angle, ctx, id);
+            }
+            if encoding_needs_update(&e.anchor, changed_inputs) {
+                p.anchor = eval_value(&e.anchor, ctx, id);
+            }
+            if encoding_needs_update(&e.baseline, changed_inputs) {
+                p.baseline = eval_value(&e.baseline, ctx, id);
+            }
+            if encoding_needs_update(&e.fill, changed_inputs) {
+                p.fill = eval_value(&e.fill, ctx, id);
+            }
+            if encoding_needs_update(&e.opacity, changed_inputs) {
+                p.opacity = eval_value(&e.opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.fill_opacity, changed_inputs) {
+                p.fill_opacity = eval_value(&e.fill_opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.blend_mode, changed_inputs) {
+                p.blend_mode = eval_value(&e.blend_mode, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+        }
+        MarkEncodings::Path(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Path(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            if encoding_needs_update(&e.path, changed_inputs) {
+                p.path = eval_value(&e.path, ctx, id);
+            }
+            if encoding_needs_update(&e.fill, changed_inputs) {
+                p.fill = eval_value(&e.fill, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke, changed_inputs) {
+                p.stroke = eval_value(&e.stroke, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke_width, changed_inputs) {
+                p.stroke_width = eval_value(&e.stroke_width, ctx, id);
+                p.stroke_style.width = p.stroke_width;
+            }
+            if encoding_needs_update(&e.stroke_style, changed_inputs) {
+                p.stroke_style = eval_value(&e.stroke_style, ctx, id);
+                p.stroke_style.width = p.stroke_width;
+            }
+            if encoding_needs_update(&e.opacity, changed_inputs) {
+                p.opacity = eval_value(&e.opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.fill_opacity, changed_inputs) {
+                p.fill_opacity = eval_value(&e.fill_opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.stroke_opacity, changed_inputs) {
+                p.stroke_opacity = eval_value(&e.stroke_opacity, ctx, id);
+            }
+            if encoding_needs_update(&e.blend_mode, changed_inputs) {
+                p.blend_mode = eval_value(&e.blend_mode, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+        }
+        MarkEncodings::Group(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Group(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            if encoding_needs_update(&e.transform, changed_inputs) {
+                p.transform = eval_value(&e.transform, ctx, id);
+            }
+            if encoding_needs_update(&e.clip, changed_inputs) {
+                p.clip = eval_value(&e.clip, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+        }
+        MarkEncodings::Image(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Image(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            if [&e.x, &e.y, &e.w, &e.h]
+                .into_iter()
+                .any(|enc| encoding_needs_update(enc, changed_inputs))
+            {
+                let x = eval_value(&e.x, ctx, id);
+                let y = eval_value(&e.y, ctx, id);
+                let w = eval_value(&e.w, ctx, id);
+                let h = eval_value(&e.h, ctx, id);
+                p.rect = Rect::new(x, y, x + w, y + h).abs();
+            }
+            if encoding_needs_update(&e.image, changed_inputs) {
+                p.image = eval_value(&e.image, ctx, id);
+            }
+            if encoding_needs_update(&e.quality, changed_inputs) {
+                p.quality = eval_value(&e.quality, ctx, id);
+            }
+            if encoding_needs_update(&e.opacity, changed_inputs) {
+                p.opacity = eval_value(&e.opacity, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+        }
+        MarkEncodings::Instances(e) => {
+            let e = e.as_ref();
+            let MarkPayload::Instances(p) = payload else {
+                *payload = eval_payload(encodings, ctx, id);
+                return;
+            };
+            if encoding_needs_update(&e.instances, changed_inputs) {
+                p.instances = eval_value(&e.instances, ctx, id);
+            }
+            if encoding_needs_update(&e.shape, changed_inputs) {
+                p.shape = eval_value(&e.shape, ctx, id);
+            }
+            if encoding_needs_update(&e.opacity, changed_inputs) {
+                p.opacity = eval_value(&e.opacity, ctx, id);
+            }
+            e.meta.update(ctx, id, changed_inputs, &mut p.meta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    #[test]
+    fn enter_update_exit_smoke() {
+        let mut scene = Scene::new();
+        let zoom_id = SignalId(1);
+        scene.insert_signal(zoom_id, 1.0_f32);
+
+        let mark_id = MarkId(7);
+        let mark = Mark::builder(mark_id)
+            .x_compute([InputRef::Signal { signal: zoom_id }], move |ctx, _| {
+                f64::from(ctx.signal::<f32>(zoom_id).unwrap_or(0.0))
+            })
+            .build();
+        let diffs = scene.tick([mark]);
+
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Enter { id, .. }] if *id == mark_id
+        ));
+
+        scene.set_signal(zoom_id, 2.0_f32).unwrap();
+        let diffs = scene.update();
+        let [MarkDiff::Update { id, old, new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        assert_eq!(*id, mark_id);
+        let (MarkPayload::Rect(old), MarkPayload::Rect(new)) = (old.as_deref().unwrap(), &**new)
+        else {
+            panic!("expected rect payloads");
+        };
+        assert_ne!(old.rect.x0, new.rect.x0);
+
+        let diffs = scene.tick(core::iter::empty());
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Exit { id, .. }] if *id == mark_id
+        ));
+    }
+
+    #[cfg(feature = "debug-deps")]
+    #[test]
+    fn dep_checks_name_mark_and_channel() {
+        let mut scene = Scene::new();
+        let a = SignalId(1);
+        let b = SignalId(2);
+        scene.insert_signal(a, 1.0_f64);
+        scene.insert_signal(b, 2.0_f64);
+        let mark = Mark::builder(MarkId(7))
+            // Reads `b` without declaring it.
+            .x_compute([InputRef::Signal { signal: a }], move |ctx, _| {
+                ctx.signal::<f64>(a).unwrap_or(0.0) + ctx.signal::<f64>(b).unwrap_or(0.0)
+            })
+            // Declares `b` without reading it.
+            .w_compute([InputRef::Signal { signal: b }], |_, _| 4.0)
+            .build();
+
+        scene.set_panic_on_undeclared_deps(false);
+        scene.tick([mark]);
+        let violations = scene.take_dep_violations();
+        assert_eq!(
+            violations,
+            [
+                DepViolation {
+                    source: DepSource::Mark(MarkId(7)),
+                    channel: "x",
+                    kind: DepViolationKind::Undeclared(InputRef::Signal { signal: b }),
+                },
+                DepViolation {
+                    source: DepSource::Mark(MarkId(7)),
+                    channel: "w",
+                    kind: DepViolationKind::Unused(InputRef::Signal { signal: b }),
+                },
+            ]
+        );
+        assert_eq!(
+            alloc::format!("{}", violations[0]),
+            "mark MarkId(7) channel `x` read undeclared input Signal { signal: SignalId(2) }"
+        );
+        assert!(scene.take_dep_violations().is_empty());
+
+        // Panicking is the default.
+        scene.set_panic_on_undeclared_deps(true);
+        scene.set_signal(a, 3.0_f64).unwrap();
+        let panicked =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| scene.update())).is_err();
+        assert!(panicked);
+    }
+
+    #[cfg(feature = "debug-deps")]
+    #[test]
+    fn dep_checks_audit_the_real_evaluation() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let mut scene = Scene::new();
+        scene.set_panic_on_undeclared_deps(false);
+        let table_id = TableId(1);
+        let col = ColId(0);
+        let dep = InputRef::TableCol {
+            table: table_id,
+            col,
+        };
+        scene.set_table_row_keys(table_id, Vec::from([1_u64, 2]));
+
+        // Closures run once per evaluation, not again for the audit.
+        let evals = Rc::new(Cell::new(0_u32));
+        let counted = {
+            let evals = evals.clone();
+            Mark::builder(MarkId(1))
+                .w_compute([dep], move |ctx, _| {
+                    evals.set(evals.get() + 1);
+                    ctx.table_row_count(table_id).unwrap_or(0) as f64
+                })
+                .build()
+        };
+        let _ = scene.tick([counted]);
+        assert_eq!(evals.get(), 1);
+        // Row changes bump every column, so a column dep covers a row-count read.
+        assert!(scene.take_dep_violations().is_empty());
+
+        // Row-local deps skip column bumps from patches, so the same read is undeclared there.
+        let row_dep = InputRef::TableRowCol {
+            table: table_id,
+            col,
+        };
+        let _ = scene.tick_table_patch(table_id, TablePatch::new().with_inserts([3]), |id, _| {
+            Mark::builder(id)
+                .w_compute([row_dep], move |ctx, _| {
+                    ctx.table_row_count(table_id).unwrap_or(0) as f64
+                })
+                .build()
+        });
+        assert_eq!(evals.get(), 2);
+        let row_mark = DepSource::Mark(MarkId::for_row(table_id, 3));
+        assert_eq!(
+            scene.take_dep_violations(),
+            [
+                DepViolation {
+                    source: row_mark,
+                    channel: "w",
+                    kind: DepViolationKind::Undeclared(InputRef::Table { table: table_id }),
+                },
+                DepViolation {
+                    source: row_mark,
+                    channel: "w",
+                    kind: DepViolationKind::Unused(row_dep),
+                },
+            ]
+        );
+
+        // Derived signals are audited when they refresh.
+        let other = SignalId(1);
+        scene.insert_signal(other, 2.0_f64);
+        scene.insert_derived_signal(SignalId(2), [dep], move |ctx| {
+            ctx.signal::<f64>(other).unwrap_or(0.0)
+        });
+        let violations = scene.take_dep_violations();
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            alloc::format!("{}", violations[0]),
+            "derived signal SignalId(2) read undeclared input Signal { signal: SignalId(1) }"
+        );
+    }
+
+    #[test]
+    fn retick_with_new_deps_tracks_the_new_inputs() {
+        let mut scene = Scene::new();
+        let a = SignalId(1);
+        let b = SignalId(2);
+        scene.insert_signal(a, 1.0_f64);
+        scene.insert_signal(b, 2.0_f64);
+        let reads = |signals: [SignalId; 2]| {
+            Mark::builder(MarkId(1))
+                .x_compute(
+                    signals.map(|signal| InputRef::Signal { signal }),
+                    move |ctx, _| signals.iter().filter_map(|s| ctx.signal::<f64>(*s)).sum(),
+                )
+                .build()
+        };
+        scene.tick([reads([a, a])]);
+
+        // Same id, different deps: re-evaluated once, then tracked by the new positions.
+        let diffs = scene.tick([reads([b, a])]);
+        assert!(matches!(&diffs[..], [MarkDiff::Update { .. }]));
+        assert!(scene.update().is_empty());
+
+        scene.set_signal(b, 5.0_f64).unwrap();
+        let diffs = scene.update();
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        assert_eq!(new.bounds().map(|r| r.x0), Some(6.0));
+    }
+
+    #[test]
+    fn update_diffs_report_changed_channels() {
+        let mut scene = Scene::new();
+        let color = SignalId(1);
+        let x = SignalId(2);
+        scene.insert_signal(color, Color::BLACK);
+        scene.insert_signal(x, 0.0_f64);
+        let mark = Mark::builder(MarkId(1))
+            .x_compute([InputRef::Signal { signal: x }], move |ctx, _| {
+                ctx.signal::<f64>(x).unwrap_or(0.0)
+            })
+            .w_const(4.0)
+            .h_const(4.0)
+            .fill_compute([InputRef::Signal { signal: color }], move |ctx, _| {
+                ctx.signal::<Color>(color).unwrap_or(Color::BLACK).into()
+            })
+            .build();
+        scene.tick([mark]);
+
+        scene.set_signal(color, Color::WHITE).unwrap();
+        let diffs = scene.update();
+        let [
+            MarkDiff::Update {
+                changed,
+                old,
+                old_bounds,
+                new_bounds,
+                ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single update diff");
+        };
+        assert_eq!(*changed, ChannelMask::FILL);
+        assert!(old.is_some());
+        assert_eq!(old_bounds, new_bounds);
+
+        scene.set_include_old_payloads(false);
+        scene.set_signal(x, 2.0_f64).unwrap();
+        scene.set_signal(color, Color::BLACK).unwrap();
+        let diffs = scene.update();
+        let [
+            MarkDiff::Update {
+                changed,
+                old,
+                new_bounds,
+                ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single update diff");
+        };
+        assert_eq!(*changed, ChannelMask::GEOMETRY | ChannelMask::FILL);
+        assert!(!changed.contains(ChannelMask::STROKE));
+        assert!(old.is_none());
+        assert_eq!(*new_bounds, Some(Rect::new(2.0, 0.0, 6.0, 4.0)));
+
+        // Re-setting a signal to an equal value yields no diff at all.
+        scene.set_signal(color, Color::BLACK).unwrap();
+        assert!(scene.update().is_empty());
+    }
+
+    #[test]
+    fn instance_updates_report_per_instance_delta() {
+        let mut scene = Scene::new();
+        let count = SignalId(1);
+        let shift = SignalId(2);
+        scene.insert_signal(count, 3_u64);
+        scene.insert_signal(shift, 0.0_f64);
+        let mark = Mark::builder(MarkId(1))
+            .instances()
+            .instances_compute(
+                [
+                    InputRef::Signal { signal: count },
+                    InputRef::Signal { signal: shift },
+                ],
+                move |ctx, _| {
+                    let dx = ctx.signal::<f64>(shift).unwrap_or(0.0);
+                    let mut data = InstanceData::new();
+                    for key in 0..ctx.signal::<u64>(count).unwrap_or(0) {
+                        let p = Point::new(key as f64 * 10.0 + dx, 0.0);
+                        data.push(key, p, 2.0, Color::BLACK);
+                    }
+                    data
+                },
+            )
+            .build();
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { new, bounds, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
+        };
+        assert!(matches!(&**new, MarkPayload::Instances(i) if i.instances.len() == 3));
+        assert_eq!(*bounds, Some(Rect::new(-1.0, -1.0, 21.0, 1.0)));
+
+        let delta = |scene: &mut Scene| {
+            let diffs = scene.update();
+            let [
+                MarkDiff::Update {
+                    changed, instances, ..
+                },
+            ] = &diffs[..]
+            else {
+                panic!("expected a single update diff");
+            };
+            assert_eq!(*changed, ChannelMask::INSTANCES);
+            *instances.clone().unwrap()
+        };
+
+        scene.set_signal(count, 4_u64).unwrap();
+        let entered = InstanceDelta {
+            entered: alloc::vec![3],
+            ..InstanceDelta::default()
+        };
+        assert_eq!(delta(&mut scene), entered);
+
+        scene.set_signal(count, 2_u64).unwrap();
+        let exited = InstanceDelta {
+            exited: alloc::vec![2, 3],
+            ..InstanceDelta::default()
+        };
+        assert_eq!(delta(&mut scene), exited);
+
+        scene.set_signal(shift, 5.0_f64).unwrap();
+        let updated = InstanceDelta {
+            updated: alloc::vec![0, 1],
+            ..InstanceDelta::default()
+        };
+        assert_eq!(delta(&mut scene), updated);
+    }
+
+    #[test]
+    fn only_recomputes_touched_encodings() {
+        let mut scene = Scene::new();
+        let a = SignalId(1);
+        let b = SignalId(2);
+        scene.insert_signal(a, 1.0_f32);
+        scene.insert_signal(b, 10.0_f32);
+
+        let mark_id = MarkId(1);
+        let mark = Mark::builder(mark_id)
+            .x_compute([InputRef::Signal { signal: a }], move |ctx, _| {
+                f64::from(ctx.signal::<f32>(a).unwrap_or(0.0))
+            })
+            .y_compute([InputRef::Signal { signal: b }], move |ctx, _| {
+                f64::from(ctx.signal::<f32>(b).unwrap_or(0.0))
+            })
+            .build();
+        let _ = scene.tick([mark]);
+
+        // Change `a` only; `y` should remain the same.
+        scene.set_signal(a, 2.0_f32).unwrap();
+        let diffs = scene.update();
+
+        let [MarkDiff::Update { old, new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        let (MarkPayload::Rect(old), MarkPayload::Rect(new)) = (old.as_deref().unwrap(), &**new)
+        else {
+            panic!("expected rect payloads");
+        };
+        assert_ne!(old.rect.x0, new.rect.x0);
+        assert_eq!(old.rect.y0, new.rect.y0);
+    }
+
+    #[test]
+    fn table_row_reconciliation_exit_enter() {
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        scene.set_table_row_keys(table_id, Vec::from([10_u64, 11_u64]));
+
+        let diffs = scene.tick_table_rows(table_id, |id, row_key, _row| {
+            Mark::builder(id).x_const(row_key as f64).build()
+        });
+        assert!(diffs.iter().any(|d| matches!(d, MarkDiff::Enter { .. })));
+
+        // Remove one row key and add a new one: expect one exit and one enter.
+        scene.set_table_row_keys(table_id, Vec::from([11_u64, 12_u64]));
+        let diffs = scene.tick_table_rows(table_id, |id, row_key, _row| {
+            Mark::builder(id).x_const(row_key as f64).build()
+        });
+
+        let enters = diffs
+            .iter()
+            .filter(|d| matches!(d, MarkDiff::Enter { .. }))
+            .count();
+        let exits = diffs
+            .iter()
+            .filter(|d| matches!(d, MarkDiff::Exit { .. }))
+            .count();
+        assert_eq!(enters, 1);
+        assert_eq!(exits, 1);
+    }
+
+    #[test]
+    fn text_updates_and_bounds_unknown() {
+        let mut scene = Scene::new();
+        let sig = SignalId(1);
+        scene.insert_signal(sig, 1_u32);
+
+        let mark_id = MarkId(1);
+        let mark = Mark::builder(mark_id)
+            .text()
+            .x_const(0.0)
+            .y_const(0.0)
+            .text_compute([InputRef::Signal { signal: sig }], move |ctx, _| {
+                let v = ctx.signal::<u32>(sig).unwrap_or(0);
+                alloc::format!("v={v}")
+            })
+            .build();
+
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { kind, bounds, .. }] = &diffs[..] else {
+            panic!("expected enter");
+        };
+        assert_eq!(*kind, MarkKind::Text);
+        assert_eq!(*bounds, None);
+
+        scene.set_signal(sig, 2_u32).unwrap();
+        let diffs = scene.update();
+        let [MarkDiff::Update { old, new, .. }] = &diffs[..] else {
+            panic!("expected update");
+        };
+        let (MarkPayload::Text(old), MarkPayload::Text(new)) = (old.as_deref().unwrap(), &**new)
+        else {
+            panic!("expected text payloads");
+        };
+        assert_ne!(old.text, new.text);
+    }
+
+    #[test]
+    fn path_enter_has_bounds() {
+        let mut scene = Scene::new();
+        let mark_id = MarkId(1);
+
+        let mut triangle = BezPath::new();
+        triangle.move_to((0.0, 0.0));
+        triangle.line_to((10.0, 0.0));
+        triangle.line_to((5.0, 10.0));
+        triangle.close_path();
+
+        let mark = Mark::builder(mark_id).path().path_const(triangle).build();
+        let diffs = scene.tick([mark]);
 
-    /// Build and tick a complete mark set derived from a table's row keys.
-    ///
-    /// This is a v1 convenience for "one mark per row" patterns. It derives stable
-    /// `MarkId`s via [`MarkId::for_row`], reconciles the full set, and evaluates.
-    ///
-    /// If you need multiple marks per row or nested mark hierarchies, build marks
-    /// explicitly and use [`Scene::tick`].
-    pub fn tick_table_rows<F>(&mut self, table: TableId, mut build: F) -> Vec<MarkDiff>
-    where
-        F: FnMut(MarkId, u64, usize) -> Mark,
-    {
-        let Some(keys) = self.tables.get(&table).map(|t| t.row_keys.clone()) else {
-            return self.tick(core::iter::empty());
+        let [MarkDiff::Enter { kind, bounds, .. }] = &diffs[..] else {
+            panic!("expected enter");
         };
-
-        self.tick(
-            keys.into_iter()
-                .enumerate()
-                .map(|(i, k)| build(MarkId::for_row(table, k), k, i)),
-        )
+        assert_eq!(*kind, MarkKind::Path);
+        assert!(bounds.is_some());
     }
 
-    /// Evaluate incremental updates and produce mark diffs.
-    ///
-    /// Use this when the mark set is stable and only input versions changed (tables/signals).
-    pub fn update(&mut self) -> Vec<MarkDiff> {
-        let ctx = EvalCtx {
-            tables: &self.tables,
-            signals: &self.signals,
-        };
-        let mut diffs = Vec::new();
+    #[derive(Debug)]
+    struct TypedCols {
+        names: Vec<String>,
+        groups: Vec<u32>,
+        group_labels: Vec<String>,
+        times: Vec<i64>,
+        flags: Vec<bool>,
+    }
 
-        for id in self.pending_removals.drain(..) {
-            let removed = self.marks.remove(&id);
-            let old = removed.as_ref().and_then(|m| m.cache.clone());
-            let bounds = old.as_ref().and_then(MarkPayload::bounds);
-            diffs.push(MarkDiff::Exit {
-                id,
-                z_index: removed.as_ref().map_or(0, |m| m.z_index),
-                kind: removed.as_ref().map_or(MarkKind::Rect, |m| m.kind),
-                old: old.map(Box::new),
-                bounds,
-            });
+    impl TableData for TypedCols {
+        fn row_count(&self) -> usize {
+            self.names.len()
         }
 
-        for mark in self.marks.values_mut() {
-            let mut changed_inputs = SmallVec::<[InputRef; 8]>::new();
+        fn f64(&self, _row: usize, _col: ColId) -> Option<f64> {
+            None
+        }
 
-            for dep in mark.deps.iter().copied() {
-                let v = match dep {
-                    InputRef::Table { table } => ctx.table_version(table),
-                    InputRef::TableCol { table, .. } => ctx.table_version(table),
-                    InputRef::Signal { signal } => ctx.signal_version(signal),
-                };
-                let Some(v) = v else { continue };
-                let prev = mark.last_seen.insert(dep, v).unwrap_or(0);
-                if mark.cache.is_some() && v != prev {
-                    changed_inputs.push(dep);
-                }
+        fn column_type(&self, col: ColId) -> Option<ColumnType> {
+            match col {
+                ColId(0) => Some(ColumnType::Str),
+                ColId(1) => Some(ColumnType::Category),
+                ColId(2) => Some(ColumnType::Timestamp),
+                ColId(3) => Some(ColumnType::Bool),
+                _ => None,
             }
+        }
 
-            if mark.cache.is_none() {
-                let new = eval_payload(&mark.encodings, &ctx, mark.id);
-                diffs.push(MarkDiff::Enter {
-                    id: mark.id,
-                    z_index: mark.z_index,
-                    kind: mark.kind,
-                    new: Box::new(new.clone()),
-                    bounds: new.bounds(),
-                });
-                mark.cache = Some(new);
-                mark.cached_z_index = mark.z_index;
-                mark.force_eval = false;
-                continue;
+        fn str(&self, row: usize, col: ColId) -> Option<&str> {
+            match col {
+                ColId(0) => self.names.get(row).map(String::as_str),
+                _ => None,
             }
+        }
 
-            if mark.force_eval {
-                let old_z_index = mark.cached_z_index;
-                let new_z_index = mark.z_index;
-                let old = mark.cache.as_ref().expect("checked above").clone();
-                let new = eval_payload(&mark.encodings, &ctx, mark.id);
-
-                if old != new || old_z_index != new_z_index {
-                    let old_bounds = old.bounds();
-                    let new_bounds = new.bounds();
-                    let damage = union_bounds(old_bounds, new_bounds);
-                    diffs.push(MarkDiff::Update {
-                        id: mark.id,
-                        old_z_index,
-                        new_z_index,
-                        kind: mark.kind,
-                        old: Box::new(old.clone()),
-                        new: Box::new(new.clone()),
-                        old_bounds,
-                        new_bounds,
-                        damage,
-                    });
-                }
+        fn category(&self, row: usize, col: ColId) -> Option<u32> {
+            match col {
+                ColId(1) => self.groups.get(row).copied(),
+                _ => None,
+            }
+        }
 
-                mark.cache = Some(new);
-                mark.cached_z_index = mark.z_index;
-                mark.force_eval = false;
-                continue;
+        fn category_label(&self, col: ColId, code: u32) -> Option<&str> {
+            match col {
+                ColId(1) => self.group_labels.get(code as usize).map(String::as_str),
+                _ => None,
             }
+        }
 
-            if changed_inputs.is_empty() {
-                if mark.cached_z_index != mark.z_index {
-                    let old = mark.cache.as_ref().expect("checked above").clone();
-                    let bounds = old.bounds();
-                    diffs.push(MarkDiff::Update {
-                        id: mark.id,
-                        old_z_index: mark.cached_z_index,
-                        new_z_index: mark.z_index,
-                        kind: mark.kind,
-                        old: Box::new(old.clone()),
-                        new: Box::new(old.clone()),
-                        old_bounds: bounds,
-                        new_bounds: bounds,
-                        damage: bounds,
-                    });
-                    mark.cached_z_index = mark.z_index;
-                }
-                continue;
+        fn timestamp(&self, row: usize, col: ColId) -> Option<i64> {
+            match col {
+                ColId(2) => self.times.get(row).copied(),
+                _ => None,
             }
+        }
 
-            let old_z_index = mark.cached_z_index;
-            let new_z_index = mark.z_index;
-            let old = mark.cache.as_ref().expect("checked above").clone();
-            let mut new = old.clone();
-            update_payload_incremental(&mark.encodings, &ctx, mark.id, &changed_inputs, &mut new);
-
-            if old != new || old_z_index != new_z_index {
-                let old_bounds = old.bounds();
-                let new_bounds = new.bounds();
-                let damage = union_bounds(old_bounds, new_bounds);
-                diffs.push(MarkDiff::Update {
-                    id: mark.id,
-                    old_z_index,
-                    new_z_index,
-                    kind: mark.kind,
-                    old: Box::new(old.clone()),
-                    new: Box::new(new.clone()),
-                    old_bounds,
-                    new_bounds,
-                    damage,
-                });
+        fn bool(&self, row: usize, col: ColId) -> Option<bool> {
+            match col {
+                ColId(3) => self.flags.get(row).copied(),
+                _ => None,
             }
-            mark.cache = Some(new);
-            mark.cached_z_index = mark.z_index;
         }
+    }
 
-        diffs
+    #[test]
+    fn typed_columns_are_readable_from_encodings() {
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let mut table = Table::new(table_id);
+        table.row_keys = Vec::from([1_u64, 2_u64]);
+        table.data = Some(Box::new(TypedCols {
+            names: Vec::from(["alpha".into(), "beta".into()]),
+            groups: Vec::from([1, 0]),
+            group_labels: Vec::from(["low".into(), "high".into()]),
+            times: Vec::from([1_700_000_000, 1_700_000_060]),
+            flags: Vec::from([true, false]),
+        }));
+        scene.insert_table(table);
+
+        let diffs = scene.tick_table_rows(table_id, |id, _key, row| {
+            Mark::builder(id)
+                .text()
+                .text_compute(
+                    [
+                        InputRef::TableCol {
+                            table: table_id,
+                            col: ColId(0),
+                        },
+                        InputRef::TableCol {
+                            table: table_id,
+                            col: ColId(1),
+                        },
+                    ],
+                    move |ctx, _| {
+                        let name = ctx.table_str(table_id, row, ColId(0)).unwrap_or("");
+                        let group = ctx.table_str(table_id, row, ColId(1)).unwrap_or("");
+                        alloc::format!("{name}/{group}")
+                    },
+                )
+                .build()
+        });
+        let mut texts: Vec<String> = diffs
+            .iter()
+            .filter_map(|d| match d {
+                MarkDiff::Enter { new, .. } => match &**new {
+                    MarkPayload::Text(t) => Some(t.text.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        texts.sort();
+        assert_eq!(texts, ["alpha/high", "beta/low"]);
+
+        let ctx = EvalCtx::new(&scene.tables, &scene.signals);
+        assert_eq!(
+            ctx.table_column_type(table_id, ColId(1)),
+            Some(ColumnType::Category)
+        );
+        assert_eq!(ctx.table_category(table_id, 0, ColId(1)), Some(1));
+        assert_eq!(ctx.table_category_label(table_id, ColId(1), 0), Some("low"));
+        assert_eq!(
+            ctx.table_timestamp(table_id, 1, ColId(2)),
+            Some(1_700_000_060)
+        );
+        assert_eq!(ctx.table_bool(table_id, 0, ColId(3)), Some(true));
+        assert_eq!(ctx.table_f64(table_id, 0, ColId(0)), None);
     }
-}
 
-fn union_bounds(a: Option<Rect>, b: Option<Rect>) -> Option<Rect> {
-    let a = a?;
-    let b = b?;
-    Some(Rect::new(
-        a.x0.min(b.x0),
-        a.y0.min(b.y0),
-        a.x1.max(b.x1),
-        a.y1.max(b.y1),
-    ))
-}
+    #[derive(Debug)]
+    struct SliceCol(Vec<f64>);
 
-impl Default for Scene {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    impl TableData for SliceCol {
+        fn row_count(&self) -> usize {
+            self.0.len()
+        }
 
-fn encoding_needs_update<T>(enc: &Encoding<T>, changed_inputs: &SmallVec<[InputRef; 8]>) -> bool {
-    match enc {
-        Encoding::Const(_) => false,
-        Encoding::Compute { deps, .. } => deps.iter().any(|d| changed_inputs.contains(d)),
+        fn f64(&self, row: usize, _col: ColId) -> Option<f64> {
+            self.0.get(row).copied()
+        }
+
+        fn f64_slice(&self, col: ColId) -> Option<&[f64]> {
+            (col == ColId(0)).then_some(self.0.as_slice())
+        }
     }
-}
 
-fn eval_value<T: Clone>(enc: &Encoding<T>, ctx: &EvalCtx<'_>, id: MarkId) -> T {
-    match enc {
-        Encoding::Const(v) => v.clone(),
-        Encoding::Compute { f, .. } => (f)(ctx, id),
+    #[test]
+    fn f64_column_prefers_slice_and_falls_back_to_cells() {
+        let data = SliceCol(Vec::from([1.0, 2.0, 3.0]));
+
+        let slice = F64Column::new(&data, ColId(0));
+        assert!(matches!(slice, F64Column::Slice(_)));
+        assert_eq!(slice.get(1), Some(2.0));
+        assert_eq!(slice.get(3), None);
+
+        let cells = F64Column::new(&data, ColId(1));
+        assert!(cells.as_slice().is_none());
+        assert_eq!(cells.get(2), Some(3.0));
     }
-}
 
-fn eval_payload(encodings: &MarkEncodings, ctx: &EvalCtx<'_>, id: MarkId) -> MarkPayload {
-    match encodings {
-        MarkEncodings::Rect(e) => {
-            let e = e.as_ref();
-            let x = eval_value(&e.x, ctx, id);
-            let y = eval_value(&e.y, ctx, id);
-            let w = eval_value(&e.w, ctx, id);
-            let h = eval_value(&e.h, ctx, id);
-            let fill = eval_value(&e.fill, ctx, id);
+    #[test]
+    fn column_change_only_recomputes_dependent_encodings() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
 
-            let x0 = x.min(x + w);
-            let x1 = x.max(x + w);
-            let y0 = y.min(y + h);
-            let y1 = y.max(y + h);
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let height_col = ColId(0);
+        let color_col = ColId(1);
+        scene.set_table_row_keys(table_id, Vec::from([1_u64, 2_u64]));
+
+        let geometry_evals = Rc::new(Cell::new(0_u32));
+        let fill_evals = Rc::new(Cell::new(0_u32));
+        let build = |id, _key, _row| {
+            let geometry_evals = geometry_evals.clone();
+            let fill_evals = fill_evals.clone();
+            Mark::builder(id)
+                .h_compute(
+                    [InputRef::TableCol {
+                        table: table_id,
+                        col: height_col,
+                    }],
+                    move |_, _| {
+                        geometry_evals.set(geometry_evals.get() + 1);
+                        10.0
+                    },
+                )
+                .fill_compute(
+                    [InputRef::TableCol {
+                        table: table_id,
+                        col: color_col,
+                    }],
+                    move |ctx, _| {
+                        fill_evals.set(fill_evals.get() + 1);
+                        let v = ctx.table_column_version(table_id, color_col).unwrap_or(0);
+                        Brush::Solid(Color::from_rgba8(0, 0, u8::try_from(v).unwrap_or(255), 255))
+                    },
+                )
+                .build()
+        };
+        let _ = scene.tick_table_rows(table_id, build);
+        assert_eq!(geometry_evals.get(), 2);
+        assert_eq!(fill_evals.get(), 2);
+
+        let table_version = scene.tables[&table_id].version;
+        assert!(scene.set_table_column_changed(table_id, color_col));
+        assert_ne!(scene.tables[&table_id].version, table_version);
+        assert_eq!(
+            scene.tables[&table_id].column_version(height_col),
+            table_version
+        );
 
-            MarkPayload::Rect(RectChannels {
-                rect: Rect::new(x0, y0, x1, y1),
-                fill,
-            })
-        }
-        MarkEncodings::Text(e) => {
-            let e = e.as_ref();
-            MarkPayload::Text(TextChannels {
-                pos: Point::new(eval_value(&e.x, ctx, id), eval_value(&e.y, ctx, id)),
-                text: eval_value(&e.text, ctx, id),
-                font_size: eval_value(&e.font_size, ctx, id),
-                angle: eval_value(&e.angle, ctx, id),
-                anchor: eval_value(&e.anchor, ctx, id),
-                baseline: eval_value(&e.baseline, ctx, id),
-                fill: eval_value(&e.fill, ctx, id),
-            })
-        }
-        MarkEncodings::Path(e) => {
-            let e = e.as_ref();
-            MarkPayload::Path(PathChannels {
-                path: eval_value(&e.path, ctx, id),
-                fill: eval_value(&e.fill, ctx, id),
-                stroke: eval_value(&e.stroke, ctx, id),
-                stroke_width: eval_value(&e.stroke_width, ctx, id),
-            })
-        }
+        let diffs = scene.update();
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(geometry_evals.get(), 2);
+        assert_eq!(fill_evals.get(), 4);
+
+        // A whole-table change invalidates every column again.
+        scene.set_table_row_keys(table_id, Vec::from([1_u64, 2_u64]));
+        let _ = scene.update();
+        assert_eq!(geometry_evals.get(), 4);
+        assert_eq!(fill_evals.get(), 6);
     }
-}
 
-fn update_payload_incremental(
-    encodings: &MarkEncodings,
-    ctx: &EvalCtx<'_>,
-    id: MarkId,
-    changed_inputs: &SmallVec<[InputRef; 8]>,
-    payload: &mut MarkPayload,
-) {
-    match encodings {
-        MarkEncodings::Rect(e) => {
-            let e = e.as_ref();
-            let MarkPayload::Rect(p) = payload else {
-                *payload = eval_payload(encodings, ctx, id);
-                return;
-            };
-            let mut x = p.rect.x0;
-            let mut y = p.rect.y0;
-            let mut w = p.rect.width();
-            let mut h = p.rect.height();
+    #[test]
+    fn table_patch_only_touches_patched_rows() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
 
-            let mut recompute_rect = false;
-            if encoding_needs_update(&e.x, changed_inputs) {
-                x = eval_value(&e.x, ctx, id);
-                recompute_rect = true;
-            }
-            if encoding_needs_update(&e.y, changed_inputs) {
-                y = eval_value(&e.y, ctx, id);
-                recompute_rect = true;
-            }
-            if encoding_needs_update(&e.w, changed_inputs) {
-                w = eval_value(&e.w, ctx, id);
-                recompute_rect = true;
-            }
-            if encoding_needs_update(&e.h, changed_inputs) {
-                h = eval_value(&e.h, ctx, id);
-                recompute_rect = true;
-            }
-            if encoding_needs_update(&e.fill, changed_inputs) {
-                p.fill = eval_value(&e.fill, ctx, id);
-            }
-            if recompute_rect {
-                let x0 = x.min(x + w);
-                let x1 = x.max(x + w);
-                let y0 = y.min(y + h);
-                let y1 = y.max(y + h);
-                p.rect = Rect::new(x0, y0, x1, y1);
-            }
-        }
-        MarkEncodings::Text(e) => {
-            let e = e.as_ref();
-            let MarkPayload::Text(p) = payload else {
-                *payload = eval_payload(encodings, ctx, id);
-                return;
-            };
-            if encoding_needs_update(&e.x, changed_inputs) {
-                p.pos.x = eval_value(&e.x, ctx, id);
-            }
-            if encoding_needs_update(&e.y, changed_inputs) {
-                p.pos.y = eval_value(&e.y, ctx, id);
-            }
-            if encoding_needs_update(&e.text, changed_inputs) {
-                p.text = eval_value(&e.text, ctx, id);
-            }
-            if encoding_needs_update(&e.font_size, changed_inputs) {
-                p.font_size = eval_value(&e.font_size, ctx, id);
-            }
-            if encoding_needs_update(&e.angle, changed_inputs) {
-                p.angle = eval_value(&e.angle, ctx, id);
-            }
-            if encoding_needs_update(&e.anchor, changed_inputs) {
-                p.anchor = eval_value(&e.anchor, ctx, id);
-            }
-            if encoding_needs_update(&e.baseline, changed_inputs) {
-                p.baseline = eval_value(&e.baseline, ctx, id);
-            }
-            if encoding_needs_update(&e.fill, changed_inputs) {
-                p.fill = eval_value(&e.fill, ctx, id);
-            }
-        }
-        MarkEncodings::Path(e) => {
-            let e = e.as_ref();
-            let MarkPayload::Path(p) = payload else {
-                *payload = eval_payload(encodings, ctx, id);
-                return;
-            };
-            if encoding_needs_update(&e.path, changed_inputs) {
-                p.path = eval_value(&e.path, ctx, id);
-            }
-            if encoding_needs_update(&e.fill, changed_inputs) {
-                p.fill = eval_value(&e.fill, ctx, id);
-            }
-            if encoding_needs_update(&e.stroke, changed_inputs) {
-                p.stroke = eval_value(&e.stroke, ctx, id);
-            }
-            if encoding_needs_update(&e.stroke_width, changed_inputs) {
-                p.stroke_width = eval_value(&e.stroke_width, ctx, id);
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let col = ColId(0);
+        let mut table = Table::new(table_id);
+        table.row_keys = Vec::from([1_u64, 2, 3, 4]);
+        table.data = Some(Box::new(SliceCol(Vec::from([1.0, 2.0, 3.0, 4.0]))));
+        scene.insert_table(table);
+
+        let evals = Rc::new(Cell::new(0_u32));
+        let build = {
+            let evals = evals.clone();
+            move |id, key| {
+                let evals = evals.clone();
+                Mark::builder(id)
+                    .x_compute(
+                        [InputRef::TableRowCol {
+                            table: table_id,
+                            col,
+                        }],
+                        move |ctx, _| {
+                            evals.set(evals.get() + 1);
+                            ctx.table_f64_by_key(table_id, key, col).unwrap_or(0.0)
+                        },
+                    )
+                    .build()
             }
+        };
+        let _ = scene.tick_table_rows(table_id, |id, key, _row| build(id, key));
+        assert_eq!(evals.get(), 4);
+
+        // Append one row.
+        let patch = TablePatch::new()
+            .with_inserts([5])
+            .with_data(Box::new(SliceCol(Vec::from([1.0, 2.0, 3.0, 4.0, 5.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, build.clone());
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Enter { id, .. }] if *id == MarkId::for_row(table_id, 5)
+        ));
+        assert_eq!(evals.get(), 5);
+
+        // Update one row in place.
+        let patch = TablePatch::new()
+            .with_updates([2])
+            .with_changed_columns([col])
+            .with_data(Box::new(SliceCol(Vec::from([1.0, 20.0, 3.0, 4.0, 5.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, build.clone());
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Update { id, .. }] if *id == MarkId::for_row(table_id, 2)
+        ));
+        assert_eq!(evals.get(), 6);
+
+        // Slide the window: drop the oldest row and append a new one. Surviving rows shift but
+        // are neither rebuilt nor re-evaluated.
+        let patch = TablePatch::new()
+            .with_deletes([1])
+            .with_inserts([6])
+            .with_data(Box::new(SliceCol(Vec::from([20.0, 3.0, 4.0, 5.0, 6.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, build.clone());
+        assert!(matches!(
+            &diffs[..],
+            [MarkDiff::Exit { id: a, .. }, MarkDiff::Enter { id: b, .. }]
+                if *a == MarkId::for_row(table_id, 1) && *b == MarkId::for_row(table_id, 6)
+        ));
+        assert_eq!(evals.get(), 7);
+        assert_eq!(scene.tables[&table_id].row_keys(), [2, 3, 4, 5, 6]);
+        assert_eq!(scene.tables[&table_id].row_index(4), Some(2));
+
+        // A shifted row resolves its new index from its key.
+        let patch = TablePatch::new()
+            .with_updates([4])
+            .with_changed_columns([col])
+            .with_data(Box::new(SliceCol(Vec::from([20.0, 3.0, 40.0, 5.0, 6.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, build);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(evals.get(), 8);
+        let Some(MarkPayload::Rect(r)) = &scene.marks[&MarkId::for_row(table_id, 4)].cache else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.rect.x0, 40.0);
+    }
+
+    #[test]
+    fn table_patch_refreshes_whole_column_dependents() {
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let col = ColId(0);
+        let mut table = Table::new(table_id);
+        table.row_keys = Vec::from([1_u64, 2]);
+        table.data = Some(Box::new(SliceCol(Vec::from([1.0, 2.0]))));
+        scene.insert_table(table);
+
+        let sum = Mark::builder(MarkId(1))
+            .w_compute(
+                [InputRef::TableCol {
+                    table: table_id,
+                    col,
+                }],
+                move |ctx, _| {
+                    ctx.table_f64_slice(table_id, col)
+                        .map_or(0.0, |s| s.iter().sum())
+                },
+            )
+            .build();
+        let _ = scene.tick([sum]);
+        let width = |scene: &Scene| scene.marks[&MarkId(1)].cache.as_ref().unwrap().bounds();
+
+        let patch = TablePatch::new()
+            .with_inserts([3, 4, 5])
+            .with_data(Box::new(SliceCol(Vec::from([1.0, 2.0, 3.0, 4.0, 5.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, |id, _| Mark::new(id));
+        assert_eq!(diffs.len(), 4);
+        assert_eq!(width(&scene).map(|r| r.x1), Some(15.0));
+
+        // Updates bump only the changed columns.
+        let other = ColId(1);
+        let patch = TablePatch::new()
+            .with_updates([2])
+            .with_changed_columns([other]);
+        let _ = scene.tick_table_patch(table_id, patch, |id, _| Mark::new(id));
+        assert_eq!(scene.tables[&table_id].column_version(col), 2);
+
+        let patch = TablePatch::new()
+            .with_updates([2])
+            .with_changed_columns([col])
+            .with_data(Box::new(SliceCol(Vec::from([1.0, 12.0, 3.0, 4.0, 5.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, |id, _| Mark::new(id));
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(width(&scene).map(|r| r.x1), Some(25.0));
+    }
+
+    #[test]
+    fn table_patch_refreshes_per_row_column_deps() {
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let col = ColId(0);
+        let mut table = Table::new(table_id);
+        table.set_row_keys(Vec::from([1_u64, 2]));
+        table.data = Some(Box::new(SliceCol(Vec::from([1.0, 2.0]))));
+        scene.insert_table(table);
+
+        // Each row's width is its value normalized by the column max: a row-local read plus a
+        // whole-column read.
+        let build = move |id, key| {
+            Mark::builder(id)
+                .w_compute(
+                    [
+                        InputRef::TableRowCol {
+                            table: table_id,
+                            col,
+                        },
+                        InputRef::TableCol {
+                            table: table_id,
+                            col,
+                        },
+                    ],
+                    move |ctx, _| {
+                        let max = ctx
+                            .table_f64_slice(table_id, col)
+                            .map_or(1.0, |s| s.iter().copied().fold(0.0, f64::max));
+                        ctx.table_f64_by_key(table_id, key, col).unwrap_or(0.0) / max
+                    },
+                )
+                .build()
+        };
+        let _ = scene.tick_table_rows(table_id, |id, key, _row| build(id, key));
+        let width = |scene: &Scene, key| {
+            scene.marks[&MarkId::for_row(table_id, key)]
+                .cache
+                .as_ref()
+                .and_then(MarkPayload::bounds)
+                .map(|r| r.width())
+        };
+        assert_eq!(width(&scene, 1), Some(0.5));
+
+        // A new maximum re-evaluates the untouched row through its column dep.
+        let patch = TablePatch::new()
+            .with_inserts([3])
+            .with_data(Box::new(SliceCol(Vec::from([1.0, 2.0, 4.0]))));
+        let diffs = scene.tick_table_patch(table_id, patch, build);
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(width(&scene, 1), Some(0.25));
+        assert_eq!(width(&scene, 3), Some(1.0));
+    }
+
+    #[test]
+    fn set_signal_type_mismatch_does_not_bump_version() {
+        let mut scene = Scene::new();
+        let id = SignalId(1);
+
+        scene.insert_signal(id, 1.0_f32);
+
+        let before_version = scene.signals.get(&id).unwrap().version();
+
+        // Wrong type: should error and should NOT bump version.
+        let err = scene.set_signal::<u32>(id, 123_u32).unwrap_err();
+        assert_eq!(err, SignalAccessError::TypeMismatch);
+
+        let after_version = scene.signals.get(&id).unwrap().version();
+        assert_eq!(before_version, after_version);
+
+        // Also assert that the original value is unchanged.
+        let sig_any = scene.signals.get(&id).unwrap();
+        let typed = sig_any.as_any().downcast_ref::<Signal<f32>>().unwrap();
+        assert_eq!(typed.value, 1.0_f32);
+    }
+
+    #[test]
+    fn signal_change_only_visits_dependents() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let mut scene = Scene::new();
+        let a = SignalId(1);
+        let b = SignalId(2);
+        scene.insert_signal(a, 1.0_f64);
+        scene.insert_signal(b, 2.0_f64);
+
+        let evals = Rc::new(Cell::new(0_u32));
+        for i in 0..100_u64 {
+            let signal = if i == 0 { a } else { b };
+            let evals = evals.clone();
+            scene.upsert_mark(
+                Mark::builder(MarkId(i))
+                    .x_compute([InputRef::Signal { signal }], move |ctx, _| {
+                        evals.set(evals.get() + 1);
+                        ctx.signal::<f64>(signal).unwrap_or(0.0)
+                    })
+                    .build(),
+            );
         }
+        assert_eq!(scene.dependents(InputRef::Signal { signal: a }).count(), 1);
+        assert_eq!(scene.pending_update_count(), 100);
+        assert_eq!(scene.update().len(), 100);
+        assert_eq!(evals.get(), 100);
+        assert_eq!(scene.pending_update_count(), 0);
+
+        // Nothing changed: nothing is visited.
+        assert!(scene.update().is_empty());
+        assert_eq!(evals.get(), 100);
+
+        scene.set_signal(a, 5.0_f64).unwrap();
+        let diffs = scene.update();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].id(), MarkId(0));
+        assert_eq!(evals.get(), 101);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    extern crate std;
+    #[test]
+    fn budgeted_update_evaluates_high_priority_first() {
+        let mut scene = Scene::new();
+        for i in 0..4_u64 {
+            let priority = if i == 3 { 10 } else { 0 };
+            scene.upsert_mark(
+                Mark::builder(MarkId(i))
+                    .x_const(i as f64)
+                    .priority(priority)
+                    .build(),
+            );
+        }
 
-    use super::*;
+        let diffs = scene.update_budgeted(2);
+        let ids: Vec<MarkId> = diffs.iter().map(MarkDiff::id).collect();
+        assert_eq!(ids, Vec::from([MarkId(3), MarkId(0)]));
+        assert_eq!(scene.pending_update_count(), 2);
+
+        let diffs = scene.update_budgeted(2);
+        let mut ids: Vec<MarkId> = diffs.iter().map(MarkDiff::id).collect();
+        ids.sort();
+        assert_eq!(ids, Vec::from([MarkId(1), MarkId(2)]));
+        assert_eq!(scene.pending_update_count(), 0);
+        assert!(scene.update_budgeted(2).is_empty());
+    }
 
     #[test]
-    fn enter_update_exit_smoke() {
+    fn update_with_stats_counts_work() {
+        use core::cell::Cell;
+
         let mut scene = Scene::new();
-        let zoom_id = SignalId(1);
-        scene.insert_signal(zoom_id, 1.0_f32);
+        let a = SignalId(1);
+        let table_id = TableId(1);
+        scene.insert_signal(a, 1.0_f64);
+        scene.set_table_row_keys(table_id, Vec::from([1_u64, 2_u64]));
+
+        let build = |id, _key, _row| {
+            Mark::builder(id)
+                .x_compute([InputRef::Signal { signal: a }], move |ctx, _| {
+                    ctx.signal::<f64>(a).unwrap_or(0.0)
+                })
+                .w_compute([InputRef::Table { table: table_id }], |_, _| 1.0)
+                .h_const(1.0)
+                .build()
+        };
+        let _ = scene.tick_table_rows(table_id, build);
 
-        let mark_id = MarkId(7);
-        let mark = Mark::builder(mark_id)
-            .x_compute([InputRef::Signal { signal: zoom_id }], move |ctx, _| {
-                f64::from(ctx.signal::<f32>(zoom_id).unwrap_or(0.0))
-            })
-            .build();
-        let diffs = scene.tick([mark]);
+        let now = Cell::new(Duration::from_millis(10));
+        let clock = || {
+            let t = now.get();
+            now.set(t + Duration::from_millis(3));
+            t
+        };
+        scene.set_signal(a, 2.0_f64).unwrap();
+        let (diffs, stats) = scene.update_with_stats(Some(&clock));
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(
+            stats,
+            FrameStats {
+                marks_visited: 2,
+                marks_updated: 2,
+                encodings_recomputed: 2,
+                signals_changed: 1,
+                elapsed: Some(Duration::from_millis(3)),
+                ..FrameStats::default()
+            }
+        );
 
-        assert!(matches!(
-            &diffs[..],
-            [MarkDiff::Enter { id, .. }] if *id == mark_id
-        ));
+        scene.remove_mark(MarkId::for_row(table_id, 1));
+        let (_, stats) = scene.update_with_stats(None);
+        assert_eq!(stats.marks_exited, 1);
+        assert_eq!(stats.marks_visited, 0);
+        assert_eq!(stats.elapsed, None);
+
+        // Exits returned by reconciliation are counted by the next update, once.
+        let patch = TablePatch::new().with_deletes([2_u64]);
+        let exits = scene.apply_table_patch(table_id, patch, |id, _| build(id, 0, 0));
+        assert_eq!(exits.len(), 1);
+        let exits = scene.set_marks([build(MarkId(7), 0, 0)]);
+        assert!(exits.is_empty());
+        let (_, stats) = scene.update_with_stats(None);
+        assert_eq!(stats.marks_exited, 1);
+        assert_eq!(stats.marks_entered, 1);
+        let exits = scene.set_marks([]);
+        assert_eq!(exits.len(), 1);
+        let (_, stats) = scene.update_with_stats(None);
+        assert_eq!(stats.marks_exited, 1);
+        let (_, stats) = scene.update_with_stats(None);
+        assert_eq!(stats.marks_exited, 0);
+    }
 
-        scene.set_signal(zoom_id, 2.0_f32).unwrap();
+    #[test]
+    fn group_transform_change_is_a_single_update() {
+        let mut scene = Scene::new();
+        let scroll = SignalId(1);
+        scene.insert_signal(scroll, 0.0_f64);
+
+        let group = MarkId(1);
+        let clip = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let marks = [
+            Mark::builder(group)
+                .group()
+                .transform_compute([InputRef::Signal { signal: scroll }], move |ctx, _| {
+                    Affine::translate((ctx.signal::<f64>(scroll).unwrap_or(0.0), 10.0))
+                })
+                .clip_const(Some(GroupClip::Rect(clip)))
+                .build(),
+            Mark::builder(MarkId(2)).parent(group).w_const(5.0).build(),
+            Mark::builder(MarkId(3)).parent(group).w_const(6.0).build(),
+        ];
+        let diffs = scene.tick(marks);
+        assert_eq!(diffs.len(), 3);
+        assert!(diffs.iter().any(|d| matches!(
+            d,
+            MarkDiff::Enter { id, parent: Some(p), .. } if *id == MarkId(2) && *p == group
+        )));
+
+        scene.set_signal(scroll, -20.0_f64).unwrap();
         let diffs = scene.update();
-        let [MarkDiff::Update { id, old, new, .. }] = &diffs[..] else {
-            panic!("expected a single update diff");
+        let [
+            MarkDiff::Update {
+                id,
+                kind,
+                new,
+                new_bounds,
+                ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single group update");
         };
-        assert_eq!(*id, mark_id);
-        let (MarkPayload::Rect(old), MarkPayload::Rect(new)) = (&**old, &**new) else {
-            panic!("expected rect payloads");
+        assert_eq!(*id, group);
+        assert_eq!(*kind, MarkKind::Group);
+        let MarkPayload::Group(g) = &**new else {
+            panic!("expected group payload");
         };
-        assert_ne!(old.rect.x0, new.rect.x0);
-
-        let diffs = scene.tick(core::iter::empty());
+        assert_eq!(g.transform, Affine::translate((-20.0, 10.0)));
+        assert_eq!(*new_bounds, Some(Rect::new(-20.0, 10.0, 80.0, 60.0)));
+
+        // Re-parenting a child is reported even when its channels are unchanged.
+        let mut child = scene.marks.remove(&MarkId(3)).unwrap();
+        child.parent = None;
+        scene.marks.insert(MarkId(3), child);
+        assert!(scene.invalidate_mark(MarkId(3)));
+        let diffs = scene.update();
         assert!(matches!(
             &diffs[..],
-            [MarkDiff::Exit { id, .. }] if *id == mark_id
+            [MarkDiff::Update { id, old_parent: Some(p), new_parent: None, .. }]
+                if *id == MarkId(3) && *p == group
         ));
     }
 
     #[test]
-    fn only_recomputes_touched_encodings() {
+    fn stroke_style_width_tracks_stroke_width() {
         let mut scene = Scene::new();
-        let a = SignalId(1);
-        let b = SignalId(2);
-        scene.insert_signal(a, 1.0_f32);
-        scene.insert_signal(b, 10.0_f32);
-
-        let mark_id = MarkId(1);
-        let mark = Mark::builder(mark_id)
-            .x_compute([InputRef::Signal { signal: a }], move |ctx, _| {
-                f64::from(ctx.signal::<f32>(a).unwrap_or(0.0))
-            })
-            .y_compute([InputRef::Signal { signal: b }], move |ctx, _| {
-                f64::from(ctx.signal::<f32>(b).unwrap_or(0.0))
+        let dash = SignalId(1);
+        scene.insert_signal(dash, 3.0_f64);
+
+        let mark = Mark::builder(MarkId(1))
+            .path()
+            .stroke_width_const(2.0)
+            .stroke_style_compute([InputRef::Signal { signal: dash }], move |ctx, _| {
+                let d = ctx.signal::<f64>(dash).unwrap_or(0.0);
+                Stroke::new(99.0).with_dashes(0.0, [d, 1.0])
             })
             .build();
-        let _ = scene.tick([mark]);
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { new, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
+        };
+        let MarkPayload::Path(p) = &**new else {
+            panic!("expected path payload");
+        };
+        assert_eq!(p.stroke_style.width, 2.0);
+        assert_eq!(&p.stroke_style.dash_pattern[..], &[3.0, 1.0]);
+        assert_eq!(p.stroke_style.join, Join::Round);
 
-        // Change `a` only; `y` should remain the same.
-        scene.set_signal(a, 2.0_f32).unwrap();
+        scene.set_signal(dash, 5.0_f64).unwrap();
         let diffs = scene.update();
-
-        let [MarkDiff::Update { old, new, .. }] = &diffs[..] else {
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
             panic!("expected a single update diff");
         };
-        let (MarkPayload::Rect(old), MarkPayload::Rect(new)) = (&**old, &**new) else {
-            panic!("expected rect payloads");
+        let MarkPayload::Path(p) = &**new else {
+            panic!("expected path payload");
         };
-        assert_ne!(old.rect.x0, new.rect.x0);
-        assert_eq!(old.rect.y0, new.rect.y0);
+        assert_eq!(p.stroke_style.width, 2.0);
+        assert_eq!(&p.stroke_style.dash_pattern[..], &[5.0, 1.0]);
     }
 
     #[test]
-    fn table_row_reconciliation_exit_enter() {
-        let mut scene = Scene::new();
-        let table_id = TableId(1);
-        scene.set_table_row_keys(table_id, Vec::from([10_u64, 11_u64]));
-
-        let diffs = scene.tick_table_rows(table_id, |id, row_key, _row| {
-            Mark::builder(id).x_const(row_key as f64).build()
-        });
-        assert!(diffs.iter().any(|d| matches!(d, MarkDiff::Enter { .. })));
+    fn opacity_channels_update_incrementally() {
+        use peniko::Mix;
 
-        // Remove one row key and add a new one: expect one exit and one enter.
-        scene.set_table_row_keys(table_id, Vec::from([11_u64, 12_u64]));
-        let diffs = scene.tick_table_rows(table_id, |id, row_key, _row| {
-            Mark::builder(id).x_const(row_key as f64).build()
-        });
+        let mut scene = Scene::new();
+        let hover = SignalId(1);
+        scene.insert_signal(hover, false);
+
+        let mark = Mark::builder(MarkId(1))
+            .w_const(10.0)
+            .h_const(10.0)
+            .fill_opacity_const(0.5)
+            .stroke_opacity_const(0.25)
+            .blend_mode_const(Some(Mix::Multiply.into()))
+            .opacity_compute([InputRef::Signal { signal: hover }], move |ctx, _| {
+                if ctx.signal::<bool>(hover).unwrap_or(false) {
+                    1.0
+                } else {
+                    0.3
+                }
+            })
+            .build();
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { new, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
+        };
+        let MarkPayload::Rect(r) = &**new else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.opacity, 0.3);
+        assert_eq!(r.fill_opacity, 0.5);
+        assert_eq!(r.stroke_opacity, 0.25);
+        assert_eq!(r.blend_mode.map(|b| b.mix), Some(Mix::Multiply));
+
+        scene.set_signal(hover, true).unwrap();
+        let (diffs, stats) = scene.update_with_stats(None);
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        let MarkPayload::Rect(r) = &**new else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.opacity, 1.0);
+        assert_eq!(r.fill_opacity, 0.5);
+        assert_eq!(stats.encodings_recomputed, 1);
+    }
 
-        let enters = diffs
-            .iter()
-            .filter(|d| matches!(d, MarkDiff::Enter { .. }))
-            .count();
-        let exits = diffs
-            .iter()
-            .filter(|d| matches!(d, MarkDiff::Exit { .. }))
-            .count();
-        assert_eq!(enters, 1);
-        assert_eq!(exits, 1);
+    #[test]
+    fn rect_stroke_inflates_bounds() {
+        let mut scene = Scene::new();
+        let mark = Mark::builder(MarkId(1))
+            .x_const(10.0)
+            .y_const(20.0)
+            .w_const(30.0)
+            .h_const(40.0)
+            .stroke_brush_const(Color::BLACK)
+            .stroke_width_const(2.0)
+            .corner_radius_const(4.0)
+            .build();
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { new, bounds, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
+        };
+        let MarkPayload::Rect(r) = &**new else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.stroke_width, 2.0);
+        assert_eq!(r.corner_radii, RoundedRectRadii::from_single_radius(4.0));
+        assert_eq!(*bounds, Some(Rect::new(9.0, 19.0, 41.0, 61.0)));
     }
 
     #[test]
-    fn text_updates_and_bounds_unknown() {
+    fn image_mark_swaps_source_without_moving() {
         let mut scene = Scene::new();
-        let sig = SignalId(1);
-        scene.insert_signal(sig, 1_u32);
+        let hover = SignalId(1);
+        scene.insert_signal(hover, false);
+
+        let mark = Mark::builder(MarkId(1))
+            .image()
+            .x_const(10.0)
+            .y_const(5.0)
+            .w_const(-4.0)
+            .h_const(3.0)
+            .image_quality_const(ImageQuality::Low)
+            .image_compute([InputRef::Signal { signal: hover }], move |ctx, _| {
+                if ctx.signal::<bool>(hover).unwrap_or(false) {
+                    "hover.png".into()
+                } else {
+                    "idle.png".into()
+                }
+            })
+            .build();
+        let diffs = scene.tick([mark]);
+        let [
+            MarkDiff::Enter {
+                kind, new, bounds, ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single enter diff");
+        };
+        assert_eq!(*kind, MarkKind::Image);
+        let MarkPayload::Image(i) = &**new else {
+            panic!("expected image payload");
+        };
+        assert_eq!(i.rect, Rect::new(6.0, 5.0, 10.0, 8.0));
+        assert_eq!(*bounds, Some(i.rect));
+        assert_eq!(i.quality, ImageQuality::Low);
+        assert_eq!(i.image, ImageSource::Handle("idle.png".into()));
+
+        scene.set_signal(hover, true).unwrap();
+        let (diffs, stats) = scene.update_with_stats(None);
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
+        };
+        let MarkPayload::Image(i) = &**new else {
+            panic!("expected image payload");
+        };
+        assert_eq!(i.image, ImageSource::Handle("hover.png".into()));
+        assert_eq!(i.rect, Rect::new(6.0, 5.0, 10.0, 8.0));
+        assert_eq!(stats.encodings_recomputed, 1);
+    }
 
-        let mark_id = MarkId(1);
-        let mark = Mark::builder(mark_id)
+    #[test]
+    fn text_font_channels_update_incrementally() {
+        let mut scene = Scene::new();
+        let emphasis = SignalId(1);
+        scene.insert_signal(emphasis, false);
+
+        let font = TextFont::default()
+            .with_family("Inter, sans-serif")
+            .with_style(FontStyle::Italic)
+            .with_line_height(1.5);
+        let mark = Mark::builder(MarkId(1))
             .text()
-            .x_const(0.0)
-            .y_const(0.0)
-            .text_compute([InputRef::Signal { signal: sig }], move |ctx, _| {
-                let v = ctx.signal::<u32>(sig).unwrap_or(0);
-                alloc::format!("v={v}")
+            .text_const("a\nb")
+            .font_const(&font)
+            .font_weight_compute([InputRef::Signal { signal: emphasis }], move |ctx, _| {
+                if ctx.signal::<bool>(emphasis).unwrap_or(false) {
+                    FontWeight::BOLD
+                } else {
+                    FontWeight::NORMAL
+                }
             })
             .build();
-
         let diffs = scene.tick([mark]);
-        let [MarkDiff::Enter { kind, bounds, .. }] = &diffs[..] else {
-            panic!("expected enter");
+        let [MarkDiff::Enter { new, .. }] = &diffs[..] else {
+            panic!("expected a single enter diff");
         };
-        assert_eq!(*kind, MarkKind::Text);
-        assert_eq!(*bounds, None);
+        let MarkPayload::Text(t) = &**new else {
+            panic!("expected text payload");
+        };
+        assert_eq!(t.font(), font);
 
-        scene.set_signal(sig, 2_u32).unwrap();
-        let diffs = scene.update();
-        let [MarkDiff::Update { old, new, .. }] = &diffs[..] else {
-            panic!("expected update");
+        scene.set_signal(emphasis, true).unwrap();
+        let (diffs, stats) = scene.update_with_stats(None);
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
+            panic!("expected a single update diff");
         };
-        let (MarkPayload::Text(old), MarkPayload::Text(new)) = (&**old, &**new) else {
-            panic!("expected text payloads");
+        let MarkPayload::Text(t) = &**new else {
+            panic!("expected text payload");
         };
-        assert_ne!(old.text, new.text);
+        assert_eq!(t.font_weight, FontWeight::BOLD);
+        assert_eq!(t.font_family, "Inter, sans-serif");
+        assert_eq!(stats.encodings_recomputed, 1);
+    }
+
+    /// Measures 5 units per char and 1em per line.
+    struct FixedMeasurer;
+
+    impl TextMeasurer for FixedMeasurer {
+        fn measure(&self, text: &str, font_size: f64, _font: &TextFont) -> (f64, f64) {
+            (5.0 * text.chars().count() as f64, font_size)
+        }
     }
 
     #[test]
-    fn path_enter_has_bounds() {
+    fn text_diffs_carry_bounds_with_measurer() {
         let mut scene = Scene::new();
-        let mark_id = MarkId(1);
-
-        let mut triangle = BezPath::new();
-        triangle.move_to((0.0, 0.0));
-        triangle.line_to((10.0, 0.0));
-        triangle.line_to((5.0, 10.0));
-        triangle.close_path();
+        let label = SignalId(1);
+        scene.insert_signal(label, String::from("ab"));
+
+        let mark = || {
+            Mark::builder(MarkId(1))
+                .text()
+                .x_const(100.0)
+                .y_const(50.0)
+                .font_size_const(10.0)
+                .text_anchor_middle()
+                .text_compute([InputRef::Signal { signal: label }], move |ctx, _| {
+                    ctx.signal::<String>(label).unwrap_or_default()
+                })
+                .build()
+        };
+        let diffs = scene.tick([mark()]);
+        assert_eq!(diffs[0].damage(), None, "no measurer, no text bounds");
 
-        let mark = Mark::builder(mark_id).path().path_const(triangle).build();
-        let diffs = scene.tick([mark]);
+        scene.set_text_measurer(Some(Box::new(FixedMeasurer)));
+        scene.set_signal(label, String::from("abcd")).unwrap();
+        let diffs = scene.update();
+        let [
+            MarkDiff::Update {
+                old_bounds,
+                new_bounds,
+                damage,
+                ..
+            },
+        ] = &diffs[..]
+        else {
+            panic!("expected a single update diff");
+        };
+        assert_eq!(*old_bounds, Some(Rect::new(95.0, 45.0, 105.0, 55.0)));
+        assert_eq!(*new_bounds, Some(Rect::new(90.0, 45.0, 110.0, 55.0)));
+        assert_eq!(*damage, *new_bounds);
 
-        let [MarkDiff::Enter { kind, bounds, .. }] = &diffs[..] else {
-            panic!("expected enter");
+        // A vertical label's bounds swap width and height around the anchor.
+        let rotated = TextChannels {
+            pos: Point::new(0.0, 0.0),
+            text: String::from("abcd"),
+            font_size: 10.0,
+            angle: -90.0,
+            baseline: TextBaseline::Hanging,
+            ..TextChannels::default()
         };
-        assert_eq!(*kind, MarkKind::Path);
-        assert!(bounds.is_some());
+        let b = rotated.bounds(&FixedMeasurer);
+        assert!((b.width() - 10.0).abs() < 1e-9);
+        assert!((b.height() - 20.0).abs() < 1e-9);
+        assert!((b.y1 - 0.0).abs() < 1e-9);
     }
 
     #[test]
-    fn set_signal_type_mismatch_does_not_bump_version() {
+    fn derived_signals_refresh_in_order_and_bump_only_on_change() {
         let mut scene = Scene::new();
-        let id = SignalId(1);
+        let table_id = TableId(1);
+        let mut table = Table::new(table_id);
+        table.row_keys = alloc::vec![0, 1, 2];
+        table.data = Some(Box::new(SliceCol(alloc::vec![1.0, 4.0, 2.0])));
+        scene.insert_table(table);
+
+        let brush = SignalId(1);
+        let max = SignalId(2);
+        let domain = SignalId(3);
+        scene.insert_signal(brush, 0.5_f64);
+        // Inserted before its upstream `max`, so refresh order must follow dependencies.
+        scene.insert_derived_signal(
+            domain,
+            [
+                InputRef::Signal { signal: brush },
+                InputRef::Signal { signal: max },
+            ],
+            move |ctx| {
+                ctx.signal::<f64>(max).unwrap_or(0.0) * ctx.signal::<f64>(brush).unwrap_or(0.0)
+            },
+        );
+        scene.insert_derived_signal(
+            max,
+            [InputRef::TableCol {
+                table: table_id,
+                col: ColId(0),
+            }],
+            move |ctx| {
+                ctx.table_f64_slice(table_id, ColId(0))
+                    .map_or(0.0, |s| s.iter().copied().fold(f64::MIN, f64::max))
+            },
+        );
+        assert!(scene.is_derived_signal(domain));
+        assert_eq!(scene.signal_ref::<f64>(max), Ok(Some(&4.0)));
 
-        scene.insert_signal(id, 1.0_f32);
+        let mark = Mark::builder(MarkId(1))
+            .w_compute([InputRef::Signal { signal: domain }], move |ctx, _| {
+                ctx.signal::<f64>(domain).unwrap_or(0.0)
+            })
+            .build();
+        let diffs = scene.tick([mark]);
+        let [MarkDiff::Enter { new, .. }] = &diffs[..] else {
+            panic!("expected a single enter");
+        };
+        let MarkPayload::Rect(r) = &**new else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.rect.width(), 2.0);
+
+        // The column changes but its max does not: no signal bump, no mark update.
+        let version = scene.signals[&domain].version();
+        scene.set_table_column_data(
+            table_id,
+            Some(Box::new(SliceCol(alloc::vec![4.0, 3.0, 2.0]))),
+            [ColId(0)],
+        );
+        assert!(scene.update().is_empty());
+        assert_eq!(scene.signals[&domain].version(), version);
+
+        // A new max flows through both derived signals in a single update.
+        scene.set_table_column_data(
+            table_id,
+            Some(Box::new(SliceCol(alloc::vec![8.0]))),
+            [ColId(0)],
+        );
+        let diffs = scene.update();
+        let [MarkDiff::Update { new, .. }] = &diffs[..] else {
+            panic!("expected a single update");
+        };
+        let MarkPayload::Rect(r) = &**new else {
+            panic!("expected rect payload");
+        };
+        assert_eq!(r.rect.width(), 4.0);
 
-        let before_version = scene.signals.get(&id).unwrap().version();
+        assert!(scene.remove_derived_signal(max));
+        scene.set_signal(max, 10.0_f64).unwrap();
+        assert_eq!(scene.update().len(), 1);
+        assert_eq!(scene.signal_ref::<f64>(domain), Ok(Some(&5.0)));
+    }
 
-        // Wrong type: should error and should NOT bump version.
-        let err = scene.set_signal::<u32>(id, 123_u32).unwrap_err();
-        assert_eq!(err, SignalAccessError::TypeMismatch);
+    #[test]
+    fn derived_signal_over_column_refreshes_after_patch() {
+        let mut scene = Scene::new();
+        let table_id = TableId(1);
+        let col = ColId(0);
+        let mut table = Table::new(table_id);
+        table.row_keys = alloc::vec![0, 1];
+        table.data = Some(Box::new(SliceCol(alloc::vec![1.0, 4.0])));
+        scene.insert_table(table);
+
+        let count = SignalId(1);
+        let max = SignalId(2);
+        scene.insert_derived_signal(
+            count,
+            [InputRef::TableCol {
+                table: table_id,
+                col,
+            }],
+            move |ctx| ctx.table_f64_slice(table_id, col).map_or(0, <[f64]>::len),
+        );
+        scene.insert_derived_signal(
+            max,
+            [InputRef::TableCol {
+                table: table_id,
+                col,
+            }],
+            move |ctx| {
+                ctx.table_f64_slice(table_id, col)
+                    .map_or(0.0, |s| s.iter().copied().fold(f64::MIN, f64::max))
+            },
+        );
 
-        let after_version = scene.signals.get(&id).unwrap().version();
-        assert_eq!(before_version, after_version);
+        let patch = TablePatch::new()
+            .with_inserts([2, 3, 4])
+            .with_data(Box::new(SliceCol(alloc::vec![1.0, 4.0, 9.0, 2.0, 3.0])));
+        let _ = scene.tick_table_patch(table_id, patch, |id, _| Mark::new(id));
+        assert_eq!(scene.signal_ref::<usize>(count), Ok(Some(&5)));
+        assert_eq!(scene.signal_ref::<f64>(max), Ok(Some(&9.0)));
+
+        let patch = TablePatch::new()
+            .with_updates([2])
+            .with_changed_columns([col])
+            .with_data(Box::new(SliceCol(alloc::vec![1.0, 4.0, 6.0, 2.0, 3.0])));
+        let _ = scene.tick_table_patch(table_id, patch, |id, _| Mark::new(id));
+        assert_eq!(scene.signal_ref::<f64>(max), Ok(Some(&6.0)));
+    }
 
-        // Also assert that the original value is unchanged.
-        let sig_any = scene.signals.get(&id).unwrap();
-        let typed = sig_any.as_any().downcast_ref::<Signal<f32>>().unwrap();
-        assert_eq!(typed.value, 1.0_f32);
+    #[test]
+    fn update_diffs_are_sorted_by_mark_id() {
+        let mut scene = Scene::new();
+        let zoom = SignalId(1);
+        scene.insert_signal(zoom, 1.0_f64);
+        let marks: Vec<Mark> = (0..1000_u64)
+            .rev()
+            .map(|i| {
+                Mark::builder(MarkId(i * 7919 % 1000))
+                    .x_compute([InputRef::Signal { signal: zoom }], move |ctx, id| {
+                        ctx.signal::<f64>(zoom).unwrap_or(0.0) * id.0 as f64
+                    })
+                    .w_const(1.0)
+                    .build()
+            })
+            .collect();
+        let diffs = scene.tick(marks);
+        assert_eq!(diffs.len(), 1000);
+        assert!(diffs.windows(2).all(|w| w[0].id() < w[1].id()));
+
+        scene.set_signal(zoom, 2.0_f64).unwrap();
+        let (diffs, stats) = scene.update_with_stats(None);
+        assert_eq!(stats.marks_visited, 1000);
+        assert_eq!(stats.encodings_recomputed, 1000);
+        assert_eq!(diffs.len(), 999, "mark 0 stays at x = 0");
+        assert!(diffs.windows(2).all(|w| w[0].id() < w[1].id()));
+        let MarkDiff::Update { new, .. } = &diffs[998] else {
+            panic!("expected an update");
+        };
+        assert_eq!(new.bounds().unwrap().x0, 1998.0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_update_matches_sequential_update() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicU32, Ordering};
+
+        let zoom = SignalId(1);
+        let deps = [InputRef::Signal { signal: zoom }];
+        let mut local = Scene::new();
+        let mut shared = Scene::new_shared();
+        local.insert_signal(zoom, 1.0_f64);
+        shared.insert_signal(zoom, 1.0_f64);
+
+        let evals = Arc::new(AtomicU32::new(0));
+        let local_marks: Vec<Mark> = (0..1000_u64)
+            .map(|i| {
+                Mark::builder(MarkId(i * 7919 % 1000))
+                    .x_compute(deps, move |ctx, id| {
+                        ctx.signal::<f64>(zoom).unwrap_or(0.0) * id.0 as f64
+                    })
+                    .w_const(1.0)
+                    .build()
+            })
+            .collect();
+        let shared_marks: Vec<Mark<Shared>> = (0..1000_u64)
+            .map(|i| {
+                let evals = evals.clone();
+                Mark::shared_builder(MarkId(i * 7919 % 1000))
+                    .x_compute(deps, move |ctx, id| {
+                        evals.fetch_add(1, Ordering::Relaxed);
+                        ctx.signal::<f64>(zoom).unwrap_or(0.0) * id.0 as f64
+                    })
+                    .w_const(1.0)
+                    .build()
+            })
+            .collect();
+        let _ = shared.set_marks(shared_marks);
+        assert_eq!(shared.update_parallel(), local.tick(local_marks));
+        assert_eq!(evals.load(Ordering::Relaxed), 1000);
+
+        local.set_signal(zoom, 2.0_f64).unwrap();
+        shared.set_signal(zoom, 2.0_f64).unwrap();
+        let (expected, expected_stats) = local.update_with_stats(None);
+        let (diffs, stats) = shared.update_parallel_with_stats(None);
+        assert_eq!(diffs, expected);
+        assert_eq!(stats, expected_stats);
+        assert_eq!(evals.load(Ordering::Relaxed), 2000);
+
+        // Small dirty sets are evaluated on the calling thread.
+        for i in 0..10 {
+            assert!(shared.invalidate_mark(MarkId(i)));
+        }
+        assert!(shared.update_parallel().is_empty());
+        assert_eq!(evals.load(Ordering::Relaxed), 2010);
+    }
+
+    #[test]
+    fn meta_channels_evaluate_for_every_kind_and_update_incrementally() {
+        let mut scene = Scene::new();
+        let hovered = SignalId(1);
+        scene.insert_signal(hovered, 1_u32);
+        let marks = [
+            Mark::builder(MarkId(1))
+                .w_const(10.0)
+                .aria_role_const("graphics-symbol")
+                .description_const("Apples, 12")
+                .tooltip_compute([InputRef::Signal { signal: hovered }], move |ctx, _| {
+                    alloc::format!("hovered {}", ctx.signal::<u32>(hovered).unwrap_or(0))
+                })
+                .build(),
+            Mark::builder(MarkId(2))
+                .group()
+                .href_const("https://example.com")
+                .build(),
+        ];
+        let diffs = scene.tick(marks);
+        assert_eq!(diffs.len(), 2);
+        let meta = scene.marks[&MarkId(1)].cache.as_ref().unwrap().meta();
+        assert_eq!(meta.aria_role, "graphics-symbol");
+        assert_eq!(meta.description, "Apples, 12");
+        assert_eq!(meta.tooltip, "hovered 1");
+        let group = scene.marks[&MarkId(2)].cache.as_ref().unwrap().meta();
+        assert_eq!(group.href, "https://example.com");
+        assert!(!group.is_empty());
+
+        // A metadata-only change is an ordinary update with unchanged geometry.
+        scene.set_signal(hovered, 2_u32).unwrap();
+        let diffs = scene.update();
+        let [MarkDiff::Update { old, new, .. }] = &diffs[..] else {
+            panic!("expected a single update");
+        };
+        assert_eq!(old.as_deref().unwrap().bounds(), new.bounds());
+        assert_eq!(new.meta().tooltip, "hovered 2");
+        assert_eq!(new.meta().description, "Apples, 12");
+
+        // Marks without metadata do not allocate any.
+        scene.tick([Mark::builder(MarkId(3)).w_const(10.0).build()]);
+        let MarkPayload::Rect(r) = scene.marks[&MarkId(3)].cache.as_ref().unwrap() else {
+            panic!("expected rect payload");
+        };
+        assert!(r.meta.is_none());
+        assert!(
+            scene.marks[&MarkId(3)]
+                .cache
+                .as_ref()
+                .unwrap()
+                .meta()
+                .is_empty()
+        );
     }
 }
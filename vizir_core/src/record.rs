@@ -0,0 +1,145 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Recording and replaying [`MarkDiff`] streams.
+
+use alloc::vec::Vec;
+
+use crate::{HitIndex, MarkDiff};
+
+/// A consumer of [`MarkDiff`] batches, such as a renderer or a [`HitIndex`].
+pub trait DiffSink {
+    /// Apply one batch of diffs, as returned by a single [`crate::Scene::update`] or
+    /// [`crate::Scene::tick`].
+    fn apply_diffs(&mut self, diffs: &[MarkDiff]);
+}
+
+impl DiffSink for HitIndex {
+    fn apply_diffs(&mut self, diffs: &[MarkDiff]) {
+        self.apply(diffs);
+    }
+}
+
+/// A recorded sequence of diff batches ("frames").
+///
+/// Record the output of each update, ship or store the recording (with the `serde` feature it
+/// serializes with any serde format), and replay it into a [`DiffSink`] to rebuild the same
+/// downstream state, e.g. in a UI process fed by a worker, or in replay tests.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffRecording {
+    /// Diff batches, in the order they were produced.
+    pub frames: Vec<Vec<MarkDiff>>,
+}
+
+impl DiffRecording {
+    /// Creates an empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a copy of one diff batch (empty batches are kept, preserving frame boundaries).
+    pub fn record(&mut self, diffs: &[MarkDiff]) {
+        self.frames.push(diffs.to_vec());
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Applies every frame, in order, to `sink`.
+    pub fn replay(&self, sink: &mut impl DiffSink) {
+        for frame in &self.frames {
+            sink.apply_diffs(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::vec;
+
+    use kurbo::{BezPath, Point, Rect};
+    use peniko::color::palette;
+    use peniko::{Blob, ImageData, ImageFormat};
+
+    use super::*;
+    use crate::{GroupClip, ImageSource, InputRef, Mark, MarkId, Scene, SignalId};
+
+    #[test]
+    fn replayed_recording_rebuilds_sink_state() {
+        let mut scene = Scene::new();
+        let x = SignalId(1);
+        scene.insert_signal(x, 0.0_f64);
+        let mut tri = BezPath::new();
+        tri.move_to((0.0, 0.0));
+        tri.quad_to((5.0, -5.0), (10.0, 0.0));
+        tri.curve_to((10.0, 5.0), (5.0, 10.0), (0.0, 10.0));
+        tri.close_path();
+        let pixels = Blob::new(std::sync::Arc::new([255_u8, 0, 0, 255]));
+        let marks = vec![
+            Mark::builder(MarkId(1))
+                .group()
+                .clip_const(Some(GroupClip::Path(tri.clone())))
+                .build(),
+            Mark::builder(MarkId(2))
+                .parent(MarkId(1))
+                .x_compute([InputRef::Signal { signal: x }], move |ctx, _| {
+                    ctx.signal::<f64>(x).unwrap_or(0.0)
+                })
+                .w_const(4.0)
+                .h_const(4.0)
+                .fill_const(palette::css::TEAL.with_alpha(0.5))
+                .build(),
+            Mark::builder(MarkId(3)).path().path_const(tri).build(),
+            Mark::builder(MarkId(4)).text().text_const("label").build(),
+            Mark::builder(MarkId(5))
+                .image()
+                .w_const(1.0)
+                .h_const(1.0)
+                .image_const(ImageSource::Data(ImageData {
+                    data: pixels,
+                    format: ImageFormat::Rgba8,
+                    alpha_type: peniko::ImageAlphaType::Alpha,
+                    width: 1,
+                    height: 1,
+                }))
+                .build(),
+        ];
+
+        let mut recording = DiffRecording::new();
+        recording.record(&scene.tick(marks));
+        scene.set_signal(x, 3.0_f64).unwrap();
+        recording.record(&scene.update());
+        recording.record(&scene.update());
+        assert_eq!(recording.len(), 3);
+        assert!(recording.frames[2].is_empty());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&recording).unwrap();
+            let decoded: DiffRecording = serde_json::from_str(&json).unwrap();
+            // Decoded image blobs get fresh ids, so compare the serialized forms.
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+            assert_eq!(decoded.frames[1], recording.frames[1]);
+        }
+
+        let mut index = HitIndex::new();
+        recording.replay(&mut index);
+        assert_eq!(index.len(), 5);
+        // The ungrouped path (id 3) draws above everything in group 1.
+        assert_eq!(
+            index.hit_all(Point::new(4.0, 1.0)),
+            vec![MarkId(3), MarkId(2)]
+        );
+        assert_eq!(index.bounds(MarkId(2)), Some(Rect::new(3.0, 0.0, 7.0, 4.0)));
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Compact serde representations for `kurbo`/`peniko` types used in payloads.
+//!
+//! These are used via `#[serde(with = ...)]` on payload fields, so that geometry and colors
+//! serialize as flat numbers rather than the upstream crates' self-describing forms.
+
+/// `BezPath` as one verb byte per element plus a flat list of point coordinates.
+pub(crate) mod bez_path {
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    use kurbo::{BezPath, PathEl, Point};
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    const MOVE_TO: u8 = 0;
+    const LINE_TO: u8 = 1;
+    const QUAD_TO: u8 = 2;
+    const CURVE_TO: u8 = 3;
+    const CLOSE_PATH: u8 = 4;
+
+    struct Verbs<'a>(&'a [u8]);
+
+    impl Serialize for Verbs<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    struct VerbBuf(Vec<u8>);
+
+    impl<'de> Deserialize<'de> for VerbBuf {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct VerbVisitor;
+
+            impl<'de> Visitor<'de> for VerbVisitor {
+                type Value = VerbBuf;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("path verb bytes")
+                }
+
+                fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<VerbBuf, E> {
+                    Ok(VerbBuf(v.to_vec()))
+                }
+
+                fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<VerbBuf, E> {
+                    Ok(VerbBuf(v))
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<VerbBuf, A::Error> {
+                    let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                    while let Some(v) = seq.next_element()? {
+                        out.push(v);
+                    }
+                    Ok(VerbBuf(out))
+                }
+            }
+
+            deserializer.deserialize_bytes(VerbVisitor)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct PathRef<'a> {
+        verbs: Verbs<'a>,
+        coords: Vec<f64>,
+    }
+
+    #[derive(Deserialize)]
+    struct PathOwned {
+        verbs: VerbBuf,
+        coords: Vec<f64>,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        path: &BezPath,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let els = path.elements();
+        let mut verbs = Vec::with_capacity(els.len());
+        let mut coords = Vec::with_capacity(els.len() * 2);
+        let mut push = |pts: &[Point]| {
+            for p in pts {
+                coords.push(p.x);
+                coords.push(p.y);
+            }
+        };
+        for el in els {
+            match *el {
+                PathEl::MoveTo(p) => {
+                    verbs.push(MOVE_TO);
+                    push(&[p]);
+                }
+                PathEl::LineTo(p) => {
+                    verbs.push(LINE_TO);
+                    push(&[p]);
+                }
+                PathEl::QuadTo(p1, p2) => {
+                    verbs.push(QUAD_TO);
+                    push(&[p1, p2]);
+                }
+                PathEl::CurveTo(p1, p2, p3) => {
+                    verbs.push(CURVE_TO);
+                    push(&[p1, p2, p3]);
+                }
+                PathEl::ClosePath => verbs.push(CLOSE_PATH),
+            }
+        }
+        PathRef {
+            verbs: Verbs(&verbs),
+            coords,
+        }
+        .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BezPath, D::Error> {
+        let PathOwned { verbs, coords } = PathOwned::deserialize(deserializer)?;
+        let mut points = coords.chunks_exact(2).map(|c| Point::new(c[0], c[1]));
+        if coords.len() % 2 != 0 {
+            return Err(de::Error::custom("odd number of path coordinates"));
+        }
+        let mut next = || {
+            points
+                .next()
+                .ok_or_else(|| de::Error::custom("too few path coordinates"))
+        };
+        if verbs.0.first().is_some_and(|v| *v != MOVE_TO) {
+            return Err(de::Error::custom("path does not start with a move"));
+        }
+        let mut els = Vec::with_capacity(verbs.0.len());
+        for verb in verbs.0 {
+            els.push(match verb {
+                MOVE_TO => PathEl::MoveTo(next()?),
+                LINE_TO => PathEl::LineTo(next()?),
+                QUAD_TO => PathEl::QuadTo(next()?, next()?),
+                CURVE_TO => PathEl::CurveTo(next()?, next()?, next()?),
+                CLOSE_PATH => PathEl::ClosePath,
+                other => {
+                    return Err(de::Error::custom(format_args!("unknown path verb {other}")));
+                }
+            });
+        }
+        if points.next().is_some() {
+            return Err(de::Error::custom("too many path coordinates"));
+        }
+        Ok(BezPath::from_vec(els))
+    }
+}
+
+/// `Brush` with solid colors as four `f32` sRGB components (gradients and images use peniko's
+/// own representation).
+pub(crate) mod brush {
+    use peniko::{Brush, Color, Gradient, ImageBrush};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    enum BrushRef<'a> {
+        Solid([f32; 4]),
+        Gradient(&'a Gradient),
+        Image(&'a ImageBrush),
+    }
+
+    #[derive(Deserialize)]
+    enum BrushOwned {
+        Solid([f32; 4]),
+        Gradient(Gradient),
+        Image(ImageBrush),
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        brush: &Brush,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match brush {
+            Brush::Solid(c) => BrushRef::Solid(c.components),
+            Brush::Gradient(g) => BrushRef::Gradient(g),
+            Brush::Image(i) => BrushRef::Image(i),
+        }
+        .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Brush, D::Error> {
+        Ok(match BrushOwned::deserialize(deserializer)? {
+            BrushOwned::Solid(c) => Brush::Solid(Color::new(c)),
+            BrushOwned::Gradient(g) => Brush::Gradient(g),
+            BrushOwned::Image(i) => Brush::Image(i),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::string::ToString;
+
+    use kurbo::BezPath;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Wrapped(#[serde(with = "super::bez_path")] BezPath);
+
+    fn decode(json: &str) -> Result<BezPath, std::string::String> {
+        serde_json::from_str::<Wrapped>(json)
+            .map(|w| w.0)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn bez_path_round_trips() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 1.0));
+        path.quad_to((2.0, 3.0), (4.0, 5.0));
+        path.close_path();
+        let json = serde_json::to_string(&Wrapped(path.clone())).unwrap();
+        assert_eq!(decode(&json).unwrap(), path);
+        assert_eq!(
+            decode(r#"{"verbs":[],"coords":[]}"#).unwrap(),
+            BezPath::new()
+        );
+    }
+
+    #[test]
+    fn malformed_bez_paths_are_errors() {
+        for (json, message) in [
+            (
+                r#"{"verbs":[1],"coords":[0,0]}"#,
+                "does not start with a move",
+            ),
+            (r#"{"verbs":[4],"coords":[]}"#, "does not start with a move"),
+            (
+                r#"{"verbs":[0,1],"coords":[0,0]}"#,
+                "too few path coordinates",
+            ),
+            (
+                r#"{"verbs":[0],"coords":[0,0,1,1]}"#,
+                "too many path coordinates",
+            ),
+            (
+                r#"{"verbs":[0],"coords":[0,0,1]}"#,
+                "odd number of path coordinates",
+            ),
+            (r#"{"verbs":[0,9],"coords":[0,0]}"#, "unknown path verb 9"),
+        ] {
+            let err = decode(json).unwrap_err();
+            assert!(err.contains(message), "{json}: {err}");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Threading modes for scenes and the data they store.
+
+use alloc::boxed::Box;
+
+use crate::{AnySignal, EvalCtx, MarkId, Signal, TableData, TextMeasurer};
+
+mod sealed {
+    #[allow(unnameable_types, reason = "seals `Threading`")]
+    pub trait Sealed {}
+}
+
+/// How a [`Scene`](crate::Scene) and the data it stores may be shared between threads.
+///
+/// Every scene-facing type ([`Scene`](crate::Scene), [`Table`](crate::Table),
+/// [`Mark`](crate::Mark), [`EvalCtx`], ...) takes a threading mode parameter that defaults to
+/// [`Local`], which places no thread-safety requirements on encoding closures, table data,
+/// signal values, or text measurers. [`Shared`] requires all of them to be `Send + Sync`, which
+/// lets `Scene::update_parallel` (with the `parallel` feature) evaluate marks across threads.
+///
+/// This trait is sealed; the associated types are the boxed trait objects a scene stores.
+pub trait Threading: sealed::Sealed + Sized + 'static {
+    /// The boxed closure type of [`Encoding::Compute`](crate::Encoding::Compute).
+    type ComputeFn<T>: ?Sized + Fn(&EvalCtx<'_, Self>, MarkId) -> T;
+    /// The boxed accessor type of [`Table::data`](crate::Table::data).
+    type TableData: ?Sized + TableData;
+    /// The boxed signal type of [`Scene::signals`](crate::Scene::signals).
+    type Signal: ?Sized + AnySignal;
+    /// The boxed measurer type of [`Scene::set_text_measurer`](crate::Scene::set_text_measurer).
+    type TextMeasurer: ?Sized + TextMeasurer;
+
+    /// Views stored table data as a plain [`TableData`] trait object.
+    fn table_data(data: &Self::TableData) -> &dyn TableData;
+
+    /// Views a stored text measurer as a plain [`TextMeasurer`] trait object.
+    fn text_measurer(measurer: &Self::TextMeasurer) -> &dyn TextMeasurer;
+
+    /// Boxes a `Send + Sync` encoding closure, which every threading mode can store.
+    fn box_sync_compute<T, F>(f: F) -> Box<Self::ComputeFn<T>>
+    where
+        F: Fn(&EvalCtx<'_, Self>, MarkId) -> T + Send + Sync + 'static;
+}
+
+/// Threading modes that can store an encoding closure of type `F` producing `T`.
+pub trait ComputeFor<T, F>: Threading {
+    /// Boxes `f` as an encoding closure.
+    fn box_compute(f: F) -> Box<Self::ComputeFn<T>>;
+
+    /// Boxes `f` followed by `map` as an encoding closure.
+    fn box_compute_map<U: 'static>(f: F, map: fn(T) -> U) -> Box<Self::ComputeFn<U>>;
+}
+
+/// Threading modes that can store a signal holding a `T`.
+pub trait SignalFor<T: Clone + 'static>: Threading {
+    /// Boxes `signal` for storage in a scene.
+    fn box_signal(signal: Signal<T>) -> Box<Self::Signal>;
+}
+
+/// Single-threaded scenes (the default): stored closures and data need not be `Send` or `Sync`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Local {}
+
+/// Scenes whose closures and data are `Send + Sync`, so they can be evaluated across threads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shared {}
+
+impl sealed::Sealed for Local {}
+impl sealed::Sealed for Shared {}
+
+impl Threading for Local {
+    type ComputeFn<T> = dyn Fn(&EvalCtx<'_, Self>, MarkId) -> T;
+    type TableData = dyn TableData;
+    type Signal = dyn AnySignal;
+    type TextMeasurer = dyn TextMeasurer;
+
+    fn table_data(data: &Self::TableData) -> &dyn TableData {
+        data
+    }
+
+    fn text_measurer(measurer: &Self::TextMeasurer) -> &dyn TextMeasurer {
+        measurer
+    }
+
+    fn box_sync_compute<T, F>(f: F) -> Box<Self::ComputeFn<T>>
+    where
+        F: Fn(&EvalCtx<'_, Self>, MarkId) -> T + Send + Sync + 'static,
+    {
+        Box::new(f)
+    }
+}
+
+impl Threading for Shared {
+    type ComputeFn<T> = dyn Fn(&EvalCtx<'_, Self>, MarkId) -> T + Send + Sync;
+    type TableData = dyn TableData + Send + Sync;
+    type Signal = dyn AnySignal + Send + Sync;
+    type TextMeasurer = dyn TextMeasurer + Send + Sync;
+
+    fn table_data(data: &Self::TableData) -> &dyn TableData {
+        data
+    }
+
+    fn text_measurer(measurer: &Self::TextMeasurer) -> &dyn TextMeasurer {
+        measurer
+    }
+
+    fn box_sync_compute<T, F>(f: F) -> Box<Self::ComputeFn<T>>
+    where
+        F: Fn(&EvalCtx<'_, Self>, MarkId) -> T + Send + Sync + 'static,
+    {
+        Box::new(f)
+    }
+}
+
+impl<T: 'static, F> ComputeFor<T, F> for Local
+where
+    F: Fn(&EvalCtx<'_, Self>, MarkId) -> T + 'static,
+{
+    fn box_compute(f: F) -> Box<Self::ComputeFn<T>> {
+        Box::new(f)
+    }
+
+    fn box_compute_map<U: 'static>(f: F, map: fn(T) -> U) -> Box<Self::ComputeFn<U>> {
+        Box::new(move |ctx, id| map(f(ctx, id)))
+    }
+}
+
+impl<T: 'static, F> ComputeFor<T, F> for Shared
+where
+    F: Fn(&EvalCtx<'_, Self>, MarkId) -> T + Send + Sync + 'static,
+{
+    fn box_compute(f: F) -> Box<Self::ComputeFn<T>> {
+        Box::new(f)
+    }
+
+    fn box_compute_map<U: 'static>(f: F, map: fn(T) -> U) -> Box<Self::ComputeFn<U>> {
+        Box::new(move |ctx, id| map(f(ctx, id)))
+    }
+}
+
+impl<T: Clone + 'static> SignalFor<T> for Local {
+    fn box_signal(signal: Signal<T>) -> Box<Self::Signal> {
+        Box::new(signal)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SignalFor<T> for Shared {
+    fn box_signal(signal: Signal<T>) -> Box<Self::Signal> {
+        Box::new(signal)
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2025 the VizIR Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Timing and memory harness for `Scene::update` on large per-row mark sets.
+//!
+//! Run with `cargo run --release -p vizir_examples --bin update_bench [rows]` (default 500k).
+//! Each mark reads two table columns and one signal, like a scatter plot with a zoom signal.
+//! Recorded results, including the baseline before per-mark versions moved into a `SmallVec`,
+//! are in `plans/engine-evolution.md`.
+
+use std::time::{Duration, Instant};
+
+use vizir_core::{ColId, InputRef, Mark, Scene, SignalId, Table, TableData, TableId};
+
+/// Resident set size in bytes, where the platform exposes it (Linux `/proc`).
+fn resident_bytes() -> Option<usize> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * 4096)
+}
+
+#[derive(Debug)]
+struct Columns {
+    x: Vec<f64>,
+    y: Vec<f64>,
+}
+
+impl TableData for Columns {
+    fn row_count(&self) -> usize {
+        self.x.len()
+    }
+
+    fn f64(&self, row: usize, col: ColId) -> Option<f64> {
+        match col {
+            ColId(0) => self.x.get(row).copied(),
+            ColId(1) => self.y.get(row).copied(),
+            _ => None,
+        }
+    }
+}
+
+const REPEATS: usize = 5;
+
+fn best_of(mut f: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut diffs = 0;
+    for _ in 0..REPEATS {
+        let start = Instant::now();
+        diffs = f();
+        best = best.min(start.elapsed());
+    }
+    (best, diffs)
+}
+
+fn main() {
+    let rows: usize = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().expect("row count"))
+        .unwrap_or(500_000);
+
+    let table_id = TableId(1);
+    let zoom = SignalId(1);
+    let mut scene = Scene::new();
+    scene.insert_signal(zoom, 1.0_f64);
+    let mut table = Table::new(table_id);
+    table.set_row_keys((0..rows as u64).collect());
+    table.data = Some(Box::new(Columns {
+        x: (0..rows).map(|i| i as f64).collect(),
+        y: (0..rows).map(|i| (i % 100) as f64).collect(),
+    }));
+    scene.insert_table(table);
+
+    let x_col = InputRef::TableCol {
+        table: table_id,
+        col: ColId(0),
+    };
+    let y_col = InputRef::TableCol {
+        table: table_id,
+        col: ColId(1),
+    };
+    let zoom_dep = InputRef::Signal { signal: zoom };
+
+    let before = resident_bytes();
+    let start = Instant::now();
+    let entered = scene
+        .tick_table_rows(table_id, |id, _key, row| {
+            Mark::builder(id)
+                .x_compute([x_col, zoom_dep], move |ctx, _| {
+                    ctx.table_f64(table_id, row, ColId(0)).unwrap_or(0.0)
+                        * ctx.signal::<f64>(zoom).unwrap_or(1.0)
+                })
+                .y_compute([y_col], move |ctx, _| {
+                    ctx.table_f64(table_id, row, ColId(1)).unwrap_or(0.0)
+                })
+                .w_const(2.0)
+                .h_const(2.0)
+                .build()
+        })
+        .len();
+    let tick = start.elapsed();
+    let grown = before
+        .zip(resident_bytes())
+        .map(|(a, b)| b.saturating_sub(a));
+
+    println!("rows: {rows}");
+    println!("size_of::<Mark>(): {} bytes", size_of::<Mark>());
+    if let Some(bytes) = grown {
+        println!(
+            "resident growth over tick: {:.1} MiB ({} bytes/mark)",
+            bytes as f64 / (1024.0 * 1024.0),
+            bytes / rows.max(1)
+        );
+    }
+    println!("tick (enter {entered}): {tick:?}");
+
+    let mut z = 1.0_f64;
+    let (t, n) = best_of(|| {
+        z += 1.0;
+        scene.set_signal(zoom, z).unwrap();
+        scene.update().len()
+    });
+    println!("signal change ({n} updates): {t:?}");
+
+    let (t, n) = best_of(|| {
+        scene.set_table_column_changed(table_id, ColId(1));
+        scene.update().len()
+    });
+    println!("column change, values unchanged ({n} updates): {t:?}");
+
+    let (t, n) = best_of(|| scene.update().len());
+    println!("idle update ({n} updates): {t:?}");
+}
@@ -12,7 +12,7 @@ use alloc::vec::Vec;
 
 use hashbrown::hash_map::Entry;
 use hashbrown::{HashMap, HashSet};
-use vizir_core::{ColId, Scene, Table, TableId};
+use vizir_core::{ColId, Scene, TableId};
 
 use crate::Program;
 use crate::program::{ExecutionError, ProgramOutput};
@@ -196,14 +196,13 @@ fn required_input_columns(transforms: &[Transform]) -> HashMap<TableId, HashSet<
     out
 }
 
-fn upsert_frame_as_table(scene: &mut Scene, id: TableId, frame: TableFrame) {
+fn upsert_frame_as_table(scene: &mut Scene, id: TableId, mut frame: TableFrame) {
     match scene.tables.entry(id) {
         Entry::Occupied(mut e) => {
-            let Table { data, row_keys, .. } = frame.into_table(id);
+            let row_keys = core::mem::take(&mut frame.row_keys);
             let existing = e.get_mut();
-            existing.row_keys = row_keys;
-            existing.data = data;
-            existing.bump();
+            existing.data = frame.into_table(id).data;
+            existing.set_row_keys(row_keys);
         }
         Entry::Vacant(e) => {
             e.insert(frame.into_table(id));
@@ -251,7 +250,7 @@ mod tests {
 
         let mut scene = Scene::new();
         let mut t = Table::new(source_id);
-        t.row_keys = vec![10, 11, 12];
+        t.set_row_keys(vec![10, 11, 12]);
         t.data = Some(Box::new(TwoCols {
             a: vec![1.0, 2.0, 3.0],
             b: vec![3.0, 2.0, 1.0],
@@ -8,7 +8,7 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
-use vizir_core::{ColId, Table, TableData, TableId};
+use vizir_core::{ColId, F64Column, Table, TableData, TableId};
 
 /// Errors returned when building or using a [`TableFrame`].
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -49,7 +49,8 @@ impl TableFrame {
 
     /// Extract a numeric frame from a `vizir_core` table.
     ///
-    /// Missing values are represented as `NaN` in the output columns.
+    /// Missing values are represented as `NaN` in the output columns. Columns exposed via
+    /// [`TableData::f64_slice`] are copied in bulk.
     pub fn from_table(table: &Table, columns: Vec<ColId>) -> Result<Self, TableFrameError> {
         if columns.is_empty() {
             return Err(TableFrameError::EmptyColumns);
@@ -57,17 +58,25 @@ impl TableFrame {
         let Some(data) = table.data.as_deref() else {
             return Err(TableFrameError::MissingData);
         };
-        let n = table.row_keys.len();
+        let n = table.row_count();
         let mut cols = Vec::with_capacity(columns.len());
         for &col in &columns {
             let mut out = Vec::with_capacity(n);
-            for row in 0..n {
-                out.push(data.f64(row, col).unwrap_or(f64::NAN));
+            match F64Column::new(data, col) {
+                F64Column::Slice(values) => {
+                    out.extend_from_slice(&values[..n.min(values.len())]);
+                    out.resize(n, f64::NAN);
+                }
+                column @ F64Column::Cells { .. } => {
+                    for row in 0..n {
+                        out.push(column.get(row).unwrap_or(f64::NAN));
+                    }
+                }
             }
             cols.push(out);
         }
         Ok(Self {
-            row_keys: table.row_keys.clone(),
+            row_keys: table.row_keys().to_vec(),
             columns,
             data: cols,
         })
@@ -91,15 +100,12 @@ impl TableFrame {
 
     /// Converts this frame into a `vizir_core::Table` with an owned `TableData` accessor.
     pub fn into_table(self, id: TableId) -> Table {
-        Table {
-            id,
-            version: 1,
-            row_keys: self.row_keys,
-            data: Some(Box::new(FrameData {
-                columns: self.columns,
-                data: self.data,
-            })),
-        }
+        let mut table = Table::with_row_keys(id, self.row_keys);
+        table.data = Some(Box::new(FrameData {
+            columns: self.columns,
+            data: self.data,
+        }));
+        table
     }
 }
 
@@ -118,4 +124,9 @@ impl TableData for FrameData {
         let idx = self.columns.iter().position(|&c| c == col)?;
         self.data.get(idx)?.get(row).copied()
     }
+
+    fn f64_slice(&self, col: ColId) -> Option<&[f64]> {
+        let idx = self.columns.iter().position(|&c| c == col)?;
+        self.data.get(idx).map(Vec::as_slice)
+    }
 }